@@ -0,0 +1,33 @@
+//! Whole configs through everything that reads them: `cargo fuzz run config`.
+//! Nothing may panic, and printing a parsed config must give back its source.
+
+#![no_main]
+
+use hyprland_config::{format::FormatOptions, lint::LintLevels, Config};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(config) = input.parse::<Config>() {
+        assert_eq!(config.to_string(), input);
+    }
+
+    let mut config = Config::parse_recovering(input);
+    assert_eq!(config.to_string().trim_end(), input.trim_end());
+
+    let _ = config.validate();
+    let _ = config.lint(&LintLevels::default(), |_| true);
+    let _ = config.resolve();
+    let _ = config.submaps();
+    let _ = config.beziers();
+    let _ = config.animations();
+
+    let formatted = config.format(&FormatOptions::default()).to_string();
+    assert_eq!(
+        Config::parse_recovering(&formatted)
+            .format(&FormatOptions::default())
+            .to_string(),
+        formatted
+    );
+
+    config.migrate();
+});
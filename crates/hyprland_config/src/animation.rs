@@ -0,0 +1,441 @@
+//! `bezier` and `animation` keywords. Animations form a tree, an animation that isn't set
+//! inherits from its parent, e.g. `windowsIn` from `windows`.
+
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+
+use crate::{
+    keyword::Keyword,
+    util::{Float, FromValue},
+    variable::Bool,
+    Config, ConfigElement,
+};
+
+/// Steps of the bisection that finds the curve parameter for an `x`
+const BISECTION_STEPS: usize = 32;
+
+/// `bezier = NAME, X1, Y1, X2, Y2`, a cubic bezier curve from (0, 0) to (1, 1)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bezier {
+    pub name: String,
+    pub x1: Float,
+    pub y1: Float,
+    pub x2: Float,
+    pub y2: Float,
+}
+
+impl Display for Bezier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let keyword = self.to_keyword();
+
+        write!(f, "{} = {}", keyword.name, keyword.value)
+    }
+}
+
+impl Bezier {
+    /// Name of the curve Hyprland always defines
+    pub const DEFAULT: &'static str = "default";
+
+    /// The curve Hyprland defines as `default`
+    pub fn default_curve() -> Self {
+        Self {
+            name: Self::DEFAULT.to_string(),
+            x1: 0.0,
+            y1: 0.75,
+            x2: 0.15,
+            y2: 1.0,
+        }
+    }
+
+    pub fn from_keyword(keyword: &Keyword) -> Option<eyre::Result<Self>> {
+        match keyword.name == "bezier" {
+            true => Some(Self::from_value(&keyword.value)),
+            false => None,
+        }
+    }
+
+    pub fn to_keyword(&self) -> Keyword {
+        Keyword::new(
+            "bezier",
+            format!(
+                "{}, {}, {}, {}, {}",
+                self.name, self.x1, self.y1, self.x2, self.y2
+            ),
+        )
+    }
+
+    /// Point of the curve at the parameter `t`, from 0 to 1
+    pub fn point(&self, t: Float) -> (Float, Float) {
+        let cubic = |p1: Float, p2: Float| {
+            let u = 1.0 - t;
+            3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+        };
+
+        (cubic(self.x1, self.x2), cubic(self.y1, self.y2))
+    }
+
+    /// Progress of the animation after the fraction `x` of its duration. `x` is clamped to
+    /// 0 to 1, the result can leave that range for curves that overshoot
+    pub fn eval(&self, x: Float) -> Float {
+        let x = x.clamp(0.0, 1.0);
+        let (mut low, mut high) = (0.0, 1.0);
+
+        // x(t) only grows while both control points are within 0 to 1, which is validated
+        for _ in 0..BISECTION_STEPS {
+            let t = (low + high) / 2.0;
+
+            match self.point(t).0 < x {
+                true => low = t,
+                false => high = t,
+            }
+        }
+
+        self.point((low + high) / 2.0).1
+    }
+
+    fn from_value(value: &str) -> eyre::Result<Self> {
+        let fields = value.split(',').map(str::trim).collect_vec();
+
+        let [name, points @ ..] = fields.as_slice() else {
+            unreachable!("split returns at least one field")
+        };
+        let [x1, y1, x2, y2] = points else {
+            return Err(eyre::eyre!(
+                "Expected `NAME, X1, Y1, X2, Y2`, got `{value}`"
+            ));
+        };
+
+        if name.is_empty() {
+            return Err(eyre::eyre!("Bezier has no name"));
+        }
+
+        let point = |value: &str| {
+            Float::from_value(value).ok_or_else(|| eyre::eyre!("Invalid point `{value}`"))
+        };
+        let x = |value: &str| {
+            point(value).and_then(|x| match (0.0..=1.0).contains(&x) {
+                true => Ok(x),
+                false => Err(eyre::eyre!(
+                    "X coordinates must be between 0 and 1, got `{x}`"
+                )),
+            })
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            x1: x(x1)?,
+            y1: point(y1)?,
+            x2: x(x2)?,
+            y2: point(y2)?,
+        })
+    }
+}
+
+macro_rules! animation_names {
+    (@parent) => { None };
+    (@parent $parent:ident) => { Some(AnimationName::$parent) };
+    ($($variant:ident => $name:literal $(in $parent:ident)?),* $(,)?) => {
+        /// Animation of the tree, see [AnimationName::parent]
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum AnimationName {
+            $($variant,)*
+        }
+
+        impl Display for AnimationName {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", match self {
+                    $(AnimationName::$variant => $name,)*
+                })
+            }
+        }
+
+        impl AnimationName {
+            pub const ALL: &'static [AnimationName] = &[$(AnimationName::$variant,)*];
+
+            pub fn from_value(value: &str) -> eyre::Result<Self> {
+                match value.trim() {
+                    $($name => Ok(AnimationName::$variant),)*
+                    name => Err(eyre::eyre!("Unknown animation `{name}`")),
+                }
+            }
+
+            /// Animation this one inherits from when it isn't set
+            pub fn parent(&self) -> Option<AnimationName> {
+                match self {
+                    $(AnimationName::$variant => animation_names!(@parent $($parent)?),)*
+                }
+            }
+        }
+    };
+}
+
+animation_names! {
+    Windows => "windows",
+    WindowsIn => "windowsIn" in Windows,
+    WindowsOut => "windowsOut" in Windows,
+    WindowsMove => "windowsMove" in Windows,
+    Fade => "fade",
+    FadeIn => "fadeIn" in Fade,
+    FadeOut => "fadeOut" in Fade,
+    FadeSwitch => "fadeSwitch" in Fade,
+    FadeShadow => "fadeShadow" in Fade,
+    FadeDim => "fadeDim" in Fade,
+    Border => "border",
+    BorderAngle => "borderangle",
+    Workspaces => "workspaces",
+    SpecialWorkspace => "specialWorkspace" in Workspaces,
+}
+
+impl AnimationName {
+    /// The animation and its ancestors, closest first
+    pub fn ancestors(&self) -> impl Iterator<Item = AnimationName> {
+        std::iter::successors(Some(*self), AnimationName::parent)
+    }
+
+    /// Root of the subtree, which decides the styles the animation accepts
+    fn root(&self) -> AnimationName {
+        self.ancestors().last().unwrap_or(*self)
+    }
+}
+
+/// Style argument of an `animation`, percentages are stored without the `%`
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnimationStyle {
+    /// `slide`, windows and workspaces
+    Slide,
+    /// `popin [N%]`, windows, scaled from N% of their size
+    Popin(Option<Float>),
+    /// `slidevert`, workspaces
+    SlideVert,
+    /// `fade`, workspaces
+    Fade,
+    /// `slidefade [N%]`, workspaces
+    SlideFade(Option<Float>),
+    /// `slidefadevert [N%]`, workspaces
+    SlideFadeVert(Option<Float>),
+    /// `once`, borderangle
+    Once,
+    /// `loop`, borderangle
+    Loop,
+}
+
+impl Display for AnimationStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (name, percent) = match self {
+            AnimationStyle::Slide => ("slide", None),
+            AnimationStyle::Popin(percent) => ("popin", *percent),
+            AnimationStyle::SlideVert => ("slidevert", None),
+            AnimationStyle::Fade => ("fade", None),
+            AnimationStyle::SlideFade(percent) => ("slidefade", *percent),
+            AnimationStyle::SlideFadeVert(percent) => ("slidefadevert", *percent),
+            AnimationStyle::Once => ("once", None),
+            AnimationStyle::Loop => ("loop", None),
+        };
+
+        match percent {
+            Some(percent) => write!(f, "{name} {percent}%"),
+            None => write!(f, "{name}"),
+        }
+    }
+}
+
+impl AnimationStyle {
+    /// Style of `animation`, checking that the animation accepts it
+    pub fn from_value(value: &str, animation: AnimationName) -> eyre::Result<Self> {
+        let (name, percent) = match value.trim().split_once(char::is_whitespace) {
+            Some((name, percent)) => (name, Some(percent.trim())),
+            None => (value.trim(), None),
+        };
+
+        let percent = percent
+            .map(|percent| {
+                percent
+                    .strip_suffix('%')
+                    .and_then(Float::from_value)
+                    .filter(|percent| (0.0..=100.0).contains(percent))
+                    .ok_or_else(|| eyre::eyre!("Expected a percentage like `80%`, got `{percent}`"))
+            })
+            .transpose()?;
+
+        let style = match (name, percent) {
+            ("slide", None) => AnimationStyle::Slide,
+            ("popin", percent) => AnimationStyle::Popin(percent),
+            ("slidevert", None) => AnimationStyle::SlideVert,
+            ("fade", None) => AnimationStyle::Fade,
+            ("slidefade", percent) => AnimationStyle::SlideFade(percent),
+            ("slidefadevert", percent) => AnimationStyle::SlideFadeVert(percent),
+            ("once", None) => AnimationStyle::Once,
+            ("loop", None) => AnimationStyle::Loop,
+            ("slide" | "slidevert" | "fade" | "once" | "loop", Some(_)) => {
+                return Err(eyre::eyre!("Style `{name}` takes no percentage"))
+            }
+            _ => return Err(eyre::eyre!("Unknown animation style `{name}`")),
+        };
+
+        if !style.applies_to(animation) {
+            return Err(eyre::eyre!(
+                "Style `{name}` doesn't apply to the `{animation}` animation"
+            ));
+        }
+
+        Ok(style)
+    }
+
+    /// Whether `animation` accepts the style
+    pub fn applies_to(&self, animation: AnimationName) -> bool {
+        match self {
+            AnimationStyle::Slide => matches!(
+                animation.root(),
+                AnimationName::Windows | AnimationName::Workspaces
+            ),
+            AnimationStyle::Popin(_) => animation.root() == AnimationName::Windows,
+            AnimationStyle::SlideVert
+            | AnimationStyle::Fade
+            | AnimationStyle::SlideFade(_)
+            | AnimationStyle::SlideFadeVert(_) => animation.root() == AnimationName::Workspaces,
+            AnimationStyle::Once | AnimationStyle::Loop => animation == AnimationName::BorderAngle,
+        }
+    }
+}
+
+/// `animation = NAME, ONOFF, SPEED, CURVE[, STYLE]` or `animation = NAME, 0`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Animation {
+    pub name: AnimationName,
+    pub enabled: bool,
+    /// Duration in deciseconds, `None` if only the animation is turned off
+    pub speed: Option<Float>,
+    /// Name of a [Bezier], `None` if only the animation is turned off
+    pub curve: Option<String>,
+    pub style: Option<AnimationStyle>,
+}
+
+impl Display for Animation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let keyword = self.to_keyword();
+
+        write!(f, "{} = {}", keyword.name, keyword.value)
+    }
+}
+
+impl Animation {
+    pub fn from_keyword(keyword: &Keyword) -> Option<eyre::Result<Self>> {
+        match keyword.name == "animation" {
+            true => Some(Self::from_value(&keyword.value)),
+            false => None,
+        }
+    }
+
+    pub fn to_keyword(&self) -> Keyword {
+        let mut fields = vec![self.name.to_string(), (self.enabled as u8).to_string()];
+
+        if let (Some(speed), Some(curve)) = (self.speed, &self.curve) {
+            fields.extend([speed.to_string(), curve.clone()]);
+            fields.extend(self.style.map(|style| style.to_string()));
+        }
+
+        Keyword::new("animation", fields.join(", "))
+    }
+
+    /// Duration of the animation in milliseconds, `None` if it is turned off
+    pub fn duration_ms(&self) -> Option<Float> {
+        match self.enabled {
+            true => self.speed.map(|speed| speed * 100.0),
+            false => None,
+        }
+    }
+
+    fn from_value(value: &str) -> eyre::Result<Self> {
+        let fields = value.splitn(5, ',').map(str::trim).collect_vec();
+
+        let (name, enabled, rest) = match fields.as_slice() {
+            [name, enabled, rest @ ..] => (name, enabled, rest),
+            _ => {
+                return Err(eyre::eyre!(
+                    "Expected `NAME, ONOFF, SPEED, CURVE[, STYLE]`, got `{value}`"
+                ))
+            }
+        };
+
+        let name = AnimationName::from_value(name)?;
+        let enabled = Bool::from_value(enabled).map(bool::from).ok_or_else(|| {
+            eyre::eyre!("Expected 0 or 1 to turn `{name}` on or off, got `{enabled}`")
+        })?;
+
+        let (speed, curve, style) = match rest {
+            [] if !enabled => (None, None, None),
+            [speed, curve, style @ ..] => {
+                let speed = Float::from_value(speed)
+                    .filter(|speed| *speed > 0.0)
+                    .ok_or_else(|| eyre::eyre!("Expected a positive speed, got `{speed}`"))?;
+                let style = style
+                    .first()
+                    .map(|style| AnimationStyle::from_value(style, name))
+                    .transpose()?;
+
+                (Some(speed), Some(curve.to_string()), style)
+            }
+            _ => {
+                return Err(eyre::eyre!(
+                    "Expected `NAME, ONOFF, SPEED, CURVE[, STYLE]`, got `{value}`"
+                ))
+            }
+        };
+
+        Ok(Self {
+            name,
+            enabled,
+            speed,
+            curve,
+            style,
+        })
+    }
+}
+
+/// Animation that applies to `name`: the last one set for it, or else the one its closest
+/// ancestor inherits. `None` if neither is set, Hyprland then uses its built-in defaults
+pub fn effective_animation(animations: &[Animation], name: AnimationName) -> Option<&Animation> {
+    name.ancestors().find_map(|name| {
+        animations
+            .iter()
+            .rev()
+            .find(|animation| animation.name == name)
+    })
+}
+
+impl Config {
+    /// `bezier` and `animation` lines at the top level and in `animations { ... }`, in order
+    fn animation_keywords(&self) -> impl Iterator<Item = &Keyword> {
+        self.nodes().iter().flat_map(|node| match &node.element {
+            ConfigElement::Keyword(keyword) => vec![keyword],
+            ConfigElement::Section(section) if section.name == "animations" => section
+                .elements
+                .iter()
+                .filter_map(|node| match &node.element {
+                    ConfigElement::Keyword(keyword) => Some(keyword),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        })
+    }
+
+    /// `bezier` curves in order, without the built-in `default`
+    pub fn beziers(&self) -> eyre::Result<Vec<Bezier>> {
+        self.animation_keywords()
+            .filter_map(Bezier::from_keyword)
+            .collect()
+    }
+
+    /// `animation` lines in order, see [effective_animation] for which one applies
+    pub fn animations(&self) -> eyre::Result<Vec<Animation>> {
+        self.animation_keywords()
+            .filter_map(Animation::from_keyword)
+            .collect()
+    }
+}
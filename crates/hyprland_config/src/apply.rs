@@ -0,0 +1,207 @@
+//! Planning how to bring a running Hyprland in line with a config. Options whose live value
+//! differs from the config become `keyword` commands, everything Hyprland only reads on reload
+//! or at startup is reported as skipped.
+
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+
+use crate::{
+    schema::{OptionType, Schema},
+    util::{Float, FromValue, Int},
+    variable::{Bool, Color},
+    Config,
+};
+
+/// Keywords that are only read when Hyprland starts
+const STARTUP_KEYWORDS: &[&str] = &["exec-once", "source", "env"];
+
+/// How far two floats may be apart to count as the same value
+const FLOAT_TOLERANCE: f64 = 1e-4;
+
+/// Value of an option in the running Hyprland, as returned by `hyprctl -j getoption`
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiveOption {
+    pub int: i64,
+    pub float: f64,
+    pub str: String,
+}
+
+impl LiveOption {
+    /// Whether the live value is `value`, `None` if values of this type can't be compared
+    pub fn matches(&self, ty: OptionType, value: &str) -> Option<bool> {
+        match ty {
+            OptionType::Int => Int::from_value(value).map(|value| value as i64 == self.int),
+            OptionType::Bool => {
+                Bool::from_value(value).map(|value| bool::from(value) == (self.int != 0))
+            }
+            OptionType::Float => Float::from_value(value)
+                .map(|value| (value as f64 - self.float).abs() < FLOAT_TOLERANCE),
+            OptionType::Color => {
+                Color::from_value(value).map(|color| color.argb() as i64 == self.int)
+            }
+            OptionType::String | OptionType::Enum => Some(value.trim() == self.string()),
+            OptionType::Gradient | OptionType::Vec2 => None,
+        }
+    }
+
+    /// Live value written the way the config would, `None` if it isn't known for this type
+    pub fn display(&self, ty: OptionType) -> Option<String> {
+        match ty {
+            OptionType::Int => Some(self.int.to_string()),
+            OptionType::Bool => Some((self.int != 0).to_string()),
+            OptionType::Float => Some(self.float.to_string()),
+            OptionType::Color => Some(Color::legacy(self.int as u32).to_string()),
+            OptionType::String | OptionType::Enum => Some(self.string().to_string()),
+            OptionType::Gradient | OptionType::Vec2 => None,
+        }
+    }
+
+    /// Hyprland reports empty strings as `[[EMPTY]]`
+    fn string(&self) -> &str {
+        match self.str.as_str() {
+            "[[EMPTY]]" => "",
+            str => str,
+        }
+    }
+}
+
+/// Option that is set to a new value
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptionChange {
+    pub path: String,
+    pub value: String,
+    /// Live value, `None` if it isn't known
+    pub current: Option<String>,
+}
+
+impl OptionChange {
+    /// `keyword path value`
+    pub fn command(&self) -> String {
+        format!("keyword {} {}", self.path, self.value)
+    }
+}
+
+impl Display for OptionChange {
+    /// `general:gaps_in: 5 -> 10` or `general:gaps_in: 10`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.current {
+            Some(current) => write!(f, "{}: {current} -> {}", self.path, self.value),
+            None => write!(f, "{}: {}", self.path, self.value),
+        }
+    }
+}
+
+/// Part of the config that can't be applied at runtime
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Skipped {
+    /// Option path or keyword name
+    pub what: String,
+    pub reason: String,
+}
+
+impl Display for Skipped {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.what, self.reason)
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApplyPlan {
+    /// In the order the options are first set in the config
+    pub changes: Vec<OptionChange>,
+    pub skipped: Vec<Skipped>,
+}
+
+impl ApplyPlan {
+    /// All changes as one `[[BATCH]]` request, without the prefix
+    pub fn batch(&self) -> String {
+        self.changes.iter().map(OptionChange::command).join(";")
+    }
+}
+
+impl Config {
+    /// Compares the options of the config with the running Hyprland. `live` looks up the current
+    /// value of an option and returns `None` if the running Hyprland doesn't know it.
+    ///
+    /// Options are compared after substituting variables, and if an option is set several times
+    /// only the last value counts. `device:NAME:...` options can't be looked up and are always
+    /// set. Keywords other than options, like `bind` or `monitor`, are reported as skipped
+    pub fn apply_plan(&self, live: impl Fn(&str) -> Option<LiveOption>) -> ApplyPlan {
+        let config = self.resolve().config;
+        let mut plan = ApplyPlan::default();
+
+        let mut options: Vec<(String, String)> = vec![];
+        for (path, keyword) in config.options() {
+            match options.iter_mut().find(|(other, _)| *other == path) {
+                Some((_, value)) => *value = keyword.value.clone(),
+                None => options.push((path, keyword.value.clone())),
+            }
+        }
+
+        for (path, value) in options {
+            if value.contains(';') {
+                plan.skipped.push(Skipped {
+                    what: path,
+                    reason: "values containing `;` can't be sent in a batch".to_string(),
+                });
+                continue;
+            }
+
+            let ty = Schema::get().option(&path).map(|option| option.ty);
+
+            if path.starts_with("device:") {
+                plan.changes.push(OptionChange {
+                    path,
+                    value,
+                    current: None,
+                });
+                continue;
+            }
+
+            let Some(current) = live(&path) else {
+                plan.skipped.push(Skipped {
+                    what: path,
+                    reason: "not an option of the running Hyprland".to_string(),
+                });
+                continue;
+            };
+
+            let unchanged = ty.and_then(|ty| current.matches(ty, &value));
+            if unchanged != Some(true) {
+                plan.changes.push(OptionChange {
+                    path,
+                    value,
+                    current: ty.and_then(|ty| current.display(ty)),
+                });
+            }
+        }
+
+        let keywords = config
+            .keywords()
+            .filter(|keyword| !keyword.name.contains(':'))
+            .counts_by(|keyword| keyword.name.clone());
+
+        for (name, count) in keywords.into_iter().sorted() {
+            let reason = match STARTUP_KEYWORDS.contains(&name.as_str()) {
+                true => "only read when Hyprland starts",
+                false => "only applied by `hyprr reload`",
+            };
+            let what = match count {
+                1 => name,
+                count => format!("{name} ({count} lines)"),
+            };
+
+            plan.skipped.push(Skipped {
+                what,
+                reason: reason.to_string(),
+            });
+        }
+
+        plan
+    }
+}
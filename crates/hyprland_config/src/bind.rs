@@ -0,0 +1,358 @@
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while, take_while1},
+    character::complete::{digit1, space0},
+    combinator::{all_consuming, map, map_res, value},
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+use crate::{keyword::Keyword, util::Parse, variable::ModifierSet, Config, ConfigElement};
+
+/// Dispatchers that can be bound, layout specific ones like `pseudo` and `togglesplit` included
+pub const DISPATCHERS: &[&str] = &[
+    "exec",
+    "execr",
+    "pass",
+    "killactive",
+    "closewindow",
+    "workspace",
+    "movetoworkspace",
+    "movetoworkspacesilent",
+    "togglefloating",
+    "fullscreen",
+    "fakefullscreen",
+    "dpms",
+    "pin",
+    "movefocus",
+    "movewindow",
+    "swapwindow",
+    "centerwindow",
+    "resizeactive",
+    "moveactive",
+    "resizewindowpixel",
+    "movewindowpixel",
+    "cyclenext",
+    "swapnext",
+    "focuswindow",
+    "focusmonitor",
+    "splitratio",
+    "toggleopaque",
+    "movecursortocorner",
+    "movecursor",
+    "workspaceopt",
+    "renameworkspace",
+    "exit",
+    "forcerendererreload",
+    "movecurrentworkspacetomonitor",
+    "moveworkspacetomonitor",
+    "swapactiveworkspaces",
+    "bringactivetotop",
+    "togglespecialworkspace",
+    "focusurgentorlast",
+    "focuscurrentorlast",
+    "togglegroup",
+    "changegroupactive",
+    "lockgroups",
+    "moveintogroup",
+    "moveoutofgroup",
+    "global",
+    "submap",
+    "pseudo",
+    "togglesplit",
+    "layoutmsg",
+];
+
+/// `bind[flags] = MODS, key, dispatcher, arg`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bind {
+    pub flags: BindFlags,
+    pub mods: ModifierSet,
+    pub key: Key,
+    pub dispatcher: String,
+    /// `None` if the line has no argument field at all (`bindm = SUPER, mouse:272, movewindow`),
+    /// `Some("")` for an empty trailing one (`bind = SUPER, C, killactive,`)
+    pub arg: Option<String>,
+}
+
+impl Display for Bind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "bind{} = {}, {}, {}",
+            self.flags, self.mods, self.key, self.dispatcher
+        )?;
+
+        match &self.arg {
+            Some(arg) if arg.is_empty() => write!(f, ","),
+            Some(arg) => write!(f, ", {arg}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Parse for Bind {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        map_res(
+            tuple((
+                preceded(tag("bind"), BindFlags::parse),
+                tuple((space0, tag("="), space0)),
+                take_while(|c: char| c != '\n'),
+            )),
+            |(flags, _, value): (BindFlags, _, &str)| Self::from_value(flags, value),
+        )(input)
+    }
+}
+
+impl Bind {
+    pub fn from_keyword(keyword: &Keyword) -> Option<eyre::Result<Self>> {
+        let flags = keyword.name.strip_prefix("bind")?;
+        let flags = match all_consuming(BindFlags::parse)(flags) {
+            Ok((_, flags)) => flags,
+            Err(_) => return None,
+        };
+
+        Some(Self::from_value(flags, &keyword.value))
+    }
+
+    pub fn to_keyword(&self) -> Keyword {
+        let line = self.to_string();
+        let (_, value) = line.split_once(" = ").unwrap();
+
+        Keyword::new(format!("bind{}", self.flags), value)
+    }
+
+    fn from_value(flags: BindFlags, value: &str) -> eyre::Result<Self> {
+        let fields = value.splitn(4, ',').map(str::trim).collect_vec();
+
+        let [mods, key, dispatcher, rest @ ..] = fields.as_slice() else {
+            return Err(eyre::eyre!(
+                "Expected `MODS, key, dispatcher[, arg]`, got `{value}`"
+            ));
+        };
+
+        Ok(Self {
+            flags,
+            mods: ModifierSet::from_value(mods)?,
+            key: Key::from_value(key)?,
+            dispatcher: dispatcher.to_string(),
+            arg: rest.first().map(ToString::to_string),
+        })
+    }
+}
+
+/// `unbind = MODS, key`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Unbind {
+    pub mods: ModifierSet,
+    pub key: Key,
+}
+
+impl Display for Unbind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unbind = {}, {}", self.mods, self.key)
+    }
+}
+
+impl Unbind {
+    pub fn from_keyword(keyword: &Keyword) -> Option<eyre::Result<Self>> {
+        if keyword.name != "unbind" {
+            return None;
+        }
+
+        Some(match keyword.value.split_once(',') {
+            Some((mods, key)) => ModifierSet::from_value(mods.trim()).and_then(|mods| {
+                Ok(Self {
+                    mods,
+                    key: Key::from_value(key.trim())?,
+                })
+            }),
+            None => Err(eyre::eyre!("Expected `MODS, key`, got `{}`", keyword.value)),
+        })
+    }
+}
+
+/// Binds of a submap. Binds outside of `submap = name` ... `submap = reset` blocks
+/// belong to the global submap, which has no name
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Submap {
+    pub name: Option<String>,
+    pub binds: Vec<Bind>,
+    pub unbinds: Vec<Unbind>,
+}
+
+impl Config {
+    /// Binds of the config grouped by submaps, the global submap is always first
+    pub fn submaps(&self) -> eyre::Result<Vec<Submap>> {
+        let mut submaps = vec![Submap::default()];
+        let mut current = 0;
+
+        for node in self.nodes() {
+            let ConfigElement::Keyword(keyword) = &node.element else {
+                continue;
+            };
+
+            if keyword.name == "submap" {
+                let name = keyword.value.trim();
+
+                current = match name {
+                    "reset" | "" => 0,
+                    name => match submaps
+                        .iter()
+                        .position(|submap| submap.name.as_deref() == Some(name))
+                    {
+                        Some(index) => index,
+                        None => {
+                            submaps.push(Submap {
+                                name: Some(name.to_string()),
+                                ..Default::default()
+                            });
+                            submaps.len() - 1
+                        }
+                    },
+                };
+            } else if let Some(bind) = Bind::from_keyword(keyword) {
+                submaps[current].binds.push(bind?);
+            } else if let Some(unbind) = Unbind::from_keyword(keyword) {
+                submaps[current].unbinds.push(unbind?);
+            }
+        }
+
+        Ok(submaps)
+    }
+}
+
+/// Letters after `bind`, e.g. `binde`, `bindlr`
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindFlags {
+    /// `l` - works when an input inhibitor (e.g. a lockscreen) is active
+    pub locked: bool,
+    /// `r` - triggers on release of the key
+    pub release: bool,
+    /// `e` - repeats when held
+    pub repeat: bool,
+    /// `n` - non-consuming, key/mouse events are passed to the active window as well
+    pub non_consuming: bool,
+    /// `m` - mouse bind
+    pub mouse: bool,
+    /// `t` - transparent, can't be shadowed by other binds
+    pub transparent: bool,
+    /// `i` - ignores modifiers
+    pub ignore_mods: bool,
+}
+
+impl Display for BindFlags {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        [
+            (self.locked, 'l'),
+            (self.release, 'r'),
+            (self.repeat, 'e'),
+            (self.non_consuming, 'n'),
+            (self.mouse, 'm'),
+            (self.transparent, 't'),
+            (self.ignore_mods, 'i'),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .try_for_each(|(_, flag)| write!(f, "{flag}"))
+    }
+}
+
+impl Parse for BindFlags {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        map_res(
+            take_while(|c: char| "lrenmti".contains(c)),
+            |flags: &str| {
+                flags
+                    .chars()
+                    .try_fold(BindFlags::default(), |mut acc, flag| {
+                        let set = match flag {
+                            'l' => &mut acc.locked,
+                            'r' => &mut acc.release,
+                            'e' => &mut acc.repeat,
+                            'n' => &mut acc.non_consuming,
+                            'm' => &mut acc.mouse,
+                            't' => &mut acc.transparent,
+                            _ => &mut acc.ignore_mods,
+                        };
+
+                        match *set {
+                            true => Err(eyre::eyre!("Duplicate bind flag `{flag}`")),
+                            false => {
+                                *set = true;
+                                Ok(acc)
+                            }
+                        }
+                    })
+            },
+        )(input)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Key {
+    /// xkb keysym name, `Q`, `Return`, `XF86AudioRaiseVolume`
+    Keysym(String),
+    /// `code:NN`, raw keycode
+    Code(u32),
+    /// `mouse:NNN`, mouse button, only for `bindm`
+    Mouse(u32),
+    /// `mouse_down`
+    MouseDown,
+    /// `mouse_up`
+    MouseUp,
+    /// `mouse_left`
+    MouseLeft,
+    /// `mouse_right`
+    MouseRight,
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Key::Keysym(keysym) => write!(f, "{keysym}"),
+            Key::Code(code) => write!(f, "code:{code}"),
+            Key::Mouse(button) => write!(f, "mouse:{button}"),
+            Key::MouseDown => write!(f, "mouse_down"),
+            Key::MouseUp => write!(f, "mouse_up"),
+            Key::MouseLeft => write!(f, "mouse_left"),
+            Key::MouseRight => write!(f, "mouse_right"),
+        }
+    }
+}
+
+impl Parse for Key {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        let number = || map_res(digit1, |s: &str| s.parse::<u32>());
+
+        alt((
+            map(preceded(tag("code:"), number()), Key::Code),
+            map(preceded(tag("mouse:"), number()), Key::Mouse),
+            value(Key::MouseDown, tag("mouse_down")),
+            value(Key::MouseUp, tag("mouse_up")),
+            value(Key::MouseLeft, tag("mouse_left")),
+            value(Key::MouseRight, tag("mouse_right")),
+            map(
+                take_while1(|c: char| !c.is_whitespace() && c != ','),
+                |keysym: &str| Key::Keysym(keysym.to_string()),
+            ),
+        ))(input)
+    }
+}
+
+impl Key {
+    pub fn from_value(value: &str) -> eyre::Result<Self> {
+        match all_consuming(Key::parse)(value.trim()) {
+            Ok((_, key)) => Ok(key),
+            Err(_) => Err(eyre::eyre!("Invalid key `{value}`")),
+        }
+    }
+}
@@ -2,17 +2,16 @@ use std::fmt::{Display, Formatter};
 
 use nom::{
     branch::alt,
-    bytes::{complete::tag, streaming::take_until},
-    character::complete::not_line_ending,
-    combinator::{map, map_res},
-    multi::many1,
-    sequence::{preceded, tuple},
+    bytes::complete::{tag, take_till, take_while1},
+    combinator::{map, verify},
+    sequence::preceded,
     IResult,
 };
 
 use crate::util::Parse;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Comment {
     OneLine(OneLineComment),
     MultiLine(MultiLineComment),
@@ -40,7 +39,8 @@ impl Parse for Comment {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OneLineComment(pub String);
 
 impl Display for OneLineComment {
@@ -50,14 +50,20 @@ impl Display for OneLineComment {
 }
 
 impl Parse for OneLineComment {
+    /// `#` up to the end of the line, without the `\r` of a `\r\n` line ending
     fn parse(input: &str) -> IResult<&str, Self> {
-        map(preceded(tag("#"), not_line_ending), |s: &str| {
-            Self(format!("#{s}"))
-        })(input)
+        let (rest, line) = preceded(tag("#"), take_till(|c: char| c == '\n'))(input)?;
+        let comment = match rest.is_empty() {
+            true => line,
+            false => line.strip_suffix('\r').unwrap_or(line),
+        };
+
+        Ok((&input[1 + comment.len()..], Self(format!("#{comment}"))))
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MultiLineComment(pub String);
 
 impl Display for MultiLineComment {
@@ -67,21 +73,32 @@ impl Display for MultiLineComment {
 }
 
 impl Parse for MultiLineComment {
+    /// `##` ... `##`, closed by the first run of exactly as many `#` that ends a line. So
+    /// `## note` lines are one-line comments instead of swallowing the lines up to the next
+    /// `##` of the file
     fn parse(input: &str) -> IResult<&str, Self> {
-        map_res(
-            tuple((many1(tag("##")), take_until("##"), many1(tag("##")))),
-            |(start, middle, end): (Vec<&str>, &str, Vec<&str>)| match start.len() == end.len() {
-                true => Ok(Self(format!(
-                    "{}{}{}",
-                    start.join(""),
-                    middle,
-                    end.join("")
-                ))),
-                false => Err(nom::Err::Error(nom::error::Error::new(
-                    input,
-                    nom::error::ErrorKind::Tag,
-                ))),
-            },
-        )(input)
+        let (body, fence) = verify(take_while1(|c: char| c == '#'), |fence: &str| {
+            fence.len() > 1
+        })(input)?;
+
+        let mut offset = input.len() - body.len();
+        for line in body.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\r', '\n']);
+            offset += line.len();
+
+            let closed = content
+                .strip_suffix(fence)
+                .is_some_and(|before| !before.ends_with('#'));
+            if closed {
+                let end = offset - (line.len() - content.len());
+
+                return Ok((&input[end..], Self(input[..end].to_string())));
+            }
+        }
+
+        Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Tag,
+        )))
     }
 }
@@ -0,0 +1,339 @@
+//! Lossless concrete syntax tree. Every [Node] keeps its byte [Span] in the source it was parsed
+//! from together with the surrounding [Trivia], so printing a config gives back the exact input
+//! and editing a single node only changes the line(s) it covers.
+
+use std::fmt::{Display, Formatter};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while, take_while1},
+    character::complete::space0,
+    combinator::{eof, map, opt},
+    error::{Error, ErrorKind},
+    sequence::tuple,
+    IResult,
+};
+
+use crate::{
+    comment::{Comment, OneLineComment},
+    keyword::Keyword,
+    section::Section,
+    util::Parse,
+    variable::Bool,
+    ConfigElement,
+};
+
+/// Byte range in the source a node was parsed from
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn slice<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start..self.end]
+    }
+}
+
+/// Whitespace and comments around a node
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trivia {
+    /// Whitespace before the node
+    pub indent: String,
+    /// Whitespace after the node, up to the inline comment or the end of the line
+    pub trailing: String,
+    /// `# ...` after the node on the same line
+    pub comment: Option<OneLineComment>,
+    /// `\n`, `\r\n` or nothing at the end of the file
+    pub line_ending: String,
+}
+
+impl Trivia {
+    pub fn new(indent: impl Into<String>) -> Self {
+        Self {
+            indent: indent.into(),
+            line_ending: "\n".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Writes everything that comes after the node
+    pub(crate) fn fmt_end(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.trailing)?;
+
+        if let Some(comment) = &self.comment {
+            write!(f, "{comment}")?;
+        }
+
+        write!(f, "{}", self.line_ending)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    /// Span of the whole node, from the indentation up to and including the line ending.
+    /// Refers to the source the node was parsed from and isn't updated by edits
+    pub span: Span,
+    pub trivia: Trivia,
+    pub element: ConfigElement,
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.trivia.indent, self.element)?;
+        self.trivia.fmt_end(f)
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        parse_node(input, input, false)
+    }
+}
+
+impl Node {
+    /// New node on its own line, without a span
+    pub fn new(indent: impl Into<String>, element: ConfigElement) -> Self {
+        Self {
+            span: Span::default(),
+            trivia: Trivia::new(indent),
+            element,
+        }
+    }
+
+    /// Byte offset of the element itself, after the indentation
+    pub fn element_start(&self) -> usize {
+        self.span.start + self.trivia.indent.len()
+    }
+}
+
+fn offset(source: &str, input: &str) -> usize {
+    source.len() - input.len()
+}
+
+fn line_ending(input: &str) -> IResult<&str, &str> {
+    alt((tag("\r\n"), tag("\n"), eof))(input)
+}
+
+/// Spaces, tabs and stray `\r` that aren't part of a `\r\n` line ending
+fn trailing(input: &str) -> IResult<&str, &str> {
+    let end = input
+        .char_indices()
+        .find(|&(i, c)| match c {
+            ' ' | '\t' => false,
+            '\r' => input[i + 1..].starts_with('\n'),
+            _ => true,
+        })
+        .map_or(input.len(), |(i, _)| i);
+
+    Ok((&input[end..], &input[..end]))
+}
+
+/// Trailing whitespace, inline comment and line ending, stored in a [Trivia] without indentation
+fn line_end(input: &str) -> IResult<&str, Trivia> {
+    map(
+        tuple((trailing, opt(OneLineComment::parse), line_ending)),
+        |(trailing, comment, line_ending): (&str, _, &str)| Trivia {
+            indent: String::new(),
+            trailing: trailing.to_string(),
+            comment,
+            line_ending: line_ending.to_string(),
+        },
+    )(input)
+}
+
+fn keyword(input: &str) -> IResult<&str, ConfigElement> {
+    map(Keyword::parse, |mut keyword| {
+        if let Some(name) = keyword.name.strip_prefix('$') {
+            keyword.name = name.to_string();
+            return ConfigElement::Variable(keyword);
+        }
+
+        match (keyword.name.as_str(), keyword.separator.as_str()) {
+            ("autogenerated", " = ") => match Bool::parse(&keyword.value) {
+                Ok(("", on)) => ConfigElement::AutoGenerated(on),
+                _ => ConfigElement::Keyword(keyword),
+            },
+            _ => ConfigElement::Keyword(keyword),
+        }
+    })(input)
+}
+
+pub(crate) fn parse_section<'a>(
+    source: &'a str,
+    input: &'a str,
+    recover: bool,
+) -> IResult<&'a str, Section> {
+    let (input, (head, tail, _)) = tuple((
+        take_while1(|c: char| !c.is_whitespace() && !"={}#".contains(c)),
+        take_while(|c: char| c != '\n' && !"={}#".contains(c)),
+        tag("{"),
+    ))(input)?;
+    let (input, opening) = line_end(input)?;
+    let (input, elements) = parse_nodes(source, input, true, recover)?;
+    let (input, (closing_indent, _)) = tuple((space0, tag("}")))(input)?;
+
+    let name = tail.trim_end();
+
+    Ok((
+        input,
+        Section {
+            name: format!("{head}{name}"),
+            separator: tail[name.len()..].to_string(),
+            opening,
+            elements,
+            closing_indent: closing_indent.to_string(),
+        },
+    ))
+}
+
+fn parse_node<'a>(source: &'a str, input: &'a str, recover: bool) -> IResult<&'a str, Node> {
+    let start = offset(source, input);
+    let (input, indent) = space0(input)?;
+
+    let (input, (element, trivia)) = alt((
+        map(
+            tuple((trailing, line_ending)),
+            |(trailing, line_ending): (&str, &str)| {
+                (
+                    ConfigElement::EmptyLines(1),
+                    Trivia {
+                        trailing: trailing.to_string(),
+                        line_ending: line_ending.to_string(),
+                        ..Default::default()
+                    },
+                )
+            },
+        ),
+        tuple((
+            alt((
+                map(Comment::parse, ConfigElement::Comment),
+                map(
+                    |input| parse_section(source, input, recover),
+                    ConfigElement::Section,
+                ),
+                keyword,
+            )),
+            line_end,
+        )),
+    ))(input)?;
+
+    Ok((
+        input,
+        Node {
+            span: Span::new(start, offset(source, input)),
+            trivia: Trivia {
+                indent: indent.to_string(),
+                ..trivia
+            },
+            element,
+        },
+    ))
+}
+
+/// Line that couldn't be parsed as a [ConfigElement::Invalid] node
+fn invalid_line<'a>(source: &'a str, input: &'a str) -> (&'a str, Node) {
+    let start = offset(source, input);
+    let content = input.trim_start_matches([' ', '\t']);
+    let indent = &input[..input.len() - content.len()];
+
+    let (line, line_ending, rest) = match content.find('\n') {
+        Some(end) if content[..end].ends_with('\r') => {
+            (&content[..end - 1], "\r\n", &content[end + 1..])
+        }
+        Some(end) => (&content[..end], "\n", &content[end + 1..]),
+        None => (content, "", ""),
+    };
+    let trimmed = line.trim_end_matches('\r');
+
+    (
+        rest,
+        Node {
+            span: Span::new(start, offset(source, rest)),
+            trivia: Trivia {
+                indent: indent.to_string(),
+                trailing: line[trimmed.len()..].to_string(),
+                line_ending: line_ending.to_string(),
+                ..Default::default()
+            },
+            element: ConfigElement::Invalid(trimmed.to_string()),
+        },
+    )
+}
+
+/// Parses nodes until the end of the input, or until the closing `}` if `nested`.
+/// Consecutive blank lines are merged into a single [ConfigElement::EmptyLines] node.
+/// With `recover`, lines that can't be parsed become [ConfigElement::Invalid] nodes
+pub(crate) fn parse_nodes<'a>(
+    source: &'a str,
+    mut input: &'a str,
+    nested: bool,
+    recover: bool,
+) -> IResult<&'a str, Vec<Node>> {
+    let mut nodes: Vec<Node> = vec![];
+
+    loop {
+        let (after_indent, _) = space0(input)?;
+
+        if after_indent.starts_with('}') && nested {
+            return Ok((input, nodes));
+        }
+
+        if input.is_empty() {
+            return match nested {
+                true => Err(nom::Err::Error(Error::new(input, ErrorKind::Eof))),
+                false => Ok((input, nodes)),
+            };
+        }
+
+        let node = match parse_node(source, input, recover) {
+            Ok((rest, node)) => {
+                input = rest;
+                node
+            }
+            Err(nom::Err::Error(_)) if recover => {
+                let (rest, node) = invalid_line(source, input);
+                input = rest;
+                node
+            }
+            Err(nom::Err::Error(_)) if !nested => return Ok((input, nodes)),
+            Err(err) => return Err(err),
+        };
+
+        match (nodes.last_mut(), &node.element) {
+            (
+                Some(Node {
+                    span,
+                    trivia,
+                    element: ConfigElement::EmptyLines(amount),
+                }),
+                ConfigElement::EmptyLines(_),
+            ) if trivia.line_ending == "\n"
+                && trivia.trailing.is_empty()
+                && node.trivia.indent.is_empty() =>
+            {
+                *amount += 1;
+                span.end = node.span.end;
+                trivia.trailing = node.trivia.trailing;
+                trivia.line_ending = node.trivia.line_ending;
+            }
+            _ => nodes.push(node),
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Problems found in a config, with their position in the source and a rustc-like renderer.
+
+use std::fmt::{Display, Formatter};
+
+use crate::{cst::Span, lint::Lint};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagnosticCode {
+    /// Line that isn't a comment, `name = value`, `name {` or `}`
+    Syntax,
+    UnknownSection,
+    UnknownOption,
+    UnknownKeyword,
+    DeprecatedOption,
+    TypeMismatch,
+    OutOfRange,
+    /// Not one of the allowed values of an option
+    InvalidValue,
+    InvalidColor,
+    MalformedBind,
+    /// Malformed `monitor`, `windowrule`, `windowrulev2` or `layerrule`
+    MalformedRule,
+    /// Malformed `bezier` or `animation`
+    MalformedAnimation,
+    /// `animation` with a curve that isn't defined by an earlier `bezier`
+    UndefinedBezier,
+    /// Option set more than once, only the last value is used
+    DuplicateKey,
+    UndefinedVariable,
+    VariableUsedBeforeDefinition,
+    /// Reported by [crate::Config::lint] instead of [crate::Config::validate]
+    Lint(Lint),
+}
+
+impl Display for DiagnosticCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DiagnosticCode::Syntax => "syntax",
+                DiagnosticCode::UnknownSection => "unknown-section",
+                DiagnosticCode::UnknownOption => "unknown-option",
+                DiagnosticCode::UnknownKeyword => "unknown-keyword",
+                DiagnosticCode::DeprecatedOption => "deprecated-option",
+                DiagnosticCode::TypeMismatch => "type-mismatch",
+                DiagnosticCode::OutOfRange => "out-of-range",
+                DiagnosticCode::InvalidValue => "invalid-value",
+                DiagnosticCode::InvalidColor => "invalid-color",
+                DiagnosticCode::MalformedBind => "malformed-bind",
+                DiagnosticCode::MalformedRule => "malformed-rule",
+                DiagnosticCode::MalformedAnimation => "malformed-animation",
+                DiagnosticCode::UndefinedBezier => "undefined-bezier",
+                DiagnosticCode::DuplicateKey => "duplicate-key",
+                DiagnosticCode::UndefinedVariable => "undefined-variable",
+                DiagnosticCode::VariableUsedBeforeDefinition => "variable-used-before-definition",
+                DiagnosticCode::Lint(lint) => return write!(f, "{lint}"),
+            }
+        )
+    }
+}
+
+/// 1-based line and column, the column counts characters
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// Position of the byte `offset` in `source`
+    pub fn new(source: &str, offset: usize) -> Self {
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Byte span together with the line and column of its start and end
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineSpan {
+    pub bytes: Span,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl LineSpan {
+    pub fn new(source: &str, bytes: Span) -> Self {
+        Self {
+            bytes,
+            start: Position::new(source, bytes.start),
+            end: Position::new(source, bytes.end),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Index of the file in the [crate::source::ConfigTree], always 0 for a single config
+    pub file: usize,
+    pub span: LineSpan,
+    pub code: DiagnosticCode,
+    pub message: String,
+    pub help: Option<String>,
+}
+
+impl Display for Diagnostic {
+    /// `error[code]: message`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+impl Diagnostic {
+    /// Renders the diagnostic with the line it points at, the way rustc does. `source` is the
+    /// contents of the file the diagnostic is in and `path` how that file is shown:
+    ///
+    /// ```text
+    /// error[unknown-option]: Unknown option `general:gap_in`
+    ///  --> hyprland.conf:2:5
+    ///   |
+    /// 2 |     gap_in = 5
+    ///   |     ^^^^^^
+    ///   = help: Did you mean `general:gaps_in`?
+    /// ```
+    pub fn render(&self, source: &str, path: &str) -> String {
+        let start = self.span.start;
+        let line = source.lines().nth(start.line - 1).unwrap_or_default();
+        let line_number = start.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        let width = match self.span.end.line == start.line {
+            true => self.span.end.column - start.column,
+            false => line.chars().count() + 1 - start.column,
+        };
+        let underline = format!(
+            "{}{}",
+            " ".repeat(start.column - 1),
+            "^".repeat(width.max(1))
+        );
+
+        let mut rendered = format!(
+            "{self}\n{gutter}--> {path}:{start}\n{gutter} |\n{line_number} | {line}\n{gutter} | {underline}\n"
+        );
+
+        if let Some(help) = &self.help {
+            rendered += &format!("{gutter} = help: {help}\n");
+        }
+
+        rendered
+    }
+}
@@ -0,0 +1,326 @@
+//! Semantic comparison of two configs. Configs are compared by what they set, after substituting
+//! variables, so formatting, comments, the order of options and equivalent spellings of values
+//! don't show up as changes.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+};
+
+use itertools::Itertools;
+
+use crate::{
+    bind::{Bind, Submap},
+    keyword::Keyword,
+    schema::{OptionType, Schema},
+    util::{Float, FromValue},
+    variable::{Bool, Color},
+    Config,
+};
+
+/// Top-level keywords that are compared through their typed form instead of as plain keywords
+const TYPED_KEYWORDS: &[&str] = &[
+    "monitor",
+    "windowrule",
+    "windowrulev2",
+    "layerrule",
+    "unbind",
+    "submap",
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiffCategory {
+    Variable,
+    Option,
+    /// Binds keyed by submap, modifiers and key
+    Bind,
+    /// Monitor rules keyed by the monitor they apply to
+    MonitorRule,
+    /// Window rules keyed by their matchers
+    WindowRule,
+    /// Layer rules keyed by their namespace
+    LayerRule,
+    /// Any other keyword, e.g. `exec-once` or `env`, compared as a set of values per name
+    Keyword,
+}
+
+impl Display for DiffCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DiffCategory::Variable => "variable",
+                DiffCategory::Option => "option",
+                DiffCategory::Bind => "bind",
+                DiffCategory::MonitorRule => "monitor rule",
+                DiffCategory::WindowRule => "window rule",
+                DiffCategory::LayerRule => "layer rule",
+                DiffCategory::Keyword => "keyword",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Display for DiffKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DiffKind::Added => "added",
+                DiffKind::Removed => "removed",
+                DiffKind::Changed => "changed",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiffEntry {
+    pub category: DiffCategory,
+    /// Option path, `$name`, `[submap] MODS, key`, monitor, window matchers, layer namespace
+    /// or keyword name
+    pub key: String,
+    /// `None` if the entry was added
+    pub old: Option<String>,
+    /// `None` if the entry was removed
+    pub new: Option<String>,
+}
+
+impl DiffEntry {
+    pub fn kind(&self) -> DiffKind {
+        match (&self.old, &self.new) {
+            (None, _) => DiffKind::Added,
+            (_, None) => DiffKind::Removed,
+            _ => DiffKind::Changed,
+        }
+    }
+}
+
+impl Display for DiffEntry {
+    /// `+ option general:gaps_in: 10`, `- bind SUPER, Q: ...` or `~ option general:gaps_in: 5 -> 10`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self { category, key, .. } = self;
+
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => write!(f, "~ {category} {key}: {old} -> {new}"),
+            (None, Some(new)) => write!(f, "+ {category} {key}: {new}"),
+            (Some(old), None) => write!(f, "- {category} {key}: {old}"),
+            (None, None) => write!(f, "  {category} {key}"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigDiff {
+    /// Sorted by category and key
+    pub entries: Vec<DiffEntry>,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Display for ConfigDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.entries
+            .iter()
+            .try_for_each(|entry| writeln!(f, "{entry}"))
+    }
+}
+
+/// Everything a config sets, in a form that can be compared
+#[derive(Default)]
+struct Snapshot {
+    /// Category and key -> value
+    values: BTreeMap<(DiffCategory, String), String>,
+    /// Name and value of plain keywords
+    keywords: BTreeSet<(String, String)>,
+}
+
+impl Snapshot {
+    fn new(config: &Config) -> eyre::Result<Self> {
+        let resolved = config.resolve();
+        let config = &resolved.config;
+        let mut snapshot = Self::default();
+
+        for (name, value) in &resolved.variables {
+            snapshot.insert(DiffCategory::Variable, format!("${name}"), value.clone());
+        }
+
+        for (path, keyword) in config.options() {
+            snapshot.insert(DiffCategory::Option, path, keyword.value.clone());
+        }
+
+        for keyword in config.keywords() {
+            if !keyword.name.contains(':') && !is_typed(keyword) {
+                snapshot
+                    .keywords
+                    .insert((keyword.name.clone(), keyword.value.clone()));
+            }
+        }
+
+        for Submap { name, binds, .. } in config.submaps()? {
+            // Modifiers are compared as combinations, `SUPER_SHIFT` is the same as `SHIFT WIN`
+            let binds = binds.into_iter().map(|bind| Bind {
+                mods: bind.mods.normalized(),
+                ..bind
+            });
+            let binds = binds.into_group_map_by(|bind| {
+                let mods = bind.mods.to_string();
+                let key = match mods.is_empty() {
+                    true => bind.key.to_string(),
+                    false => format!("{mods}, {}", bind.key),
+                };
+
+                match &name {
+                    Some(name) => format!("[{name}] {key}"),
+                    None => key,
+                }
+            });
+
+            for (key, binds) in binds {
+                let binds = binds.iter().map(Bind::to_string).sorted().join("; ");
+                snapshot.insert(DiffCategory::Bind, key, binds);
+            }
+        }
+
+        for rule in config.monitor_rules()? {
+            let target = rule.target.to_string();
+            snapshot.insert(DiffCategory::MonitorRule, target, rule.to_string());
+        }
+
+        let window_rules = config
+            .window_rules()?
+            .into_iter()
+            .into_group_map_by(|rule| rule.matchers.iter().join(", "));
+
+        for (matchers, rules) in window_rules {
+            let actions = rules
+                .iter()
+                .map(|rule| rule.action.to_string())
+                .sorted()
+                .join("; ");
+            snapshot.insert(DiffCategory::WindowRule, matchers, actions);
+        }
+
+        let layer_rules = config
+            .layer_rules()?
+            .into_iter()
+            .into_group_map_by(|rule| rule.namespace.to_string());
+
+        for (namespace, rules) in layer_rules {
+            let actions = rules
+                .iter()
+                .map(|rule| rule.action.to_string())
+                .sorted()
+                .join("; ");
+            snapshot.insert(DiffCategory::LayerRule, namespace, actions);
+        }
+
+        Ok(snapshot)
+    }
+
+    fn insert(&mut self, category: DiffCategory, key: String, value: String) {
+        self.values.insert((category, key), value);
+    }
+}
+
+/// Whether the keyword is compared through its typed form
+fn is_typed(keyword: &Keyword) -> bool {
+    TYPED_KEYWORDS.contains(&keyword.name.as_str()) || Bind::from_keyword(keyword).is_some()
+}
+
+/// Whether two values of the option at `path` mean the same, e.g. `yes` and `true`, `1` and `1.0`
+/// or `rgba(1a1a1aee)` and `0xee1a1a1a`
+pub(crate) fn same_value(path: &str, old: &str, new: &str) -> bool {
+    let (old, new) = (old.trim(), new.trim());
+
+    match Schema::get().option(path).map(|option| option.ty) {
+        Some(OptionType::Bool) => match (Bool::from_value(old), Bool::from_value(new)) {
+            (Some(old), Some(new)) => bool::from(old) == bool::from(new),
+            _ => old == new,
+        },
+        Some(OptionType::Int | OptionType::Float) => {
+            match (Float::from_value(old), Float::from_value(new)) {
+                (Some(old), Some(new)) => old == new,
+                _ => old == new,
+            }
+        }
+        Some(OptionType::Color) => match (Color::from_value(old), Color::from_value(new)) {
+            (Some(old), Some(new)) => old.same_color(&new),
+            _ => old == new,
+        },
+        _ => old == new,
+    }
+}
+
+impl Config {
+    /// What `other` sets differently from this config, see [DiffCategory] for how things are keyed.
+    /// Fails if binds or rules of either config are malformed
+    pub fn diff(&self, other: &Config) -> eyre::Result<ConfigDiff> {
+        let old = Snapshot::new(self)?;
+        let new = Snapshot::new(other)?;
+        let mut entries = vec![];
+
+        let keys = old
+            .values
+            .keys()
+            .chain(new.values.keys())
+            .collect::<BTreeSet<_>>();
+
+        for key @ (category, name) in keys {
+            let (old, new) = (old.values.get(key), new.values.get(key));
+
+            let unchanged = match (old, new) {
+                (Some(old), Some(new)) if *category == DiffCategory::Option => {
+                    same_value(name, old, new)
+                }
+                (old, new) => old == new,
+            };
+
+            if !unchanged {
+                entries.push(DiffEntry {
+                    category: *category,
+                    key: name.clone(),
+                    old: old.cloned(),
+                    new: new.cloned(),
+                });
+            }
+        }
+
+        let removed = old
+            .keywords
+            .difference(&new.keywords)
+            .map(|keyword| (keyword, true));
+        let added = new
+            .keywords
+            .difference(&old.keywords)
+            .map(|keyword| (keyword, false));
+
+        for ((name, value), removed) in removed.chain(added).sorted() {
+            entries.push(DiffEntry {
+                category: DiffCategory::Keyword,
+                key: name.clone(),
+                old: removed.then(|| value.clone()),
+                new: (!removed).then(|| value.clone()),
+            });
+        }
+
+        Ok(ConfigDiff { entries })
+    }
+}
@@ -0,0 +1,230 @@
+//! Structured form of a config for JSON, TOML and YAML with the `serde` feature, and for Nix
+//! (see [nix](crate::nix)). A [Document] keeps the lines of the config in order, as single-key
+//! maps from the keyword, `$variable` or section name to its value:
+//!
+//! ```json
+//! { "config": [{ "$mainMod": "SUPER" }, { "general": [{ "gaps_in": 5 }] }, { "bind": "$mainMod, Q, exec, kitty" }] }
+//! ```
+//!
+//! Values of known options are typed with the [Schema], everything else is kept as a string.
+//! Comments, blank lines and lines that couldn't be parsed are dropped, formatting is regenerated
+
+use crate::{
+    cst::Node,
+    format::FormatOptions,
+    keyword::Keyword,
+    schema::Schema,
+    section::Section,
+    variable::{Bool, VariableValue},
+    Config, ConfigElement,
+};
+
+/// Config as a list of entries. The list is wrapped in a `config` table because TOML
+/// documents can't be arrays
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Document {
+    pub config: Vec<Entry>,
+}
+
+/// `name = value`, `$name = value` or `name { ... }`, written as `{ name: value }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub name: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Section(Vec<Entry>),
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Entry {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.name, &self.value)?;
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Entry {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{self, MapAccess, Visitor};
+
+        struct EntryVisitor;
+
+        impl<'de> Visitor<'de> for EntryVisitor {
+            type Value = Entry;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a map with a single key")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Entry, A::Error> {
+                let Some((name, value)) = map.next_entry()? else {
+                    return Err(de::Error::invalid_length(0, &self));
+                };
+
+                match map.next_key::<String>()? {
+                    Some(key) => Err(de::Error::custom(format!(
+                        "unexpected key `{key}` after `{name}`, entries have a single key"
+                    ))),
+                    None => Ok(Entry { name, value }),
+                }
+            }
+        }
+
+        deserializer.deserialize_map(EntryVisitor)
+    }
+}
+
+impl Entry {
+    pub fn new(name: impl Into<String>, value: Value) -> Self {
+        Self {
+            name: name.into(),
+            value,
+        }
+    }
+}
+
+impl Value {
+    /// Value as written in `hyprland.conf`, `None` for sections
+    pub fn to_config_value(&self) -> Option<String> {
+        match self {
+            Value::Bool(value) => Some(value.to_string()),
+            Value::Int(value) => Some(value.to_string()),
+            Value::Float(value) => Some(value.to_string()),
+            Value::String(value) => Some(value.clone()),
+            Value::Section(_) => None,
+        }
+    }
+
+    /// Value of the option at `path`, typed if the schema knows the option
+    fn typed(path: &str, value: &str) -> Self {
+        let ty = Schema::get()
+            .option(path)
+            .filter(|option| option.deprecated.is_none())
+            .map(|option| option.ty);
+
+        // Numbers are read again from the text, f32 values would print with noise as f64
+        match ty.and_then(|ty| ty.parse_value(value)) {
+            Some(VariableValue::Bool(value)) => Value::Bool(value.into()),
+            Some(VariableValue::Int(value)) => Value::Int(value.into()),
+            Some(VariableValue::Float(_)) => match value.trim().parse() {
+                Ok(value) => Value::Float(value),
+                Err(_) => Value::String(value.to_string()),
+            },
+            _ => Value::String(value.to_string()),
+        }
+    }
+}
+
+impl Config {
+    /// Structured form of the config, see [Document]
+    pub fn to_document(&self) -> Document {
+        fn entries(section: &str, nodes: &[Node]) -> Vec<Entry> {
+            nodes
+                .iter()
+                .filter_map(|node| match &node.element {
+                    ConfigElement::Variable(variable) => Some(Entry::new(
+                        format!("${}", variable.name),
+                        Value::String(variable.value.clone()),
+                    )),
+                    ConfigElement::AutoGenerated(on) => {
+                        Some(Entry::new("autogenerated", Value::Bool((*on).into())))
+                    }
+                    ConfigElement::Keyword(keyword) => {
+                        let path = match section {
+                            "" => keyword.name.clone(),
+                            section => format!("{section}:{}", keyword.name),
+                        };
+
+                        Some(Entry::new(
+                            &keyword.name,
+                            Value::typed(&path, &keyword.value),
+                        ))
+                    }
+                    ConfigElement::Section(inner) => {
+                        let path = match section {
+                            "" => inner.name.clone(),
+                            section => format!("{section}:{}", inner.name),
+                        };
+
+                        Some(Entry::new(
+                            &inner.name,
+                            Value::Section(entries(&path, &inner.elements)),
+                        ))
+                    }
+                    ConfigElement::EmptyLines(_)
+                    | ConfigElement::Comment(_)
+                    | ConfigElement::Invalid(_) => None,
+                })
+                .collect()
+        }
+
+        Document {
+            config: entries("", self.nodes()),
+        }
+    }
+}
+
+impl Document {
+    /// Formatted config with the entries of the document
+    pub fn to_config(&self) -> Config {
+        fn nodes(entries: &[Entry]) -> Vec<Node> {
+            entries
+                .iter()
+                .flat_map(|entry| {
+                    let element = match (&entry.value, entry.name.strip_prefix('$')) {
+                        (Value::Section(entries), _) => {
+                            let mut section = Section::new(&entry.name);
+                            section.elements = nodes(entries);
+
+                            ConfigElement::Section(section)
+                        }
+                        (Value::Bool(on), _) if entry.name == "autogenerated" => {
+                            ConfigElement::AutoGenerated(if *on { Bool::One } else { Bool::Zero })
+                        }
+                        (value, Some(name)) => ConfigElement::Variable(Keyword::new(
+                            name,
+                            value.to_config_value().unwrap_or_default(),
+                        )),
+                        (value, None) => ConfigElement::Keyword(Keyword::new(
+                            &entry.name,
+                            value.to_config_value().unwrap_or_default(),
+                        )),
+                    };
+
+                    // Sections are set apart by blank lines, the formatter merges them and
+                    // drops them at the start and end
+                    match element {
+                        ConfigElement::Section(_) => vec![
+                            Node::new("", ConfigElement::EmptyLines(1)),
+                            Node::new("", element),
+                            Node::new("", ConfigElement::EmptyLines(1)),
+                        ],
+                        element => vec![Node::new("", element)],
+                    }
+                })
+                .collect()
+        }
+
+        let mut config = Config::default();
+        *config.nodes_mut() = nodes(&self.config);
+
+        config.format(&FormatOptions::default())
+    }
+}
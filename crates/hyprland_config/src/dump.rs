@@ -0,0 +1,90 @@
+//! Building a config from the state of a running Hyprland, to capture options that were
+//! changed at runtime.
+
+use itertools::Itertools;
+
+use crate::{
+    apply::LiveOption, bind::Submap, cst::Node, format::FormatOptions, keyword::Keyword,
+    monitor::MonitorRule, schema::Schema, variable::VariableValue, Config, ConfigElement,
+};
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DumpOptions {
+    /// Leaves out options that are set to their default value
+    pub non_default_only: bool,
+}
+
+/// Monitors and binds of a running Hyprland
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiveState {
+    pub monitors: Vec<MonitorRule>,
+    pub submaps: Vec<Submap>,
+}
+
+impl Config {
+    /// Formatted config that sets every option of the [Schema] to its live value, followed by
+    /// the monitors and the binds of `state`. `live` looks up the current value of an option,
+    /// options it doesn't know and gradient and vector options, whose values `getoption` doesn't
+    /// report in a usable form, are left out, and so are `device:NAME:...` and deprecated options
+    pub fn dump(
+        state: &LiveState,
+        live: impl Fn(&str) -> Option<LiveOption>,
+        options: &DumpOptions,
+    ) -> Config {
+        let mut config = Config::default();
+
+        for option in Schema::get().options() {
+            if option.path.contains('*') || option.deprecated.is_some() {
+                continue;
+            }
+
+            let Some(current) = live(&option.path) else {
+                continue;
+            };
+            let Some(value) = current.display(option.ty) else {
+                continue;
+            };
+
+            let is_default = option
+                .default
+                .as_ref()
+                .is_some_and(|default| current.matches(option.ty, default) == Some(true));
+
+            if !(options.non_default_only && is_default) {
+                config.set(&option.path, VariableValue::String(value));
+            }
+        }
+
+        let mut groups = vec![state
+            .monitors
+            .iter()
+            .map(MonitorRule::to_keyword)
+            .collect_vec()];
+
+        for submap in &state.submaps {
+            let binds = submap.binds.iter().map(|bind| bind.to_keyword());
+
+            groups.push(match &submap.name {
+                Some(name) => std::iter::once(Keyword::new("submap", name))
+                    .chain(binds)
+                    .chain([Keyword::new("submap", "reset")])
+                    .collect_vec(),
+                None => binds.collect_vec(),
+            });
+        }
+
+        for keywords in groups.into_iter().filter(|group| !group.is_empty()) {
+            let nodes = config.nodes_mut();
+            nodes.push(Node::new("", ConfigElement::EmptyLines(1)));
+            nodes.extend(
+                keywords
+                    .into_iter()
+                    .map(|keyword| Node::new("", ConfigElement::Keyword(keyword))),
+            );
+        }
+
+        config.format(&FormatOptions::default())
+    }
+}
@@ -0,0 +1,176 @@
+//! Editing options by path. Edits touch as little as possible: existing lines keep their
+//! indentation, separator and inline comment, and new lines follow the indentation of their section.
+
+use crate::{
+    cst::Node, keyword::Keyword, schema::Schema, section::Section, util::Parse,
+    variable::VariableValue, Config, ConfigElement,
+};
+
+/// Spaces per nesting level for sections that have no lines to take the indentation from
+const INDENT: usize = 4;
+
+impl Config {
+    /// Value at `path`, the last one if the option is set several times. Known options are
+    /// parsed as their type (see [OptionType::parse_value](crate::schema::OptionType::parse_value)),
+    /// values that aren't a valid [VariableValue] are returned as [VariableValue::String].
+    /// See [Config::value]
+    pub fn get(&self, path: &str) -> Option<VariableValue> {
+        let typed = Schema::get()
+            .option(path)
+            .filter(|option| option.deprecated.is_none())
+            .map(|option| option.ty);
+
+        self.value(path).map(|value| {
+            typed.and_then(|ty| ty.parse_value(value)).unwrap_or_else(
+                || match VariableValue::parse(value) {
+                    Ok(("", value)) => value,
+                    _ => VariableValue::String(value.to_string()),
+                },
+            )
+        })
+    }
+
+    /// Sets the option at `path`. The last line setting the option is edited in place,
+    /// otherwise a line is added to the end of its section, creating the sections on the way.
+    /// `device:NAME:...` options go into a `device:NAME { ... }` section
+    pub fn set(&mut self, path: &str, value: VariableValue) {
+        let value = match value {
+            VariableValue::Variable(name) => format!("${name}"),
+            value => value.to_string(),
+        };
+
+        match find_mut(self.nodes_mut(), path) {
+            Some(keyword) => keyword.value = value,
+            None => {
+                let keyword = ConfigElement::Keyword(Keyword::new("", value));
+                insert(self.nodes_mut(), path, Node::new("", keyword), 0)
+            }
+        }
+    }
+
+    /// Removes every line setting the option at `path`, and the sections left empty by that.
+    /// Returns whether anything was removed
+    pub fn remove(&mut self, path: &str) -> bool {
+        remove(self.nodes_mut(), path)
+    }
+}
+
+/// First section of a path and the rest, the first section of `device:NAME:...` is `device:NAME`
+fn split(path: &str) -> Option<(&str, &str)> {
+    match path.strip_prefix("device:") {
+        Some(rest) => rest
+            .split_once(':')
+            .map(|(name, rest)| (&path[.."device:".len() + name.len()], rest)),
+        None => path.split_once(':'),
+    }
+}
+
+pub(crate) fn is_blank(node: &Node) -> bool {
+    matches!(node.element, ConfigElement::EmptyLines(_))
+}
+
+fn find_mut<'a>(nodes: &'a mut [Node], path: &str) -> Option<&'a mut Keyword> {
+    nodes
+        .iter_mut()
+        .rev()
+        .find_map(|node| match &mut node.element {
+            ConfigElement::Keyword(keyword) if keyword.name == path => Some(keyword),
+            ConfigElement::Section(section) => path
+                .strip_prefix(section.name.as_str())
+                .and_then(|rest| rest.strip_prefix(':'))
+                .and_then(|rest| find_mut(&mut section.elements, rest)),
+            _ => None,
+        })
+}
+
+/// Adds the keyword `node` at `path`, setting its name and indentation
+pub(crate) fn insert(nodes: &mut Vec<Node>, path: &str, mut node: Node, depth: usize) {
+    let indent = nodes.iter().find(|node| !is_blank(node)).map_or_else(
+        || " ".repeat(depth * INDENT),
+        |node| node.trivia.indent.clone(),
+    );
+
+    let Some((section, rest)) = split(path) else {
+        if let ConfigElement::Keyword(keyword) = &mut node.element {
+            keyword.name = path.to_string();
+        }
+        node.trivia.indent = indent;
+        push(nodes, vec![node]);
+        return;
+    };
+
+    let position = nodes.iter().rposition(|node| {
+        matches!(&node.element, ConfigElement::Section(existing) if existing.name == section)
+    });
+
+    let index = match position {
+        Some(index) => index,
+        None => {
+            let mut new = Section::new(section);
+            new.closing_indent = indent.clone();
+            let new = Node::new(indent, ConfigElement::Section(new));
+
+            // New sections are separated from the lines before them by a blank line
+            match nodes.iter().any(|node| !is_blank(node)) {
+                true => {
+                    push(
+                        nodes,
+                        vec![Node::new("", ConfigElement::EmptyLines(1)), new],
+                    ) + 1
+                }
+                false => push(nodes, vec![new]),
+            }
+        }
+    };
+
+    if let ConfigElement::Section(section) = &mut nodes[index].element {
+        insert(&mut section.elements, rest, node, depth + 1);
+    }
+}
+
+/// Adds `new` after the last line that isn't blank and returns the index of its first node
+pub(crate) fn push(nodes: &mut Vec<Node>, new: Vec<Node>) -> usize {
+    let index = nodes
+        .iter()
+        .rposition(|node| !is_blank(node))
+        .map_or(0, |index| index + 1);
+
+    // A file that doesn't end with a line ending gets one, so the new lines start on their own
+    if index == nodes.len() {
+        if let Some(last) = nodes.last_mut() {
+            if last.trivia.line_ending.is_empty() {
+                last.trivia.line_ending = "\n".to_string();
+            }
+        }
+    }
+
+    nodes.splice(index..index, new);
+    index
+}
+
+fn remove(nodes: &mut Vec<Node>, path: &str) -> bool {
+    let mut removed = false;
+
+    nodes.retain_mut(|node| match &mut node.element {
+        ConfigElement::Keyword(keyword) if keyword.name == path => {
+            removed = true;
+            false
+        }
+        ConfigElement::Section(section) => {
+            let rest = path
+                .strip_prefix(section.name.as_str())
+                .and_then(|rest| rest.strip_prefix(':'));
+
+            match rest {
+                Some(rest) if remove(&mut section.elements, rest) => {
+                    removed = true;
+                    section.elements.iter().any(|node| !is_blank(node))
+                }
+                _ => true,
+            }
+        }
+        _ => true,
+    });
+
+    removed
+}
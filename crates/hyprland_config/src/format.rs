@@ -0,0 +1,214 @@
+//! Canonical formatting. Formatting only touches whitespace, comments and values are kept,
+//! and formatting a formatted config doesn't change it.
+
+use smart_default::SmartDefault;
+
+use crate::{
+    bind::Bind,
+    comment::{Comment, OneLineComment},
+    cst::{Node, Trivia},
+    keyword::Keyword,
+    Config, ConfigElement,
+};
+
+#[derive(Debug, Clone, Eq, PartialEq, SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormatOptions {
+    /// Spaces per nesting level of sections
+    #[default(4)]
+    pub indent: usize,
+    /// Blank lines kept between two lines, longer runs are shortened
+    #[default(1)]
+    pub max_blank_lines: usize,
+    /// Aligns the fields of consecutive `bind` lines into columns
+    pub align_binds: bool,
+}
+
+impl Config {
+    /// Canonically formatted copy of the config:
+    /// - ` = ` around the `=` of options, keywords and variables
+    /// - sections indented by [FormatOptions::indent] per level, with a single space before `{`
+    /// - no trailing whitespace, a single space before inline comments and `\n` line endings
+    /// - no blank lines at the start and end of the file and of sections, and at most
+    ///   [FormatOptions::max_blank_lines] anywhere else
+    pub fn format(&self, options: &FormatOptions) -> Config {
+        let mut config = Config::default();
+        *config.nodes_mut() = format_nodes(self.nodes(), 0, options);
+
+        config
+    }
+}
+
+fn format_nodes(nodes: &[Node], depth: usize, options: &FormatOptions) -> Vec<Node> {
+    let mut formatted = vec![];
+    let mut blank_lines = 0;
+
+    for node in nodes {
+        if let ConfigElement::EmptyLines(amount) = node.element {
+            blank_lines += amount;
+            continue;
+        }
+
+        if !formatted.is_empty() && blank_lines > 0 && options.max_blank_lines > 0 {
+            formatted.push(Node::new(
+                "",
+                ConfigElement::EmptyLines(blank_lines.min(options.max_blank_lines)),
+            ));
+        }
+
+        blank_lines = 0;
+        formatted.push(format_node(node, depth, options));
+    }
+
+    if options.align_binds {
+        align_binds(&mut formatted);
+    }
+
+    formatted
+}
+
+/// Trivia after the node, without trailing whitespace unless there is an inline comment
+fn format_trivia(trivia: &Trivia, indent: String) -> Trivia {
+    Trivia {
+        indent,
+        trailing: match trivia.comment {
+            Some(_) => " ".to_string(),
+            None => String::new(),
+        },
+        comment: trivia.comment.as_ref().map(format_comment),
+        line_ending: "\n".to_string(),
+    }
+}
+
+fn format_comment(comment: &OneLineComment) -> OneLineComment {
+    OneLineComment(comment.0.trim_end().to_string())
+}
+
+fn format_keyword(keyword: &Keyword) -> Keyword {
+    Keyword {
+        name: keyword.name.clone(),
+        separator: match keyword.value.is_empty() {
+            true => " =".to_string(),
+            false => " = ".to_string(),
+        },
+        value: keyword.value.clone(),
+    }
+}
+
+fn format_node(node: &Node, depth: usize, options: &FormatOptions) -> Node {
+    let element = match &node.element {
+        ConfigElement::Keyword(keyword) => ConfigElement::Keyword(format_keyword(keyword)),
+        ConfigElement::Variable(keyword) => ConfigElement::Variable(format_keyword(keyword)),
+        ConfigElement::Section(section) => {
+            let mut section = section.clone();
+
+            section.separator = " ".to_string();
+            section.opening = format_trivia(&section.opening, String::new());
+            section.elements = format_nodes(&section.elements, depth + 1, options);
+            section.closing_indent = " ".repeat(depth * options.indent);
+
+            ConfigElement::Section(section)
+        }
+        ConfigElement::Comment(Comment::OneLine(comment)) => {
+            ConfigElement::Comment(Comment::OneLine(format_comment(comment)))
+        }
+        element => element.clone(),
+    };
+
+    Node {
+        span: node.span,
+        trivia: format_trivia(&node.trivia, " ".repeat(depth * options.indent)),
+        element,
+    }
+}
+
+/// Pads the names and the `MODS, key, dispatcher` fields of every run of consecutive binds
+/// to the same widths
+fn align_binds(nodes: &mut [Node]) {
+    let is_bind = |node: &Node| match &node.element {
+        ConfigElement::Keyword(keyword) => Bind::from_keyword(keyword).is_some(),
+        _ => false,
+    };
+
+    let mut start = 0;
+
+    while start < nodes.len() {
+        let end = start
+            + nodes[start..]
+                .iter()
+                .take_while(|node| is_bind(node))
+                .count();
+
+        match end > start {
+            true => {
+                align_bind_run(&mut nodes[start..end]);
+                start = end;
+            }
+            false => start += 1,
+        }
+    }
+}
+
+fn align_bind_run(nodes: &mut [Node]) {
+    let keywords = nodes
+        .iter_mut()
+        .filter_map(|node| match &mut node.element {
+            ConfigElement::Keyword(keyword) => Some(keyword),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let fields = keywords
+        .iter()
+        .map(|keyword| {
+            keyword
+                .value
+                .splitn(4, ',')
+                .map(str::trim)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let name_width = keywords
+        .iter()
+        .map(|keyword| keyword.name.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    // Only fields followed by another one are padded, the last field of a line isn't
+    let widths = (0..3)
+        .map(|column| {
+            fields
+                .iter()
+                .filter(|fields| fields.len() > column + 1)
+                .map(|fields| fields[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    for (keyword, fields) in keywords.into_iter().zip(fields) {
+        let padding = name_width - keyword.name.chars().count();
+        let mut value = String::new();
+
+        for (column, field) in fields.iter().enumerate() {
+            match column + 1 < fields.len() {
+                true => {
+                    value += &format!(
+                        "{:<width$} ",
+                        format!("{field},"),
+                        width = widths[column] + 1
+                    )
+                }
+                false => value += field,
+            }
+        }
+
+        keyword.value = value.trim_end().to_string();
+        keyword.separator = match keyword.value.is_empty() {
+            true => format!("{} =", " ".repeat(padding)),
+            false => format!("{} = ", " ".repeat(padding)),
+        };
+    }
+}
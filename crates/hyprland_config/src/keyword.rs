@@ -0,0 +1,107 @@
+use std::fmt::{Display, Formatter};
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    character::complete::space0,
+    combinator::recognize,
+    sequence::tuple,
+    IResult,
+};
+
+use crate::{
+    util::Parse,
+    variable::{Variable, VariableValue},
+};
+
+/// `name = value` line, used both for section options (`gaps_in = 5`) and
+/// top-level keywords (`bind = ...`, `monitor = ...`, `exec-once = ...`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyword {
+    pub name: String,
+    /// `=` together with the whitespace around it, e.g. ` = ` or `=`
+    pub separator: String,
+    /// Raw value, without trailing whitespace and inline comments
+    pub value: String,
+}
+
+impl Display for Keyword {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.name, self.separator, self.value)
+    }
+}
+
+impl Parse for Keyword {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        let (rest, (name, separator, value)) = tuple((
+            take_while1(|c: char| !c.is_whitespace() && !"={}#".contains(c)),
+            recognize(tuple((space0, tag("="), space0))),
+            Self::parse_value,
+        ))(input)?;
+
+        Ok((
+            rest,
+            Self {
+                name: name.to_string(),
+                separator: separator.to_string(),
+                value: value.to_string(),
+            },
+        ))
+    }
+}
+
+impl Keyword {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            separator: " = ".to_string(),
+            value: value.into(),
+        }
+    }
+
+    /// Typed interpretation of the raw value, if it is a valid [VariableValue]
+    pub fn parsed_value(&self) -> Option<VariableValue> {
+        match VariableValue::parse(&self.value) {
+            Ok(("", value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Typed interpretation of a `$name = value` line
+    pub fn variable(&self) -> Option<Variable> {
+        self.parsed_value().map(|value| Variable {
+            name: self.name.clone(),
+            value,
+        })
+    }
+
+    /// Takes everything up to the end of the line or the start of an inline comment.
+    /// `##` is an escaped `#` and doesn't start a comment. Trailing whitespace is left in the input.
+    fn parse_value(input: &str) -> IResult<&str, &str> {
+        let mut chars = input.char_indices().peekable();
+        let mut end = input.len();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\n' => {
+                    end = i;
+                    break;
+                }
+                '#' => match chars.peek() {
+                    Some((_, '#')) => {
+                        chars.next();
+                    }
+                    _ => {
+                        end = i;
+                        break;
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        let value = input[..end].trim_end();
+
+        Ok((&input[value.len()..], value))
+    }
+}
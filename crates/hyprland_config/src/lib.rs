@@ -1,27 +1,41 @@
-use std::fmt::{Display, Formatter};
-
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{space0, space1},
-    combinator::map,
-    multi::many1,
-    sequence::{preceded, tuple},
-    IResult,
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+    str::FromStr,
 };
 
+use nom::{combinator::map, IResult};
+
 use crate::{
-    comment::Comment,
-    util::Parse,
-    variable::{Bool, Variable},
+    comment::Comment, cst::Node, keyword::Keyword, section::Section, util::Parse, variable::Bool,
 };
 
+pub mod animation;
+pub mod apply;
+pub mod bind;
 pub mod comment;
+pub mod cst;
+pub mod diagnostic;
+pub mod diff;
+pub mod document;
+pub mod dump;
+pub mod edit;
+pub mod format;
+pub mod keyword;
+pub mod lint;
+pub mod migrate;
+pub mod monitor;
+pub mod nix;
+pub mod resolve;
+pub mod rule;
+pub mod schema;
 pub mod section;
+pub mod source;
 pub mod util;
+pub mod validate;
 pub mod variable;
 
-const DEFAULT_CONFIG_STR: &str = r#"
+pub const DEFAULT_CONFIG_STR: &str = r#"
 ########################################################################################
 AUTOGENERATED HYPR CONFIG.
 PLEASE USE THE CONFIG PROVIDED IN THE GIT REPO /examples/hypr.conf AND EDIT IT,
@@ -187,45 +201,148 @@ bindm = $mainMod, mouse:272, movewindow
 bindm = $mainMod, mouse:273, resizewindow
 "#;
 
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Config {
-    elements: Vec<ConfigElement>,
+    nodes: Vec<Node>,
+}
+
+impl Display for Config {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.nodes.iter().try_for_each(|node| write!(f, "{node}"))
+    }
+}
+
+impl Parse for Config {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        map(
+            |rest| cst::parse_nodes(input, rest, false, false),
+            |nodes| Self { nodes },
+        )(input)
+    }
+}
+
+impl FromStr for Config {
+    type Err = eyre::Report;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match Self::parse(input) {
+            Ok(("", config)) => Ok(config),
+            Ok((rest, _)) => {
+                let line = input[..input.len() - rest.len()].lines().count() + 1;
+
+                Err(eyre::eyre!(
+                    "Couldn't parse config at line {line}: {}",
+                    rest.lines().next().unwrap_or_default()
+                ))
+            }
+            Err(err) => Err(eyre::eyre!("Couldn't parse config: {err}")),
+        }
+    }
+}
+
+impl Config {
+    pub fn from_path(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Parses the config like [FromStr], but instead of failing, keeps lines that can't be
+    /// parsed as [ConfigElement::Invalid] nodes, so the rest of the config is still available.
+    /// An unclosed section makes its header line invalid, its contents are read as top-level lines
+    pub fn parse_recovering(input: &str) -> Self {
+        match cst::parse_nodes(input, input, false, true) {
+            Ok((_, nodes)) => Self { nodes },
+            Err(_) => unreachable!("recovering parser doesn't fail at the top level"),
+        }
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn nodes_mut(&mut self) -> &mut Vec<Node> {
+        &mut self.nodes
+    }
+
+    /// Top-level keywords, without variables
+    pub fn keywords(&self) -> impl Iterator<Item = &Keyword> {
+        self.nodes.iter().filter_map(|node| match &node.element {
+            ConfigElement::Keyword(keyword) => Some(keyword),
+            _ => None,
+        })
+    }
+
+    /// Top-level sections
+    pub fn sections(&self) -> impl Iterator<Item = &Section> {
+        self.nodes.iter().filter_map(|node| match &node.element {
+            ConfigElement::Section(section) => Some(section),
+            _ => None,
+        })
+    }
+
+    /// Options set by the config in reading order, with their full paths like `input:touchpad:natural_scroll`.
+    /// Includes options set in sections and top-level keywords with a path (`general:gaps_in = 5`),
+    /// but not `bezier` and `animation` lines inside `animations { ... }`
+    pub fn options(&self) -> Vec<(String, &Keyword)> {
+        fn collect<'a>(section: &str, nodes: &'a [Node], options: &mut Vec<(String, &'a Keyword)>) {
+            for node in nodes {
+                match &node.element {
+                    ConfigElement::Keyword(keyword)
+                        if !section.is_empty()
+                            && !["bezier", "animation"].contains(&keyword.name.as_str()) =>
+                    {
+                        options.push((format!("{section}:{}", keyword.name), keyword))
+                    }
+                    ConfigElement::Keyword(keyword) if keyword.name.contains(':') => {
+                        options.push((keyword.name.clone(), keyword))
+                    }
+                    ConfigElement::Section(inner) => {
+                        let path = match section {
+                            "" => inner.name.clone(),
+                            section => format!("{section}:{}", inner.name),
+                        };
+
+                        collect(&path, &inner.elements, options)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut options = vec![];
+        collect("", &self.nodes, &mut options);
+
+        options
+    }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConfigElement {
+    /// Amount of blank lines. The line ending of the last one is stored in the node's trivia
     EmptyLines(usize),
     Comment(Comment),
-    Variable(Variable),
+    /// `$name = value`, the name is stored without the `$`
+    Variable(Keyword),
     AutoGenerated(Bool),
+    Keyword(Keyword),
+    Section(Section),
+    /// Line that couldn't be parsed, kept verbatim. Only produced by [Config::parse_recovering]
+    Invalid(String),
 }
 
 impl Display for ConfigElement {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            ConfigElement::EmptyLines(amount) => write!(f, "{}", "\n".repeat(*amount)),
+            ConfigElement::EmptyLines(amount) => {
+                write!(f, "{}", "\n".repeat(amount.saturating_sub(1)))
+            }
             ConfigElement::Comment(comment) => write!(f, "{comment}"),
-            ConfigElement::Variable(variable) => write!(f, "{variable}"),
+            ConfigElement::Variable(variable) => write!(f, "${variable}"),
             ConfigElement::AutoGenerated(on) => write!(f, "autogenerated = {on}"),
+            ConfigElement::Keyword(keyword) => write!(f, "{keyword}"),
+            ConfigElement::Section(section) => write!(f, "{section}"),
+            ConfigElement::Invalid(line) => write!(f, "{line}"),
         }
     }
 }
-
-impl ConfigElement {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        let empty_lines = map(
-            many1(preceded(space0, alt((tag("\n"), tag("\r"))))),
-            |lines: Vec<&str>| ConfigElement::EmptyLines(lines.len()),
-        );
-
-        let autogenerated = map(
-            tuple((tag("animated"), space1, tag("="), space1, Bool::parse)),
-            |(_, _, _, _, r)| ConfigElement::AutoGenerated(r),
-        );
-
-        alt((
-            empty_lines,
-            map(Comment::parse, ConfigElement::Comment),
-            map(Variable::parse, ConfigElement::Variable),
-            autogenerated,
-        ))(input)
-    }
-}
@@ -0,0 +1,729 @@
+//! Style and correctness lints. Unlike [Config::validate], lints point at lines Hyprland accepts
+//! but that likely don't do what was meant. Every lint can be allowed, warned about or denied,
+//! and most come with a [Fix] that can be applied without looking at it.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter},
+    path::Path,
+    str::FromStr,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    bind::{Bind, BindFlags, Unbind},
+    cst::{Node, Span},
+    diagnostic::{Diagnostic, DiagnosticCode, LineSpan, Severity},
+    diff::same_value,
+    keyword::Keyword,
+    resolve::{ResolvedConfig, VariableIssueKind},
+    schema::{OptionType, Schema},
+    source::{ConfigTree, NodeLocation},
+    util::FromValue,
+    variable::{Gradient, ModifierSet},
+    Config, ConfigElement,
+};
+
+/// Keywords that run a command
+const EXEC_KEYWORDS: &[&str] = &["exec", "exec-once"];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Lint {
+    /// `$variable` that is never referenced
+    UnusedVariable,
+    /// Bind repeated later with the same keys and action in the same submap, Hyprland runs
+    /// every bind of a key so the action would run twice
+    ShadowedBind,
+    /// `exec` or `exec-once` of a program that isn't on `PATH`
+    MissingExecutable,
+    /// Option set to its default value
+    DefaultValue,
+    /// Section opened again at the same level, e.g. a second `general { ... }`
+    DuplicateSection,
+    /// Gradient whose colors are all the same
+    SingleColorGradient,
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Lint::UnusedVariable => "unused-variable",
+                Lint::ShadowedBind => "shadowed-bind",
+                Lint::MissingExecutable => "missing-executable",
+                Lint::DefaultValue => "default-value",
+                Lint::DuplicateSection => "duplicate-section",
+                Lint::SingleColorGradient => "single-color-gradient",
+            }
+        )
+    }
+}
+
+impl FromStr for Lint {
+    type Err = eyre::Report;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Lint::ALL
+            .into_iter()
+            .find(|lint| lint.to_string() == name)
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "Unknown lint `{name}`, expected one of {}",
+                    Lint::ALL.iter().join(", ")
+                )
+            })
+    }
+}
+
+impl Lint {
+    pub const ALL: [Lint; 6] = [
+        Lint::UnusedVariable,
+        Lint::ShadowedBind,
+        Lint::MissingExecutable,
+        Lint::DefaultValue,
+        Lint::DuplicateSection,
+        Lint::SingleColorGradient,
+    ];
+
+    /// Setting options to their defaults is common and harmless, so `default-value` is only
+    /// reported when asked for
+    pub fn default_level(&self) -> LintLevel {
+        match self {
+            Lint::DefaultValue => LintLevel::Allow,
+            _ => LintLevel::Warn,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LintLevel {
+    /// Not reported
+    Allow,
+    /// Reported as a [Severity::Warning]
+    Warn,
+    /// Reported as a [Severity::Error]
+    Deny,
+}
+
+impl Display for LintLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                LintLevel::Allow => "allow",
+                LintLevel::Warn => "warn",
+                LintLevel::Deny => "deny",
+            }
+        )
+    }
+}
+
+impl LintLevel {
+    fn severity(&self) -> Option<Severity> {
+        match self {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(Severity::Warning),
+            LintLevel::Deny => Some(Severity::Error),
+        }
+    }
+}
+
+/// Level of every lint, lints that aren't set explicitly use [Lint::default_level]
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LintLevels(HashMap<Lint, LintLevel>);
+
+impl LintLevels {
+    pub fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.0.insert(lint, level);
+    }
+
+    pub fn get(&self, lint: Lint) -> LintLevel {
+        self.0
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level())
+    }
+}
+
+/// Replacement of a span of the file a lint is in
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edit {
+    pub span: Span,
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fix {
+    /// What the fix does, e.g. `Remove the line`
+    pub description: String,
+    pub edits: Vec<Edit>,
+}
+
+impl Fix {
+    fn remove(span: Span) -> Self {
+        Self {
+            description: "Remove the line".to_string(),
+            edits: vec![Edit {
+                span,
+                replacement: String::new(),
+            }],
+        }
+    }
+
+    /// Applies `fixes` to `source`. A fix that overlaps an earlier one is left out, linting the
+    /// result again finds it. Returns the new source and how many fixes were applied
+    pub fn apply_all<'a>(
+        source: &str,
+        fixes: impl IntoIterator<Item = &'a Fix>,
+    ) -> (String, usize) {
+        let mut applied: Vec<&Edit> = vec![];
+        let mut count = 0;
+
+        for fix in fixes {
+            let overlaps = fix.edits.iter().any(|edit| {
+                applied.iter().any(|other| {
+                    edit.span.start < other.span.end && other.span.start < edit.span.end
+                })
+            });
+
+            if !overlaps {
+                applied.extend(&fix.edits);
+                count += 1;
+            }
+        }
+
+        let mut result = source.to_string();
+        for edit in applied.iter().sorted_by_key(|edit| edit.span.start).rev() {
+            result.replace_range(edit.span.start..edit.span.end, &edit.replacement);
+        }
+
+        (result, count)
+    }
+}
+
+/// Lint together with its fix, if it can be fixed automatically
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LintDiagnostic {
+    pub diagnostic: Diagnostic,
+    pub fix: Option<Fix>,
+}
+
+/// Whether `command` can be run, either as a path or as a program on `PATH`
+pub fn is_executable(command: &str) -> bool {
+    if command.contains('/') {
+        let path = match command.strip_prefix("~/") {
+            Some(rest) => match std::env::var("HOME") {
+                Ok(home) => Path::new(&home).join(rest),
+                Err(_) => return false,
+            },
+            None => Path::new(command).to_path_buf(),
+        };
+
+        return path.is_file();
+    }
+
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(command).is_file()))
+}
+
+/// Program an `exec` line runs and its byte offset in the value, `None` if it can't be told
+/// without running a shell. `[rules]` in front of the command are skipped
+fn program(value: &str) -> Option<(usize, &str)> {
+    let command = match value.trim_start().strip_prefix('[') {
+        Some(rest) => rest.split_once(']')?.1,
+        None => value,
+    };
+    let command = command.trim_start();
+    let program = command.split_whitespace().next()?;
+
+    if program.contains(['$', '=', '`', '(', '{', '"', '\'']) {
+        return None;
+    }
+
+    Some((
+        value.len() - command.len(),
+        program.trim_end_matches([';', '&']),
+    ))
+}
+
+/// Keys of a bind
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+struct BindKeys {
+    submap: Option<String>,
+    flags: BindFlags,
+    mask: u32,
+    key: String,
+}
+
+impl BindKeys {
+    /// `None` if the modifiers still contain variables
+    fn new(
+        submap: &Option<String>,
+        flags: BindFlags,
+        mods: &ModifierSet,
+        key: String,
+    ) -> Option<Self> {
+        Some(Self {
+            submap: submap.clone(),
+            flags,
+            mask: mods.mask()?,
+            key,
+        })
+    }
+}
+
+struct Linter<'a, F> {
+    /// Contents of every file, as printed
+    sources: Vec<String>,
+    paths: Vec<String>,
+    levels: &'a LintLevels,
+    is_executable: F,
+    lints: Vec<LintDiagnostic>,
+    /// Option paths set so far
+    set: HashSet<String>,
+    /// Section path -> file and node of the first block, and the number of assignments before it
+    sections: HashMap<String, (usize, Node, usize)>,
+    /// File, span and option path or `$name` of every option and variable set so far, in
+    /// reading order
+    assignments: Vec<(usize, Span, String)>,
+    submap: Option<String>,
+    /// Keys and action -> file and span of the latest bind
+    binds: HashMap<(BindKeys, String), (usize, Span)>,
+}
+
+impl<'a, F: Fn(&str) -> bool> Linter<'a, F> {
+    fn lint(
+        sources: Vec<String>,
+        paths: Vec<String>,
+        nodes: Vec<(NodeLocation, &Node)>,
+        resolved: &ResolvedConfig,
+        levels: &'a LintLevels,
+        is_executable: F,
+    ) -> Vec<LintDiagnostic> {
+        let mut linter = Self {
+            sources,
+            paths,
+            levels,
+            is_executable,
+            lints: vec![],
+            set: HashSet::new(),
+            sections: HashMap::new(),
+            assignments: vec![],
+            submap: None,
+            binds: HashMap::new(),
+        };
+
+        for ((location, node), resolved) in nodes.into_iter().zip(resolved.config.nodes()) {
+            linter.node(location.file, "", node, resolved);
+        }
+
+        linter.unused_variables(resolved);
+
+        let mut lints = linter.lints;
+        lints.sort_by_key(|lint| (lint.diagnostic.file, lint.diagnostic.span.bytes.start));
+
+        lints
+    }
+
+    fn push(&mut self, lint: Lint, file: usize, span: Span, message: String, fix: Option<Fix>) {
+        let Some(severity) = self.levels.get(lint).severity() else {
+            return;
+        };
+
+        self.lints.push(LintDiagnostic {
+            diagnostic: Diagnostic {
+                severity,
+                file,
+                span: LineSpan::new(&self.sources[file], span),
+                code: DiagnosticCode::Lint(lint),
+                message,
+                help: fix.as_ref().map(|fix| fix.description.clone()),
+            },
+            fix,
+        });
+    }
+
+    fn enabled(&self, lint: Lint) -> bool {
+        self.levels.get(lint) != LintLevel::Allow
+    }
+
+    /// `line 3` or `line 3 of colors.conf`
+    fn location(&self, file: usize, previous_file: usize, span: Span) -> String {
+        let line = LineSpan::new(&self.sources[previous_file], span).start.line;
+
+        match previous_file == file {
+            true => format!("line {line}"),
+            false => format!("line {line} of {}", self.paths[previous_file]),
+        }
+    }
+
+    /// `section` is the path of the enclosing section, empty at the top level.
+    /// `resolved` is the same node with variables substituted
+    fn node(&mut self, file: usize, section: &str, node: &Node, resolved: &Node) {
+        match (&node.element, &resolved.element) {
+            (ConfigElement::Keyword(keyword), ConfigElement::Keyword(resolved_keyword)) => {
+                match (section, keyword.name.contains(':')) {
+                    ("", false) => self.keyword(file, node, resolved_keyword),
+                    ("", true) => self.option(file, &keyword.name, node, keyword, resolved_keyword),
+                    (section, _) => {
+                        let path = format!("{section}:{}", keyword.name);
+                        self.option(file, &path, node, keyword, resolved_keyword)
+                    }
+                }
+            }
+            (ConfigElement::Variable(variable), _) => {
+                self.assignments
+                    .push((file, node.span, format!("${}", variable.name)));
+            }
+            (ConfigElement::Section(inner), ConfigElement::Section(resolved_inner)) => {
+                let path = match section {
+                    "" => inner.name.clone(),
+                    section => format!("{section}:{}", inner.name),
+                };
+
+                self.section(file, &path, node);
+
+                for (node, resolved) in inner.elements.iter().zip(&resolved_inner.elements) {
+                    self.node(file, &path, node, resolved);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn section(&mut self, file: usize, path: &str, node: &Node) {
+        let Some((first_file, first, before)) = self.sections.get(path).cloned() else {
+            let before = self.assignments.len();
+            self.sections
+                .insert(path.to_string(), (file, node.clone(), before));
+            return;
+        };
+
+        let ConfigElement::Section(section) = &node.element else {
+            return;
+        };
+
+        // Moving the options up changes their meaning if something in between sets an option
+        // of the section or redefines a variable they use
+        let text = node.to_string();
+        let prefix = format!("{path}:");
+        let reordered = self.assignments[before..]
+            .iter()
+            .filter(|(assigned_file, span, _)| {
+                *assigned_file != first_file
+                    || span.start < first.span.start
+                    || span.start >= first.span.end
+            })
+            .any(|(_, _, name)| match name.starts_with('$') {
+                true => text.contains(name.as_str()),
+                false => name.starts_with(&prefix),
+            });
+
+        // Merging changes the first block, which is only possible in the same file
+        let fix = match (first_file == file && !reordered, first.element.clone()) {
+            (true, ConfigElement::Section(mut merged)) => {
+                merged.elements.extend(section.elements.iter().cloned());
+
+                Some(Fix {
+                    description: "Move the options into the first block".to_string(),
+                    edits: vec![
+                        Edit {
+                            span: first.span,
+                            replacement: Node {
+                                element: ConfigElement::Section(merged),
+                                ..first.clone()
+                            }
+                            .to_string(),
+                        },
+                        Edit {
+                            span: node.span,
+                            replacement: String::new(),
+                        },
+                    ],
+                })
+            }
+            _ => None,
+        };
+
+        let start = node.element_start();
+        self.push(
+            Lint::DuplicateSection,
+            file,
+            Span::new(start, start + section.name.len()),
+            format!(
+                "Section `{path}` is already opened on {}",
+                self.location(file, first_file, first.span)
+            ),
+            fix,
+        );
+    }
+
+    /// Top-level keyword that isn't an option
+    fn keyword(&mut self, file: usize, node: &Node, resolved: &Keyword) {
+        let start = node.element_start();
+
+        if resolved.name == "submap" {
+            self.submap = match resolved.value.trim() {
+                "reset" | "" => None,
+                name => Some(name.to_string()),
+            };
+        } else if let Some(Ok(bind)) = Bind::from_keyword(resolved) {
+            let Some(keys) =
+                BindKeys::new(&self.submap, bind.flags, &bind.mods, bind.key.to_string())
+            else {
+                return;
+            };
+
+            let action = format!("{}, {}", bind.dispatcher, bind.arg.unwrap_or_default());
+            let previous = self.binds.insert((keys, action.clone()), (file, node.span));
+
+            if let Some((previous_file, previous)) = previous {
+                let line = &self.sources[previous_file][previous.start..previous.end];
+                let indent = line.len() - line.trim_start().len();
+                let end = previous.start + line.trim_end().len();
+
+                self.push(
+                    Lint::ShadowedBind,
+                    previous_file,
+                    Span::new(previous.start + indent, end),
+                    format!(
+                        "Bind `{}, {}, {action}` is repeated on {}",
+                        bind.mods,
+                        bind.key,
+                        self.location(previous_file, file, node.span)
+                    ),
+                    Some(Fix::remove(previous)),
+                );
+            }
+        } else if let Some(Ok(unbind)) = Unbind::from_keyword(resolved) {
+            if let Some(keys) = BindKeys::new(
+                &self.submap,
+                BindFlags::default(),
+                &unbind.mods,
+                unbind.key.to_string(),
+            ) {
+                self.binds.retain(|(other, _), _| {
+                    (&other.submap, other.mask, &other.key) != (&keys.submap, keys.mask, &keys.key)
+                });
+            }
+        } else if EXEC_KEYWORDS.contains(&resolved.name.as_str())
+            && self.enabled(Lint::MissingExecutable)
+        {
+            let Some((offset, program)) = program(&resolved.value) else {
+                return;
+            };
+
+            if !(self.is_executable)(program) {
+                let value_start = start + resolved.name.len() + resolved.separator.len();
+                // Offsets only match the source if the command has no variables
+                let span = match node.element.to_string() == resolved.to_string() {
+                    true => Span::new(value_start + offset, value_start + offset + program.len()),
+                    false => Span::new(start, start + resolved.name.len()),
+                };
+
+                self.push(
+                    Lint::MissingExecutable,
+                    file,
+                    span,
+                    format!("`{program}` isn't on PATH"),
+                    None,
+                );
+            }
+        }
+    }
+
+    fn option(
+        &mut self,
+        file: usize,
+        path: &str,
+        node: &Node,
+        keyword: &Keyword,
+        resolved: &Keyword,
+    ) {
+        let first = self.set.insert(path.to_string());
+        self.assignments.push((file, node.span, path.to_string()));
+
+        let Some(option) = Schema::get().option(path) else {
+            return;
+        };
+        if option.deprecated.is_some() {
+            return;
+        }
+
+        let start = node.element_start();
+        let value_start = start + keyword.name.len() + keyword.separator.len();
+        let value_span = Span::new(value_start, value_start + keyword.value.len());
+
+        // Resetting an option that was set before isn't redundant
+        if first
+            && option
+                .default
+                .as_deref()
+                .is_some_and(|default| same_value(path, &resolved.value, default))
+        {
+            self.push(
+                Lint::DefaultValue,
+                file,
+                Span::new(start, start + keyword.name.len()),
+                format!("`{path}` is set to its default value `{}`", resolved.value),
+                Some(Fix::remove(node.span)),
+            );
+        }
+
+        if option.ty == OptionType::Gradient {
+            let Some(gradient) = Gradient::from_value(&resolved.value) else {
+                return;
+            };
+            let Some((color, rest)) = gradient.colors.split_first() else {
+                return;
+            };
+
+            if !rest.is_empty() && rest.iter().all(|other| other.same_color(color)) {
+                let fix = match keyword.value == resolved.value {
+                    true => Some(Fix {
+                        description: format!("Replace the gradient with `{color}`"),
+                        edits: vec![Edit {
+                            span: value_span,
+                            replacement: color.to_string(),
+                        }],
+                    }),
+                    false => None,
+                };
+
+                self.push(
+                    Lint::SingleColorGradient,
+                    file,
+                    value_span,
+                    format!("Gradient of `{path}` only has a single color"),
+                    fix,
+                );
+            }
+        }
+    }
+
+    fn unused_variables(&mut self, resolved: &ResolvedConfig) {
+        for issue in &resolved.issues {
+            if issue.kind != VariableIssueKind::Unused {
+                continue;
+            }
+
+            let file = issue.location.file;
+            let reference = format!("${}", issue.name);
+            let line = issue.span.slice(&self.sources[file]);
+            let span = line.find(&reference).map_or(issue.span, |offset| {
+                let start = issue.span.start + offset;
+                Span::new(start, start + reference.len())
+            });
+
+            self.push(
+                Lint::UnusedVariable,
+                file,
+                span,
+                format!("`{reference}` is never used"),
+                Some(Fix::remove(issue.span)),
+            );
+        }
+    }
+}
+
+impl Config {
+    /// Lints the config at the given levels. `is_executable` tells whether a program run by
+    /// `exec` lines exists, see [is_executable]. Spans refer to the source the config was
+    /// parsed from
+    pub fn lint(
+        &self,
+        levels: &LintLevels,
+        is_executable: impl Fn(&str) -> bool,
+    ) -> Vec<LintDiagnostic> {
+        let nodes = self
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(node, element)| (NodeLocation { file: 0, node }, element))
+            .collect();
+
+        Linter::lint(
+            vec![self.to_string()],
+            vec![String::new()],
+            nodes,
+            &self.resolve(),
+            levels,
+            is_executable,
+        )
+    }
+
+    /// Applies the fixes of `lints`, which must come from [Config::lint] of this config.
+    /// Returns how many were applied, overlapping fixes need another round of linting
+    pub fn apply_fixes(&mut self, lints: &[LintDiagnostic]) -> usize {
+        let (fixed, count) = Fix::apply_all(
+            &self.to_string(),
+            lints.iter().filter_map(|lint| lint.fix.as_ref()),
+        );
+
+        *self = Config::parse_recovering(&fixed);
+        count
+    }
+}
+
+impl ConfigTree {
+    /// Lints all files together, so variables, binds and sections are tracked across `source`
+    /// lines, see [Config::lint]
+    pub fn lint(
+        &self,
+        levels: &LintLevels,
+        is_executable: impl Fn(&str) -> bool,
+    ) -> Vec<LintDiagnostic> {
+        let nodes = self
+            .nodes()
+            .into_iter()
+            .map(|sourced| (sourced.location, sourced.node))
+            .collect();
+
+        Linter::lint(
+            self.files()
+                .iter()
+                .map(|file| file.config.to_string())
+                .collect(),
+            self.files()
+                .iter()
+                .map(|file| file.path.display().to_string())
+                .collect(),
+            nodes,
+            &self.resolve(),
+            levels,
+            is_executable,
+        )
+    }
+
+    /// Applies the fixes of `lints`, which must come from [ConfigTree::lint] of this tree,
+    /// each to the file it is in. See [Config::apply_fixes]
+    pub fn apply_fixes(&mut self, lints: &[LintDiagnostic]) -> usize {
+        let mut count = 0;
+
+        for index in 0..self.files().len() {
+            let Some(file) = self.file_mut(index) else {
+                continue;
+            };
+
+            count += file.config.apply_fixes(
+                &lints
+                    .iter()
+                    .filter(|lint| lint.diagnostic.file == index)
+                    .cloned()
+                    .collect_vec(),
+            );
+        }
+
+        count
+    }
+}
@@ -0,0 +1,271 @@
+//! Migrating configs written for older Hyprland versions. Deprecated options that have a
+//! replacement in the [Schema] are renamed or moved to it, and `windowrule` lines become
+//! `windowrulev2`. Rewritten lines keep their value, inline comment and line ending.
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    cst::Node,
+    edit::{self, is_blank},
+    keyword::Keyword,
+    rule::WindowRule,
+    schema::Schema,
+    section::Section,
+    util::FromValue,
+    variable::Bool,
+    Config, ConfigElement,
+};
+
+/// Deprecated options whose replacement has the opposite meaning, their value is negated
+const INVERTED: &[&str] = &["misc:no_vfr"];
+
+/// Indentation of a nested section whose lines don't tell it
+const INDENT: &str = "    ";
+
+/// Outdated line that was rewritten
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Migration {
+    /// The outdated line with its full path, e.g. `decoration:blur_size = 3`
+    pub from: String,
+    /// What the line was rewritten to, `None` if it was removed
+    pub to: Option<String>,
+    pub note: String,
+}
+
+impl Display for Migration {
+    /// `decoration:blur_size = 3 -> decoration:blur:size = 3 (moved into ...)`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.to {
+            Some(to) => write!(f, "{} -> {to} ({})", self.from, self.note),
+            None => write!(f, "{} removed ({})", self.from, self.note),
+        }
+    }
+}
+
+impl Config {
+    /// Rewrites outdated options and keywords to their current form, in reading order.
+    ///
+    /// Options moved within their section are renamed in place, options moved into a nested
+    /// section are moved into it, creating the section where the first of them was. Options
+    /// moved to another section are added to the end of it. An outdated option is removed
+    /// if its replacement is already set. Deprecated options without a replacement are
+    /// left alone, see [Config::validate]
+    pub fn migrate(&mut self) -> Vec<Migration> {
+        let mut migrator = Migrator {
+            existing: self.options().into_iter().map(|(path, _)| path).collect(),
+            migrations: vec![],
+            moved: vec![],
+        };
+
+        migrator.migrate(self.nodes_mut(), "", "");
+
+        for (path, node) in std::mem::take(&mut migrator.moved) {
+            edit::insert(self.nodes_mut(), &path, node, 0);
+        }
+
+        migrator.migrations
+    }
+}
+
+struct Migrator {
+    /// Paths of the options set before migrating
+    existing: HashSet<String>,
+    migrations: Vec<Migration>,
+    /// Nodes moved to another section, added after walking the config
+    moved: Vec<(String, Node)>,
+}
+
+impl Migrator {
+    /// Migrates the lines of the section at `section` whose header is indented by `indent`
+    fn migrate(&mut self, nodes: &mut Vec<Node>, section: &str, indent: &str) {
+        let mut index = 0;
+
+        while index < nodes.len() {
+            let node = &mut nodes[index];
+
+            match &mut node.element {
+                ConfigElement::Section(inner) => {
+                    let path = match section {
+                        "" => inner.name.clone(),
+                        section => format!("{section}:{}", inner.name),
+                    };
+
+                    self.migrate(&mut inner.elements, &path, &node.trivia.indent);
+                }
+                ConfigElement::Keyword(keyword) if section.is_empty() => {
+                    match keyword.name == "windowrule" {
+                        true => self.window_rule(keyword),
+                        false if keyword.name.contains(':') => {
+                            let path = keyword.name.clone();
+                            if !self.option(nodes, index, &path, "", indent) {
+                                continue;
+                            }
+                        }
+                        false => {}
+                    }
+                }
+                ConfigElement::Keyword(keyword) => {
+                    let path = format!("{section}:{}", keyword.name);
+                    if !self.option(nodes, index, &path, section, indent) {
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+
+            index += 1;
+        }
+    }
+
+    /// Migrates the option at `nodes[index]`, returns `false` if the node was removed from `nodes`
+    fn option(
+        &mut self,
+        nodes: &mut Vec<Node>,
+        index: usize,
+        path: &str,
+        section: &str,
+        indent: &str,
+    ) -> bool {
+        let Some(deprecation) = Schema::get()
+            .option(path)
+            .and_then(|option| option.deprecated.as_ref())
+        else {
+            return true;
+        };
+        let Some(to) = deprecation.replaced_by else {
+            return true;
+        };
+        let ConfigElement::Keyword(keyword) = &mut nodes[index].element else {
+            return true;
+        };
+
+        let from = format!("{path} = {}", keyword.value);
+
+        if self.existing.contains(to) {
+            nodes.remove(index);
+            self.migrations.push(Migration {
+                from,
+                to: None,
+                note: format!("`{to}` is already set"),
+            });
+            return false;
+        }
+
+        if INVERTED.contains(&path) {
+            match Bool::from_value(&keyword.value) {
+                Some(value) => keyword.value = (!value).to_string(),
+                None => return true,
+            }
+        }
+
+        self.migrations.push(Migration {
+            from,
+            to: Some(format!("{to} = {}", keyword.value)),
+            note: deprecation.note.to_string(),
+        });
+
+        // Top-level lines with a full path only need a new path
+        if section.is_empty() {
+            keyword.name = to.to_string();
+            return true;
+        }
+
+        let relative = to
+            .strip_prefix(section)
+            .and_then(|rest| rest.strip_prefix(':'));
+
+        match relative.map(|relative| relative.split_once(':').ok_or(relative)) {
+            Some(Err(name)) => {
+                keyword.name = name.to_string();
+                true
+            }
+            Some(Ok((nested, name))) if !name.contains(':') => {
+                keyword.name = name.to_string();
+                let node = nodes.remove(index);
+
+                nest(nodes, index, nested, node, indent)
+            }
+            _ => {
+                self.moved.push((to.to_string(), nodes.remove(index)));
+                false
+            }
+        }
+    }
+
+    /// `windowrule = RULE, WINDOW` to `windowrulev2 = RULE, class:WINDOW`, or
+    /// `windowrulev2 = RULE, title:WINDOW` for title rules. Invalid rules are left alone
+    fn window_rule(&mut self, keyword: &mut Keyword) {
+        let Some(Ok(_)) = WindowRule::from_keyword(keyword) else {
+            return;
+        };
+        let Some((action, window)) = keyword.value.split_once(',') else {
+            return;
+        };
+
+        let from = format!("{} = {}", keyword.name, keyword.value);
+        let matcher = window.trim_start();
+        let value = match matcher.starts_with("title:") {
+            true => keyword.value.clone(),
+            false => format!(
+                "{action},{}class:{matcher}",
+                &window[..window.len() - matcher.len()]
+            ),
+        };
+
+        keyword.name = "windowrulev2".to_string();
+        keyword.value = value;
+
+        self.migrations.push(Migration {
+            from,
+            to: Some(format!("{} = {}", keyword.name, keyword.value)),
+            note: "`windowrule` is replaced by `windowrulev2`".to_string(),
+        });
+    }
+}
+
+/// Moves the keyword `node`, which was at `nodes[index]`, into the nested section `name`.
+/// A missing section is created at `index`. Returns whether a node was added at `index`
+fn nest(nodes: &mut Vec<Node>, index: usize, name: &str, mut node: Node, indent: &str) -> bool {
+    let unit = match node.trivia.indent.strip_prefix(indent) {
+        Some(unit) if !unit.is_empty() => unit.to_string(),
+        _ => INDENT.to_string(),
+    };
+
+    let existing = nodes
+        .iter_mut()
+        .rev()
+        .find_map(|other| match &mut other.element {
+            ConfigElement::Section(section) if section.name == name => {
+                Some((other.trivia.indent.clone(), section))
+            }
+            _ => None,
+        });
+
+    match existing {
+        Some((section_indent, section)) => {
+            node.trivia.indent = match section.elements.iter().find(|node| !is_blank(node)) {
+                Some(sibling) => sibling.trivia.indent.clone(),
+                None => format!("{section_indent}{unit}"),
+            };
+            edit::push(&mut section.elements, vec![node]);
+            false
+        }
+        None => {
+            let mut section = Section::new(name);
+            section.closing_indent = node.trivia.indent.clone();
+
+            let header = Node::new(node.trivia.indent.clone(), ConfigElement::Section(section));
+            node.trivia.indent = format!("{}{unit}", node.trivia.indent);
+
+            nodes.insert(index, header);
+            if let ConfigElement::Section(section) = &mut nodes[index].element {
+                section.elements.push(node);
+            }
+            true
+        }
+    }
+}
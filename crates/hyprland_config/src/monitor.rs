@@ -0,0 +1,646 @@
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while},
+    character::complete::{i32 as int, space0, u32 as uint},
+    combinator::{all_consuming, map, map_res, opt, value},
+    number::complete::float,
+    sequence::{preceded, separated_pair, tuple},
+    IResult,
+};
+
+use crate::{
+    keyword::Keyword,
+    util::{Float, Int, Parse},
+    Config,
+};
+
+/// `monitor = name, resolution, position, scale[, transform, N][, mirror, NAME][, bitdepth, N][, vrr, N]`
+/// or `monitor = name, disable`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorRule {
+    pub target: MonitorTarget,
+    pub settings: MonitorSettings,
+}
+
+impl Display for MonitorRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "monitor = {},{}", self.target, self.settings)
+    }
+}
+
+impl Parse for MonitorRule {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        map_res(
+            tuple((
+                tag("monitor"),
+                tuple((space0, tag("="), space0)),
+                take_while(|c: char| c != '\n'),
+            )),
+            |(_, _, value): (_, _, &str)| Self::from_value(value),
+        )(input)
+    }
+}
+
+impl MonitorRule {
+    pub fn from_keyword(keyword: &Keyword) -> Option<eyre::Result<Self>> {
+        match keyword.name == "monitor" {
+            true => Some(Self::from_value(&keyword.value)),
+            false => None,
+        }
+    }
+
+    pub fn to_keyword(&self) -> Keyword {
+        Keyword::new("monitor", format!("{},{}", self.target, self.settings))
+    }
+
+    /// Whether the rule targets the monitor. A rule without a name matches every monitor
+    pub fn matches(&self, monitor: &MonitorState) -> bool {
+        match &self.target {
+            MonitorTarget::Any => true,
+            MonitorTarget::Name(name) => *name == monitor.name,
+            MonitorTarget::Description(description) => monitor.description.starts_with(description),
+        }
+    }
+
+    /// Differences between the live state of the monitor and what the rule asks for.
+    /// Only settings the rule pins down are compared, e.g. `preferred` or `auto` never diverge
+    pub fn divergences(&self, monitor: &MonitorState) -> Vec<MonitorDivergence> {
+        let mode = match &self.settings {
+            MonitorSettings::Disable => return vec![MonitorDivergence::Enabled],
+            MonitorSettings::AddReserved { .. } => return vec![],
+            MonitorSettings::Mode(mode) => mode,
+        };
+
+        let mut divergences = vec![];
+
+        if let Resolution::Custom {
+            width,
+            height,
+            refresh_rate,
+        } = mode.resolution
+        {
+            if (width, height) != (monitor.width, monitor.height) {
+                divergences.push(MonitorDivergence::Resolution {
+                    expected: (width, height),
+                    actual: (monitor.width, monitor.height),
+                });
+            }
+
+            // Hyprland picks the closest mode the monitor supports, e.g. 59.951 for 60
+            if let Some(refresh_rate) = refresh_rate {
+                if (refresh_rate - monitor.refresh_rate).abs() >= 1.0 {
+                    divergences.push(MonitorDivergence::RefreshRate {
+                        expected: refresh_rate,
+                        actual: monitor.refresh_rate,
+                    });
+                }
+            }
+        }
+
+        if let Position::Custom(x, y) = mode.position {
+            if (x, y) != (monitor.x, monitor.y) {
+                divergences.push(MonitorDivergence::Position {
+                    expected: (x, y),
+                    actual: (monitor.x, monitor.y),
+                });
+            }
+        }
+
+        if let Scale::Custom(scale) = mode.scale {
+            if (scale - monitor.scale).abs() >= 0.01 {
+                divergences.push(MonitorDivergence::Scale {
+                    expected: scale,
+                    actual: monitor.scale,
+                });
+            }
+        }
+
+        let transform = mode.transform.unwrap_or_default();
+        if transform != monitor.transform {
+            divergences.push(MonitorDivergence::Transform {
+                expected: transform,
+                actual: monitor.transform,
+            });
+        }
+
+        divergences
+    }
+
+    fn from_value(value: &str) -> eyre::Result<Self> {
+        let Some((target, settings)) = value.split_once(',') else {
+            return Err(eyre::eyre!(
+                "Expected `name, resolution, position, scale`, got `{value}`"
+            ));
+        };
+
+        Ok(Self {
+            target: MonitorTarget::from_value(target),
+            settings: MonitorSettings::from_value(settings)?,
+        })
+    }
+}
+
+impl From<&MonitorState> for MonitorRule {
+    /// Rule that sets up the monitor the way it currently is
+    fn from(monitor: &MonitorState) -> Self {
+        Self {
+            target: MonitorTarget::Name(monitor.name.clone()),
+            settings: MonitorSettings::Mode(MonitorMode {
+                resolution: Resolution::Custom {
+                    width: monitor.width,
+                    height: monitor.height,
+                    refresh_rate: Some(monitor.refresh_rate),
+                },
+                position: Position::Custom(monitor.x, monitor.y),
+                scale: Scale::Custom(monitor.scale),
+                transform: (monitor.transform != Transform::Normal).then_some(monitor.transform),
+                mirror: None,
+                bitdepth: None,
+                vrr: None,
+            }),
+        }
+    }
+}
+
+/// Monitor a rule applies to
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MonitorTarget {
+    /// Empty name, fallback for monitors without a rule of their own
+    Any,
+    /// `DP-1`
+    Name(String),
+    /// `desc:Chimei Innolux Corporation 0x150C`, matched against the start of the description
+    Description(String),
+}
+
+impl Display for MonitorTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonitorTarget::Any => Ok(()),
+            MonitorTarget::Name(name) => write!(f, "{name}"),
+            MonitorTarget::Description(description) => write!(f, "desc:{description}"),
+        }
+    }
+}
+
+impl MonitorTarget {
+    pub fn from_value(value: &str) -> Self {
+        match value.trim() {
+            "" => Self::Any,
+            value => match value.strip_prefix("desc:") {
+                Some(description) => Self::Description(description.trim().to_string()),
+                None => Self::Name(value.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MonitorSettings {
+    /// `disable`
+    Disable,
+    /// `addreserved, top, bottom, left, right`
+    AddReserved {
+        top: Int,
+        bottom: Int,
+        left: Int,
+        right: Int,
+    },
+    Mode(MonitorMode),
+}
+
+impl Display for MonitorSettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonitorSettings::Disable => write!(f, "disable"),
+            MonitorSettings::AddReserved {
+                top,
+                bottom,
+                left,
+                right,
+            } => write!(f, "addreserved,{top},{bottom},{left},{right}"),
+            MonitorSettings::Mode(mode) => write!(f, "{mode}"),
+        }
+    }
+}
+
+impl MonitorSettings {
+    /// Everything after the monitor name
+    pub fn from_value(value: &str) -> eyre::Result<Self> {
+        let fields = value.split(',').map(str::trim).collect_vec();
+
+        match fields.as_slice() {
+            ["disable" | "disabled"] => Ok(Self::Disable),
+            ["addreserved", top, bottom, left, right] => {
+                let reserved = |value: &str| {
+                    value
+                        .parse::<Int>()
+                        .map_err(|_| eyre::eyre!("Invalid reserved area `{value}`"))
+                };
+
+                Ok(Self::AddReserved {
+                    top: reserved(top)?,
+                    bottom: reserved(bottom)?,
+                    left: reserved(left)?,
+                    right: reserved(right)?,
+                })
+            }
+            _ => MonitorMode::from_fields(&fields).map(Self::Mode),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorMode {
+    pub resolution: Resolution,
+    pub position: Position,
+    pub scale: Scale,
+    pub transform: Option<Transform>,
+    /// Name of the monitor to mirror
+    pub mirror: Option<String>,
+    pub bitdepth: Option<u8>,
+    pub vrr: Option<Vrr>,
+}
+
+impl Display for MonitorMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.resolution, self.position, self.scale)?;
+
+        if let Some(transform) = self.transform {
+            write!(f, ",transform,{transform}")?;
+        }
+
+        if let Some(mirror) = &self.mirror {
+            write!(f, ",mirror,{mirror}")?;
+        }
+
+        if let Some(bitdepth) = self.bitdepth {
+            write!(f, ",bitdepth,{bitdepth}")?;
+        }
+
+        if let Some(vrr) = self.vrr {
+            write!(f, ",vrr,{vrr}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl MonitorMode {
+    fn from_fields(fields: &[&str]) -> eyre::Result<Self> {
+        fn field<T: Parse>(name: &str, value: &str) -> eyre::Result<T> {
+            match all_consuming(T::parse)(value) {
+                Ok((_, value)) => Ok(value),
+                Err(_) => Err(eyre::eyre!("Invalid {name} `{value}`")),
+            }
+        }
+
+        let [resolution, position, scale, extra @ ..] = fields else {
+            return Err(eyre::eyre!(
+                "Expected `resolution, position, scale`, got `{}`",
+                fields.join(",")
+            ));
+        };
+
+        let mut mode = Self {
+            resolution: field("resolution", resolution)?,
+            position: field("position", position)?,
+            scale: field("scale", scale)?,
+            transform: None,
+            mirror: None,
+            bitdepth: None,
+            vrr: None,
+        };
+
+        for pair in extra.chunks(2) {
+            match pair {
+                ["transform", transform] => mode.transform = Some(field("transform", transform)?),
+                ["mirror", mirror] => mode.mirror = Some(mirror.to_string()),
+                ["bitdepth", bitdepth] => {
+                    mode.bitdepth = Some(
+                        bitdepth
+                            .parse()
+                            .map_err(|_| eyre::eyre!("Invalid bitdepth `{bitdepth}`"))?,
+                    )
+                }
+                ["vrr", vrr] => mode.vrr = Some(field("vrr", vrr)?),
+                [name, _] => return Err(eyre::eyre!("Unknown monitor setting `{name}`")),
+                [name] => return Err(eyre::eyre!("Missing value for monitor setting `{name}`")),
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(mode)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Resolution {
+    /// `preferred`, the monitor's preferred mode
+    Preferred,
+    /// `highres`, the highest supported resolution
+    HighRes,
+    /// `highrr`, the highest supported refresh rate
+    HighRr,
+    /// `1920x1080` or `1920x1080@144`
+    Custom {
+        width: u32,
+        height: u32,
+        refresh_rate: Option<Float>,
+    },
+}
+
+impl Display for Resolution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resolution::Preferred => write!(f, "preferred"),
+            Resolution::HighRes => write!(f, "highres"),
+            Resolution::HighRr => write!(f, "highrr"),
+            Resolution::Custom {
+                width,
+                height,
+                refresh_rate,
+            } => {
+                write!(f, "{width}x{height}")?;
+
+                match refresh_rate {
+                    Some(refresh_rate) => write!(f, "@{refresh_rate}"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl Parse for Resolution {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        alt((
+            value(Resolution::Preferred, tag("preferred")),
+            value(Resolution::HighRes, tag("highres")),
+            value(Resolution::HighRr, tag("highrr")),
+            map(
+                tuple((
+                    separated_pair(uint, tag("x"), uint),
+                    opt(preceded(tag("@"), float)),
+                )),
+                |((width, height), refresh_rate)| Resolution::Custom {
+                    width,
+                    height,
+                    refresh_rate,
+                },
+            ),
+        ))(input)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Position {
+    /// `auto`, right of the already placed monitors
+    Auto,
+    /// `1920x0`
+    Custom(Int, Int),
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Position::Auto => write!(f, "auto"),
+            Position::Custom(x, y) => write!(f, "{x}x{y}"),
+        }
+    }
+}
+
+impl Parse for Position {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        alt((
+            value(Position::Auto, tag("auto")),
+            map(separated_pair(int, tag("x"), int), |(x, y)| {
+                Position::Custom(x, y)
+            }),
+        ))(input)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scale {
+    /// `auto`, picked by Hyprland based on the resolution
+    Auto,
+    Custom(Float),
+}
+
+impl Display for Scale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scale::Auto => write!(f, "auto"),
+            Scale::Custom(scale) => write!(f, "{scale}"),
+        }
+    }
+}
+
+impl Parse for Scale {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        alt((value(Scale::Auto, tag("auto")), map(float, Scale::Custom)))(input)
+    }
+}
+
+/// `wl_output_transform`, `0` - `7`
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Transform {
+    #[default]
+    Normal,
+    Normal90,
+    Normal180,
+    Normal270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+}
+
+impl Display for Transform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", *self as u8)
+    }
+}
+
+impl Parse for Transform {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        alt((
+            value(Transform::Normal, tag("0")),
+            value(Transform::Normal90, tag("1")),
+            value(Transform::Normal180, tag("2")),
+            value(Transform::Normal270, tag("3")),
+            value(Transform::Flipped, tag("4")),
+            value(Transform::Flipped90, tag("5")),
+            value(Transform::Flipped180, tag("6")),
+            value(Transform::Flipped270, tag("7")),
+        ))(input)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Vrr {
+    /// `0`
+    Off,
+    /// `1`
+    On,
+    /// `2`, only for fullscreen windows
+    Fullscreen,
+}
+
+impl Display for Vrr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Vrr::Off => 0,
+                Vrr::On => 1,
+                Vrr::Fullscreen => 2,
+            }
+        )
+    }
+}
+
+impl Parse for Vrr {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        alt((
+            value(Vrr::Off, tag("0")),
+            value(Vrr::On, tag("1")),
+            value(Vrr::Fullscreen, tag("2")),
+        ))(input)
+    }
+}
+
+/// Live state of a connected monitor, as reported by `hyprctl monitors`
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorState {
+    pub name: String,
+    pub description: String,
+    pub width: u32,
+    pub height: u32,
+    pub refresh_rate: Float,
+    pub x: Int,
+    pub y: Int,
+    pub scale: Float,
+    pub transform: Transform,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MonitorDivergence {
+    /// The rule disables the monitor, but it is enabled
+    Enabled,
+    Resolution {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    RefreshRate {
+        expected: Float,
+        actual: Float,
+    },
+    Position {
+        expected: (Int, Int),
+        actual: (Int, Int),
+    },
+    Scale {
+        expected: Float,
+        actual: Float,
+    },
+    Transform {
+        expected: Transform,
+        actual: Transform,
+    },
+}
+
+impl Display for MonitorDivergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonitorDivergence::Enabled => write!(f, "enabled, but the rule disables it"),
+            MonitorDivergence::Resolution { expected, actual } => write!(
+                f,
+                "resolution is {}x{}, expected {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            MonitorDivergence::RefreshRate { expected, actual } => {
+                write!(f, "refresh rate is {actual}, expected {expected}")
+            }
+            MonitorDivergence::Position { expected, actual } => write!(
+                f,
+                "position is {}x{}, expected {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            MonitorDivergence::Scale { expected, actual } => {
+                write!(f, "scale is {actual}, expected {expected}")
+            }
+            MonitorDivergence::Transform { expected, actual } => {
+                write!(f, "transform is {actual}, expected {expected}")
+            }
+        }
+    }
+}
+
+/// Rule that applies to a live monitor and how the monitor diverges from it
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MonitorReport<'a> {
+    pub monitor: &'a MonitorState,
+    pub rule: Option<&'a MonitorRule>,
+    pub divergences: Vec<MonitorDivergence>,
+}
+
+/// Rule Hyprland applies to the monitor: the last rule targeting it by name or description,
+/// otherwise the last rule without a name
+pub fn applied_rule<'a>(
+    rules: &'a [MonitorRule],
+    monitor: &MonitorState,
+) -> Option<&'a MonitorRule> {
+    let matching = || rules.iter().rev().filter(|rule| rule.matches(monitor));
+
+    matching()
+        .find(|rule| rule.target != MonitorTarget::Any)
+        .or_else(|| matching().next())
+}
+
+/// Reports the applied rule and its divergences for every live monitor
+pub fn compare_monitors<'a>(
+    rules: &'a [MonitorRule],
+    monitors: &'a [MonitorState],
+) -> Vec<MonitorReport<'a>> {
+    monitors
+        .iter()
+        .map(|monitor| {
+            let rule = applied_rule(rules, monitor);
+
+            MonitorReport {
+                monitor,
+                rule,
+                divergences: rule
+                    .map(|rule| rule.divergences(monitor))
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+impl Config {
+    /// Top-level `monitor = ...` rules, in order
+    pub fn monitor_rules(&self) -> eyre::Result<Vec<MonitorRule>> {
+        self.keywords()
+            .filter_map(MonitorRule::from_keyword)
+            .collect()
+    }
+}
@@ -0,0 +1,422 @@
+//! Nix attribute sets in the shape of home-manager's `wayland.windowManager.hyprland.settings`.
+//! Sections are attribute sets, keywords like `bind` and anything set more than once are lists:
+//!
+//! ```nix
+//! {
+//!   "$mod" = "SUPER";
+//!   general = {
+//!     gaps_in = 5;
+//!     "col.active_border" = "rgba(33ccffee)";
+//!   };
+//!   bind = [
+//!     "$mod, Q, exec, kitty"
+//!   ];
+//! }
+//! ```
+//!
+//! Only literals are read back: strings without interpolation, numbers, booleans, lists and
+//! attribute sets, whose attribute names may be dotted paths like `general.gaps_in`
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until, take_while},
+    character::complete::{
+        anychar, char, digit0, digit1, multispace1, none_of, not_line_ending, one_of, satisfy,
+    },
+    combinator::{cut, map, map_res, not, opt, recognize, value},
+    multi::{fold_many0, many0, separated_list1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::{
+    document::{Document, Entry, Value},
+    util::Parse,
+    validate::is_keyword,
+    Config,
+};
+
+/// Spaces per nesting level
+const INDENT: usize = 2;
+
+/// Nix keywords, attributes with these names have to be quoted
+const RESERVED: &[&str] = &[
+    "assert", "else", "if", "in", "inherit", "let", "or", "rec", "then", "with",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NixValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    List(Vec<NixValue>),
+    /// Attributes in the order they are written
+    Attrs(Vec<(String, NixValue)>),
+}
+
+impl Display for NixValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl Parse for NixValue {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        preceded(
+            trivia,
+            alt((
+                attrs,
+                list,
+                map(string, NixValue::String),
+                number,
+                boolean,
+                delimited(char('('), NixValue::parse, preceded(trivia, char(')'))),
+            )),
+        )(input)
+    }
+}
+
+impl FromStr for NixValue {
+    type Err = eyre::Report;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let rest = match terminated(NixValue::parse, trivia)(input) {
+            Ok(("", value)) => return Ok(value),
+            Ok((rest, _)) => rest,
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => err.input,
+            Err(nom::Err::Incomplete(_)) => "",
+        };
+        let line = input[..input.len() - rest.len()].lines().count().max(1);
+
+        Err(eyre::eyre!(
+            "Couldn't parse Nix expression at line {line}: {}",
+            rest.lines().next().unwrap_or_default()
+        ))
+    }
+}
+
+impl NixValue {
+    fn write(&self, f: &mut Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let indent = " ".repeat((depth + 1) * INDENT);
+        let closing = " ".repeat(depth * INDENT);
+
+        match self {
+            NixValue::Bool(value) => write!(f, "{value}"),
+            NixValue::Int(value) => write!(f, "{value}"),
+            NixValue::Float(value) => write!(f, "{}", float(*value)),
+            NixValue::String(value) => write!(f, "\"{}\"", escape(value)),
+            NixValue::List(items) if items.is_empty() => write!(f, "[ ]"),
+            NixValue::List(items) => {
+                writeln!(f, "[")?;
+                for item in items {
+                    write!(f, "{indent}")?;
+                    // `[ 1 -1 ]` would be a subtraction
+                    match item {
+                        NixValue::Int(value) if *value < 0 => write!(f, "({value})")?,
+                        NixValue::Float(value) if value.is_sign_negative() => {
+                            write!(f, "({})", float(*value))?
+                        }
+                        item => item.write(f, depth + 1)?,
+                    }
+                    writeln!(f)?;
+                }
+                write!(f, "{closing}]")
+            }
+            NixValue::Attrs(attrs) if attrs.is_empty() => write!(f, "{{ }}"),
+            NixValue::Attrs(attrs) => {
+                writeln!(f, "{{")?;
+                for (name, value) in attrs {
+                    write!(f, "{indent}{} = ", attr_name(name))?;
+                    value.write(f, depth + 1)?;
+                    writeln!(f, ";")?;
+                }
+                write!(f, "{closing}}}")
+            }
+        }
+    }
+
+    /// Attribute set of the document. Entries with the same name are grouped into a list at the
+    /// place of the first one, keywords are always lists. `submap` lines can't be exported, they
+    /// only make sense at their place among the binds
+    pub fn from_document(document: &Document) -> eyre::Result<Self> {
+        fn attrs(entries: &[Entry]) -> NixValue {
+            let mut groups: Vec<(String, Vec<NixValue>)> = vec![];
+
+            for entry in entries {
+                let value = match &entry.value {
+                    Value::Bool(value) => NixValue::Bool(*value),
+                    Value::Int(value) => NixValue::Int(*value),
+                    Value::Float(value) => NixValue::Float(*value),
+                    Value::String(value) => NixValue::String(value.clone()),
+                    Value::Section(entries) => attrs(entries),
+                };
+
+                match groups.iter_mut().find(|(name, _)| *name == entry.name) {
+                    Some((_, values)) => values.push(value),
+                    None => groups.push((entry.name.clone(), vec![value])),
+                }
+            }
+
+            NixValue::Attrs(
+                groups
+                    .into_iter()
+                    .map(|(name, mut values)| {
+                        let value = if values.len() == 1 && !is_keyword(&name) {
+                            values.remove(0)
+                        } else {
+                            NixValue::List(values)
+                        };
+
+                        (name, value)
+                    })
+                    .collect(),
+            )
+        }
+
+        if document.config.iter().any(|entry| entry.name == "submap") {
+            return Err(eyre::eyre!(
+                "Submaps depend on the order of the binds, which attribute sets don't keep. \
+                 Move them to home-manager's `extraConfig`"
+            ));
+        }
+
+        Ok(attrs(&document.config))
+    }
+
+    /// Document of an attribute set, lists become an entry per item
+    pub fn to_document(&self) -> eyre::Result<Document> {
+        fn entries(attrs: &[(String, NixValue)]) -> eyre::Result<Vec<Entry>> {
+            let mut entries = vec![];
+
+            for (name, value) in attrs {
+                match value {
+                    NixValue::List(items) => {
+                        for item in items {
+                            entries.push(entry(name, item)?);
+                        }
+                    }
+                    value => entries.push(entry(name, value)?),
+                }
+            }
+
+            Ok(entries)
+        }
+
+        fn entry(name: &str, value: &NixValue) -> eyre::Result<Entry> {
+            let value = match value {
+                NixValue::Bool(value) => Value::Bool(*value),
+                NixValue::Int(value) => Value::Int(*value),
+                NixValue::Float(value) => Value::Float(*value),
+                NixValue::String(value) => Value::String(value.clone()),
+                NixValue::Attrs(attrs) => Value::Section(entries(attrs)?),
+                NixValue::List(_) => {
+                    return Err(eyre::eyre!("`{name}` contains a list inside a list"))
+                }
+            };
+
+            Ok(Entry::new(name, value))
+        }
+
+        match self {
+            NixValue::Attrs(attrs) => Ok(Document {
+                config: entries(attrs)?,
+            }),
+            _ => Err(eyre::eyre!("Expected an attribute set")),
+        }
+    }
+}
+
+impl Config {
+    /// home-manager settings for the config, see [nix](crate::nix)
+    pub fn to_nix(&self) -> eyre::Result<NixValue> {
+        NixValue::from_document(&self.to_document())
+    }
+
+    /// Formatted config from home-manager settings, see [nix](crate::nix)
+    pub fn from_nix(input: &str) -> eyre::Result<Config> {
+        Ok(input.parse::<NixValue>()?.to_document()?.to_config())
+    }
+}
+
+/// Nix floats need a `.`, `1` would be read back as an integer
+fn float(value: f64) -> String {
+    let value = format!("{value:?}");
+
+    match value.split_once('e') {
+        _ if value.contains('.') => value,
+        Some((mantissa, exponent)) => format!("{mantissa}.0e{exponent}"),
+        None => format!("{value}.0"),
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "\\${")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_'-".contains(c)
+}
+
+/// Name as is if it's an identifier, quoted otherwise, e.g. `"col.active_border"`
+fn attr_name(name: &str) -> String {
+    let identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(is_identifier_char)
+        && !RESERVED.contains(&name);
+
+    match identifier {
+        true => name.to_string(),
+        false => format!("\"{}\"", escape(name)),
+    }
+}
+
+/// Whitespace, `# ...` and `/* ... */` comments
+fn trivia(input: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        many0(alt((
+            multispace1,
+            preceded(char('#'), not_line_ending),
+            delimited(tag("/*"), take_until("*/"), tag("*/")),
+        ))),
+    )(input)
+}
+
+fn identifier(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        satisfy(|c| c.is_ascii_alphabetic() || c == '_'),
+        take_while(is_identifier_char),
+    ))(input)
+}
+
+/// `"..."` with escapes, `${` interpolations aren't literals and fail
+fn string(input: &str) -> IResult<&str, String> {
+    delimited(
+        char('"'),
+        fold_many0(
+            alt((
+                map(preceded(char('\\'), anychar), |c| match c {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    c => c,
+                }),
+                terminated(char('$'), not(char('{'))),
+                none_of("\"\\$"),
+            )),
+            String::new,
+            |mut string, c| {
+                string.push(c);
+                string
+            },
+        ),
+        char('"'),
+    )(input)
+}
+
+fn number(input: &str) -> IResult<&str, NixValue> {
+    map_res(
+        recognize(tuple((
+            opt(char('-')),
+            digit1,
+            opt(pair(char('.'), digit0)),
+            opt(tuple((one_of("eE"), opt(one_of("+-")), digit1))),
+        ))),
+        |number: &str| match number.contains(['.', 'e', 'E']) {
+            true => number.parse().map(NixValue::Float).map_err(|_| ()),
+            false => number.parse().map(NixValue::Int).map_err(|_| ()),
+        },
+    )(input)
+}
+
+fn boolean(input: &str) -> IResult<&str, NixValue> {
+    terminated(
+        alt((
+            value(NixValue::Bool(true), tag("true")),
+            value(NixValue::Bool(false), tag("false")),
+        )),
+        not(satisfy(is_identifier_char)),
+    )(input)
+}
+
+fn list(input: &str) -> IResult<&str, NixValue> {
+    map(
+        delimited(
+            char('['),
+            many0(NixValue::parse),
+            preceded(trivia, char(']')),
+        ),
+        NixValue::List,
+    )(input)
+}
+
+/// `{ name = value; a.b = value; }`, dotted paths are merged into nested attribute sets
+fn attrs(input: &str) -> IResult<&str, NixValue> {
+    let attr_path = separated_list1(
+        preceded(trivia, char('.')),
+        preceded(trivia, alt((map(identifier, str::to_string), string))),
+    );
+    let binding = tuple((
+        attr_path,
+        preceded(trivia, char('=')),
+        // Past the `=` it's a binding, errors in the value are reported where they are
+        cut(NixValue::parse),
+        cut(preceded(trivia, char(';'))),
+    ));
+
+    map_res(
+        delimited(char('{'), many0(binding), preceded(trivia, char('}'))),
+        |bindings| {
+            let mut attrs = vec![];
+            for (path, _, value, _) in bindings {
+                insert(&mut attrs, &path, value)?;
+            }
+
+            Ok::<_, String>(NixValue::Attrs(attrs))
+        },
+    )(input)
+}
+
+/// Sets `path` in `attrs`, attribute sets given for the same name are merged
+fn insert(
+    attrs: &mut Vec<(String, NixValue)>,
+    path: &[String],
+    value: NixValue,
+) -> Result<(), String> {
+    let Some((name, rest)) = path.split_first() else {
+        return Ok(());
+    };
+
+    // `a.b = 1;` is `a = { b = 1; };`
+    let value = match rest.is_empty() {
+        true => value,
+        false => {
+            let mut inner = vec![];
+            insert(&mut inner, rest, value)?;
+
+            NixValue::Attrs(inner)
+        }
+    };
+
+    match (attrs.iter_mut().find(|(key, _)| key == name), value) {
+        (None, value) => {
+            attrs.push((name.clone(), value));
+            Ok(())
+        }
+        (Some((_, NixValue::Attrs(existing))), NixValue::Attrs(new)) => new
+            .into_iter()
+            .try_for_each(|(key, value)| insert(existing, std::slice::from_ref(&key), value)),
+        _ => Err(format!("`{name}` is defined more than once")),
+    }
+}
@@ -0,0 +1,289 @@
+//! `$name` variable resolution. Like Hyprland, variables are substituted while the config is read
+//! from top to bottom, so a variable is only visible to the lines after its definition, and a
+//! redefinition only affects the lines after it.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    cst::{Node, Span},
+    source::{ConfigTree, NodeLocation},
+    Config, ConfigElement,
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VariableIssueKind {
+    /// Referenced, but never defined
+    Undefined,
+    /// Referenced before its definition
+    UsedBeforeDefinition,
+    /// Defined, but never referenced
+    Unused,
+    /// Defined again, `previous` is the location of the definition before
+    Redefined { previous: NodeLocation },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariableIssue {
+    pub kind: VariableIssueKind,
+    /// Name of the variable, without the `$`
+    pub name: String,
+    /// Top-level node the issue is in
+    pub location: NodeLocation,
+    /// Span of the line the issue is on, in the file of `location`
+    pub span: Span,
+}
+
+impl Display for VariableIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = &self.name;
+
+        match &self.kind {
+            VariableIssueKind::Undefined => write!(f, "`${name}` is not defined"),
+            VariableIssueKind::UsedBeforeDefinition => {
+                write!(f, "`${name}` is used before it is defined")
+            }
+            VariableIssueKind::Unused => write!(f, "`${name}` is never used"),
+            VariableIssueKind::Redefined { .. } => write!(f, "`${name}` is defined again"),
+        }
+    }
+}
+
+/// Config with all variable references substituted
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResolvedConfig {
+    /// Copy of the config with substituted values. Variable definitions are kept,
+    /// with their own references substituted as well
+    pub config: Config,
+    /// Final value of every variable
+    pub variables: BTreeMap<String, String>,
+    pub issues: Vec<VariableIssue>,
+}
+
+impl ResolvedConfig {
+    /// See [Config::value]
+    pub fn value(&self, path: &str) -> Option<&str> {
+        match path.strip_prefix('$') {
+            Some(name) => self.variables.get(name).map(String::as_str),
+            None => self.config.value(path),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Resolver {
+    /// Name -> value and location of the latest definition
+    defined: HashMap<String, (String, NodeLocation)>,
+    /// Name -> location of the first definition, anywhere in the config
+    definitions: HashMap<String, (NodeLocation, Span)>,
+    used: HashSet<String>,
+    issues: Vec<VariableIssue>,
+}
+
+impl Resolver {
+    fn resolve<'a>(
+        nodes: impl Iterator<Item = (NodeLocation, &'a Node)> + Clone,
+    ) -> ResolvedConfig {
+        let mut resolver = Self::default();
+
+        for (location, node) in nodes.clone() {
+            if let ConfigElement::Variable(keyword) = &node.element {
+                resolver
+                    .definitions
+                    .entry(keyword.name.clone())
+                    .or_insert((location, node.span));
+            }
+        }
+
+        let mut config = Config::default();
+        config.nodes_mut().extend(nodes.map(|(location, node)| {
+            let mut node = node.clone();
+            resolver.resolve_node(location, &mut node);
+
+            node
+        }));
+
+        let mut unused = resolver
+            .definitions
+            .iter()
+            .filter(|(name, _)| !resolver.used.contains(*name))
+            .map(|(name, (location, span))| VariableIssue {
+                kind: VariableIssueKind::Unused,
+                name: name.clone(),
+                location: *location,
+                span: *span,
+            })
+            .collect::<Vec<_>>();
+        unused.sort_by_key(|issue| (issue.location.file, issue.location.node));
+        resolver.issues.extend(unused);
+
+        ResolvedConfig {
+            config,
+            variables: resolver
+                .defined
+                .into_iter()
+                .map(|(name, (value, _))| (name, value))
+                .collect(),
+            issues: resolver.issues,
+        }
+    }
+
+    fn resolve_node(&mut self, location: NodeLocation, node: &mut Node) {
+        let span = node.span;
+
+        match &mut node.element {
+            ConfigElement::Variable(keyword) => {
+                keyword.value = self.substitute(&keyword.value, false, location, span);
+
+                let previous = self
+                    .defined
+                    .insert(keyword.name.clone(), (keyword.value.clone(), location));
+
+                if let Some((_, previous)) = previous {
+                    self.issues.push(VariableIssue {
+                        kind: VariableIssueKind::Redefined { previous },
+                        name: keyword.name.clone(),
+                        location,
+                        span,
+                    });
+                }
+            }
+            ConfigElement::Keyword(keyword) => {
+                let shell = matches!(keyword.name.as_str(), "exec" | "exec-once");
+                keyword.value = self.substitute(&keyword.value, shell, location, span);
+            }
+            ConfigElement::Section(section) => section
+                .elements
+                .iter_mut()
+                .for_each(|node| self.resolve_node(location, node)),
+            ConfigElement::EmptyLines(_)
+            | ConfigElement::Comment(_)
+            | ConfigElement::AutoGenerated(_)
+            | ConfigElement::Invalid(_) => {}
+        }
+    }
+
+    /// Replaces every `$name` with the value of the longest variable name defined so far
+    /// that the text after the `$` starts with, the way Hyprland does it. In `shell` commands
+    /// unknown names are shell variables like `$HOME`, they are kept and not reported
+    fn substitute(
+        &mut self,
+        value: &str,
+        shell: bool,
+        location: NodeLocation,
+        span: Span,
+    ) -> String {
+        let mut result = String::new();
+        let mut rest = value;
+
+        while let Some(start) = rest.find('$') {
+            result.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+
+            let longest = self
+                .defined
+                .iter()
+                .filter(|(name, _)| rest.starts_with(name.as_str()))
+                .max_by_key(|(name, _)| name.len())
+                .map(|(name, (value, _))| (name.clone(), value.clone()));
+
+            if let Some((name, value)) = longest {
+                result.push_str(&value);
+                rest = &rest[name.len()..];
+                self.used.insert(name);
+                continue;
+            }
+
+            result.push('$');
+
+            let name = rest
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect::<String>();
+
+            if !name.starts_with(char::is_alphabetic) {
+                continue;
+            }
+
+            let defined_later = self
+                .definitions
+                .keys()
+                .filter(|defined| rest.starts_with(defined.as_str()))
+                .max_by_key(|defined| defined.len());
+
+            match defined_later {
+                Some(defined) => self.issues.push(VariableIssue {
+                    kind: VariableIssueKind::UsedBeforeDefinition,
+                    name: defined.clone(),
+                    location,
+                    span,
+                }),
+                None if shell => {}
+                None => self.issues.push(VariableIssue {
+                    kind: VariableIssueKind::Undefined,
+                    name,
+                    location,
+                    span,
+                }),
+            }
+        }
+
+        result.push_str(rest);
+        result
+    }
+}
+
+impl Config {
+    /// Substitutes variable references in reading order, see [ResolvedConfig]
+    pub fn resolve(&self) -> ResolvedConfig {
+        Resolver::resolve(
+            self.nodes()
+                .iter()
+                .enumerate()
+                .map(|(node, element)| (NodeLocation { file: 0, node }, element)),
+        )
+    }
+
+    /// Raw value at `path`, the last one if the keyword is set several times.
+    /// Section names and keys are separated by `:`, e.g. `general:gaps_in`,
+    /// `input:touchpad:natural_scroll` or `device:epic-mouse-v1:sensitivity`
+    pub fn value(&self, path: &str) -> Option<&str> {
+        fn find<'a>(nodes: &'a [Node], path: &str) -> Option<&'a str> {
+            nodes.iter().rev().find_map(|node| match &node.element {
+                ConfigElement::Keyword(keyword) if keyword.name == path => {
+                    Some(keyword.value.as_str())
+                }
+                ConfigElement::Section(section) => path
+                    .strip_prefix(section.name.as_str())
+                    .and_then(|rest| rest.strip_prefix(':'))
+                    .and_then(|rest| find(&section.elements, rest)),
+                _ => None,
+            })
+        }
+
+        find(self.nodes(), path)
+    }
+
+    /// Value at `path` with variables substituted, see [Config::value].
+    /// `$name` gives the final value of a variable
+    pub fn resolved_value(&self, path: &str) -> Option<String> {
+        self.resolve().value(path).map(ToString::to_string)
+    }
+}
+
+impl ConfigTree {
+    /// Substitutes variable references across all files in reading order, so variables
+    /// defined in a sourced file are visible after its `source` line
+    pub fn resolve(&self) -> ResolvedConfig {
+        Resolver::resolve(
+            self.nodes()
+                .into_iter()
+                .map(|sourced| (sourced.location, sourced.node)),
+        )
+    }
+}
@@ -0,0 +1,455 @@
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::{
+    keyword::Keyword,
+    util::{FromValue, Int},
+    variable::Bool,
+    Config,
+};
+
+/// Generates a rule action enum from the rule names, split into rules without and with arguments
+macro_rules! rule_action {
+    (
+        $(#[$meta:meta])*
+        $name:ident($kind:literal) {
+            $($variant:ident => $keyword:literal),* $(,)?
+        }
+        args {
+            $($arg_variant:ident => $arg_keyword:literal),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Eq, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum $name {
+            $($variant,)*
+            $($arg_variant(String),)*
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $($name::$variant => write!(f, $keyword),)*
+                    $($name::$arg_variant(args) => write!(f, "{} {args}", $arg_keyword),)*
+                }
+            }
+        }
+
+        impl $name {
+            /// `name` or `name args`
+            pub fn from_value(value: &str) -> eyre::Result<Self> {
+                let (name, args) = match value.trim().split_once(char::is_whitespace) {
+                    Some((name, args)) => (name, args.trim()),
+                    None => (value.trim(), ""),
+                };
+
+                match (name, args) {
+                    $(($keyword, "") => Ok($name::$variant),)*
+                    $(($arg_keyword, "") => Err(eyre::eyre!(
+                        "{} `{name}` requires arguments", $kind
+                    )),)*
+                    $(($arg_keyword, args) => Ok($name::$arg_variant(args.to_string())),)*
+                    $(($keyword, args) => Err(eyre::eyre!(
+                        "{} `{name}` takes no arguments, got `{args}`", $kind
+                    )),)*
+                    _ => Err(eyre::eyre!("Unknown {} `{name}`", $kind)),
+                }
+            }
+        }
+    };
+}
+
+rule_action!(
+    /// What a window rule does to the windows it matches
+    WindowRuleAction("window rule") {
+        Float => "float",
+        Tile => "tile",
+        Fullscreen => "fullscreen",
+        FakeFullscreen => "fakefullscreen",
+        Center => "center",
+        Pseudo => "pseudo",
+        Pin => "pin",
+        NoBlur => "noblur",
+        NoBorder => "noborder",
+        NoDim => "nodim",
+        NoShadow => "noshadow",
+        NoAnim => "noanim",
+        NoFocus => "nofocus",
+        NoInitialFocus => "noinitialfocus",
+        ForceInput => "forceinput",
+        WindowDance => "windowdance",
+        NoMaxSize => "nomaxsize",
+        DimAround => "dimaround",
+        KeepAspectRatio => "keepaspectratio",
+    }
+    args {
+        Move => "move",
+        Size => "size",
+        MinSize => "minsize",
+        MaxSize => "maxsize",
+        Monitor => "monitor",
+        Workspace => "workspace",
+        Opacity => "opacity",
+        Animation => "animation",
+        Rounding => "rounding",
+        BorderColor => "bordercolor",
+        IdleInhibit => "idleinhibit",
+    }
+);
+
+rule_action!(
+    /// What a layer rule does to the layer surfaces it matches
+    LayerRuleAction("layer rule") {
+        Blur => "blur",
+        IgnoreZero => "ignorezero",
+        NoAnim => "noanim",
+        Unset => "unset",
+    }
+    args {}
+);
+
+/// Compiled regex of a rule, compared and printed by its source
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct RulePattern(pub Regex);
+
+impl PartialEq for RulePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for RulePattern {}
+
+impl Display for RulePattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.as_str())
+    }
+}
+
+impl TryFrom<String> for RulePattern {
+    type Error = eyre::Report;
+
+    fn try_from(pattern: String) -> Result<Self, Self::Error> {
+        Self::new(&pattern)
+    }
+}
+
+impl From<RulePattern> for String {
+    fn from(pattern: RulePattern) -> Self {
+        pattern.to_string()
+    }
+}
+
+impl RulePattern {
+    pub fn new(pattern: &str) -> eyre::Result<Self> {
+        Regex::new(pattern)
+            .map(Self)
+            .map_err(|err| eyre::eyre!("Invalid regex `{pattern}`: {err}"))
+    }
+
+    /// Hyprland searches for the pattern anywhere in the string, like [Regex::is_match]
+    pub fn is_match(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WorkspaceMatcher {
+    /// `workspace:3`
+    Id(Int),
+    /// `workspace:name:web`
+    Name(String),
+}
+
+impl Display for WorkspaceMatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkspaceMatcher::Id(id) => write!(f, "{id}"),
+            WorkspaceMatcher::Name(name) => write!(f, "name:{name}"),
+        }
+    }
+}
+
+/// `key:value` part of a `windowrulev2`
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindowMatcher {
+    Class(RulePattern),
+    Title(RulePattern),
+    Xwayland(bool),
+    Floating(bool),
+    Fullscreen(bool),
+    Pinned(bool),
+    Workspace(WorkspaceMatcher),
+}
+
+impl Display for WindowMatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowMatcher::Class(class) => write!(f, "class:{class}"),
+            WindowMatcher::Title(title) => write!(f, "title:{title}"),
+            WindowMatcher::Xwayland(xwayland) => write!(f, "xwayland:{}", *xwayland as u8),
+            WindowMatcher::Floating(floating) => write!(f, "floating:{}", *floating as u8),
+            WindowMatcher::Fullscreen(fullscreen) => write!(f, "fullscreen:{}", *fullscreen as u8),
+            WindowMatcher::Pinned(pinned) => write!(f, "pinned:{}", *pinned as u8),
+            WindowMatcher::Workspace(workspace) => write!(f, "workspace:{workspace}"),
+        }
+    }
+}
+
+impl WindowMatcher {
+    const KEYS: [&'static str; 7] = [
+        "class",
+        "title",
+        "xwayland",
+        "floating",
+        "fullscreen",
+        "pinned",
+        "workspace",
+    ];
+
+    pub fn from_value(key: &str, value: &str) -> eyre::Result<Self> {
+        let flag = || match Bool::from_value(value) {
+            Some(flag) => Ok(bool::from(flag)),
+            None => Err(eyre::eyre!(
+                "Invalid value `{value}` for `{key}`, expected 0 or 1"
+            )),
+        };
+
+        Ok(match key {
+            "class" => WindowMatcher::Class(RulePattern::new(value)?),
+            "title" => WindowMatcher::Title(RulePattern::new(value)?),
+            "xwayland" => WindowMatcher::Xwayland(flag()?),
+            "floating" => WindowMatcher::Floating(flag()?),
+            "fullscreen" => WindowMatcher::Fullscreen(flag()?),
+            "pinned" => WindowMatcher::Pinned(flag()?),
+            "workspace" => WindowMatcher::Workspace(match value.strip_prefix("name:") {
+                Some(name) => WorkspaceMatcher::Name(name.to_string()),
+                None => WorkspaceMatcher::Id(value.parse().map_err(|_| {
+                    eyre::eyre!("Invalid workspace `{value}`, expected an id or `name:NAME`")
+                })?),
+            }),
+            key => return Err(eyre::eyre!("Unknown window rule matcher `{key}`")),
+        })
+    }
+
+    pub fn matches(&self, window: &WindowState) -> bool {
+        match self {
+            WindowMatcher::Class(class) => class.is_match(&window.class),
+            WindowMatcher::Title(title) => title.is_match(&window.title),
+            WindowMatcher::Xwayland(xwayland) => *xwayland == window.xwayland,
+            WindowMatcher::Floating(floating) => *floating == window.floating,
+            WindowMatcher::Fullscreen(fullscreen) => *fullscreen == window.fullscreen,
+            WindowMatcher::Pinned(pinned) => *pinned == window.pinned,
+            WindowMatcher::Workspace(WorkspaceMatcher::Id(id)) => *id == window.workspace_id,
+            WindowMatcher::Workspace(WorkspaceMatcher::Name(name)) => {
+                *name == window.workspace_name
+            }
+        }
+    }
+
+    /// Splits the matchers of a `windowrulev2` on commas that start a new `key:`,
+    /// so that commas inside of a regex are kept
+    fn split(value: &str) -> eyre::Result<Vec<Self>> {
+        let mut parts: Vec<(&str, String)> = vec![];
+
+        for part in value.split(',') {
+            let key = part
+                .trim_start()
+                .split_once(':')
+                .filter(|(key, _)| Self::KEYS.contains(key));
+
+            match (key, parts.last_mut()) {
+                (Some((key, value)), _) => parts.push((key, value.to_string())),
+                (None, Some((_, value))) => {
+                    value.push(',');
+                    value.push_str(part);
+                }
+                (None, None) => {
+                    return Err(eyre::eyre!("Unknown window rule matcher `{}`", part.trim()))
+                }
+            }
+        }
+
+        if let Some(key) = parts.iter().map(|(key, _)| key).duplicates().next() {
+            return Err(eyre::eyre!("Duplicate window rule matcher `{key}`"));
+        }
+
+        parts
+            .iter()
+            .map(|(key, value)| Self::from_value(key, value.trim()))
+            .collect()
+    }
+}
+
+/// `windowrule = RULE, WINDOW` or `windowrulev2 = RULE, MATCHER[, MATCHER...]`
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowRule {
+    pub action: WindowRuleAction,
+    pub matchers: Vec<WindowMatcher>,
+    /// Whether the rule is a `windowrulev2`, `windowrule` only supports a single class or title matcher
+    pub v2: bool,
+}
+
+impl Display for WindowRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let keyword = self.to_keyword();
+
+        write!(f, "{} = {}", keyword.name, keyword.value)
+    }
+}
+
+impl WindowRule {
+    pub fn from_keyword(keyword: &Keyword) -> Option<eyre::Result<Self>> {
+        let v2 = match keyword.name.as_str() {
+            "windowrule" => false,
+            "windowrulev2" => true,
+            _ => return None,
+        };
+
+        Some(Self::from_value(&keyword.value, v2))
+    }
+
+    pub fn to_keyword(&self) -> Keyword {
+        let matchers = match (self.v2, self.matchers.as_slice()) {
+            (false, [WindowMatcher::Class(class)]) => class.to_string(),
+            _ => self.matchers.iter().join(", "),
+        };
+
+        Keyword::new(
+            match self.v2 {
+                true => "windowrulev2",
+                false => "windowrule",
+            },
+            format!("{}, {matchers}", self.action),
+        )
+    }
+
+    /// Whether all matchers of the rule match the window
+    pub fn matches(&self, window: &WindowState) -> bool {
+        self.matchers.iter().all(|matcher| matcher.matches(window))
+    }
+
+    fn from_value(value: &str, v2: bool) -> eyre::Result<Self> {
+        let Some((action, matchers)) = value.split_once(',') else {
+            return Err(eyre::eyre!("Expected `RULE, WINDOW`, got `{value}`"));
+        };
+
+        let action = WindowRuleAction::from_value(action)?;
+        let matchers = matchers.trim();
+
+        let matchers = match v2 {
+            true => WindowMatcher::split(matchers)?,
+            false => vec![match matchers.strip_prefix("title:") {
+                Some(title) => WindowMatcher::Title(RulePattern::new(title)?),
+                None => WindowMatcher::Class(RulePattern::new(matchers)?),
+            }],
+        };
+
+        if matchers.is_empty() {
+            return Err(eyre::eyre!("Window rule `{action}` has no matchers"));
+        }
+
+        Ok(Self {
+            action,
+            matchers,
+            v2,
+        })
+    }
+}
+
+/// `layerrule = RULE, NAMESPACE`
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayerRule {
+    pub action: LayerRuleAction,
+    pub namespace: RulePattern,
+}
+
+impl Display for LayerRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "layerrule = {}, {}", self.action, self.namespace)
+    }
+}
+
+impl LayerRule {
+    pub fn from_keyword(keyword: &Keyword) -> Option<eyre::Result<Self>> {
+        if keyword.name != "layerrule" {
+            return None;
+        }
+
+        Some(match keyword.value.split_once(',') {
+            Some((action, namespace)) => LayerRuleAction::from_value(action).and_then(|action| {
+                Ok(Self {
+                    action,
+                    namespace: RulePattern::new(namespace.trim())?,
+                })
+            }),
+            None => Err(eyre::eyre!(
+                "Expected `RULE, NAMESPACE`, got `{}`",
+                keyword.value
+            )),
+        })
+    }
+
+    pub fn to_keyword(&self) -> Keyword {
+        Keyword::new("layerrule", format!("{}, {}", self.action, self.namespace))
+    }
+
+    pub fn matches(&self, namespace: &str) -> bool {
+        self.namespace.is_match(namespace)
+    }
+}
+
+/// Live state of a window, as reported by `hyprctl clients`
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowState {
+    pub class: String,
+    pub title: String,
+    pub xwayland: bool,
+    pub floating: bool,
+    pub fullscreen: bool,
+    pub pinned: bool,
+    pub workspace_id: Int,
+    pub workspace_name: String,
+}
+
+/// Rules that match the window, in config order
+pub fn matching_window_rules<'a>(
+    rules: &'a [WindowRule],
+    window: &WindowState,
+) -> Vec<&'a WindowRule> {
+    rules.iter().filter(|rule| rule.matches(window)).collect()
+}
+
+/// Rules that match the layer surface namespace, in config order
+pub fn matching_layer_rules<'a>(rules: &'a [LayerRule], namespace: &str) -> Vec<&'a LayerRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches(namespace))
+        .collect()
+}
+
+impl Config {
+    /// Top-level `windowrule` and `windowrulev2` rules, in order
+    pub fn window_rules(&self) -> eyre::Result<Vec<WindowRule>> {
+        self.keywords()
+            .filter_map(WindowRule::from_keyword)
+            .collect()
+    }
+
+    /// Top-level `layerrule` rules, in order
+    pub fn layer_rules(&self) -> eyre::Result<Vec<LayerRule>> {
+        self.keywords()
+            .filter_map(LayerRule::from_keyword)
+            .collect()
+    }
+}
@@ -0,0 +1,401 @@
+//! Registry of all known options with their types, defaults, allowed ranges and descriptions,
+//! built from the typed section data in [crate::section].
+
+use std::{
+    fmt::{Display, Formatter},
+    sync::OnceLock,
+};
+
+use crate::{
+    section::{
+        AnimationData, BindsData, BlurData, DebugData, DecorationData, DeviceData, DwindleData,
+        GeneralData, GesturesData, InputData, Layout, MasterData, MiscData, SectionData,
+    },
+    util::{Float, FromValue, Int},
+    variable::{Bool, Color, Gradient, Variable, VariableValue, Vec2},
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OptionType {
+    Int,
+    Float,
+    Bool,
+    Color,
+    Gradient,
+    Vec2,
+    String,
+    /// One of [OptionSchema::values]
+    Enum,
+}
+
+impl Display for OptionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                OptionType::Int => "int",
+                OptionType::Float => "float",
+                OptionType::Bool => "bool",
+                OptionType::Color => "color",
+                OptionType::Gradient => "gradient",
+                OptionType::Vec2 => "vec2",
+                OptionType::String => "string",
+                OptionType::Enum => "enum",
+            }
+        )
+    }
+}
+
+impl OptionType {
+    /// Value of an option of this type, `None` if it isn't one. A whole `$variable` is kept as
+    /// a [VariableValue::Variable]. Unlike [VariableValue::parse], the type decides what `1`
+    /// is: an int, a bool or a float
+    pub fn parse_value(&self, value: &str) -> Option<VariableValue> {
+        let value = value.trim();
+
+        if let Ok(("", name)) = Variable::parse_name(value) {
+            return Some(VariableValue::Variable(name));
+        }
+
+        match self {
+            OptionType::Int => Int::from_value(value).map(VariableValue::Int),
+            OptionType::Float => Float::from_value(value).map(VariableValue::Float),
+            OptionType::Bool => Bool::from_value(value).map(VariableValue::Bool),
+            OptionType::Color => Color::from_value(value).map(VariableValue::Color),
+            OptionType::Gradient => Gradient::from_value(value).map(VariableValue::Gradient),
+            OptionType::Vec2 => Vec2::from_value(value).map(VariableValue::Vec2),
+            OptionType::String | OptionType::Enum => Some(VariableValue::String(value.to_string())),
+        }
+    }
+}
+
+/// Typed value of an option
+pub trait OptionValue: FromValue {
+    const TYPE: OptionType;
+    /// Allowed values, empty if any value of the type is allowed
+    const VALUES: &'static [&'static str] = &[];
+
+    /// Value as written in the config, `None` if it is not set
+    fn to_value(&self) -> Option<String>;
+}
+
+macro_rules! option_value {
+    ($($ty:ty => $option_type:ident $(: [$($value:literal),*])?),* $(,)?) => {
+        $(
+            impl OptionValue for $ty {
+                const TYPE: OptionType = OptionType::$option_type;
+                $(const VALUES: &'static [&'static str] = &[$($value),*];)?
+
+                fn to_value(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+option_value!(
+    Int => Int,
+    Float => Float,
+    Bool => Bool,
+    Color => Color,
+    Gradient => Gradient,
+    Vec2 => Vec2,
+    String => String,
+    Layout => Enum: ["dwindle", "master"],
+);
+
+impl<T: OptionValue> OptionValue for Option<T> {
+    const TYPE: OptionType = T::TYPE;
+    const VALUES: &'static [&'static str] = T::VALUES;
+
+    fn to_value(&self) -> Option<String> {
+        self.as_ref().and_then(OptionValue::to_value)
+    }
+}
+
+/// Whether `value` is one of `values`, empty values and empty lists allow anything
+pub(crate) fn is_allowed(values: &[&str], value: &str) -> bool {
+    let value = value.trim();
+
+    values.is_empty() || value.is_empty() || values.contains(&value)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Deprecation {
+    /// Option to use instead, if there is one
+    pub replaced_by: Option<&'static str>,
+    pub note: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OptionSchema {
+    /// Full path, e.g. `general:gaps_in`, `input:touchpad:natural_scroll`
+    /// or `device:*:sensitivity`
+    pub path: String,
+    pub ty: OptionType,
+    /// Default value as written in the config, `None` if the option is unset by default
+    pub default: Option<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Allowed values, empty if any value of the type is allowed
+    pub values: Vec<&'static str>,
+    pub description: String,
+    pub deprecated: Option<Deprecation>,
+}
+
+/// Reason a value doesn't fit an option
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ValueError {
+    /// Not a value of the option's type
+    Type(OptionType),
+    Range {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    NotAllowed(Vec<&'static str>),
+}
+
+impl Display for ValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueError::Type(ty) => write!(f, "expected a value of type {ty}"),
+            ValueError::Range {
+                min: Some(min),
+                max: Some(max),
+            } => write!(f, "expected a value between {min} and {max}"),
+            ValueError::Range { min: Some(min), .. } => write!(f, "expected at least {min}"),
+            ValueError::Range { max: Some(max), .. } => write!(f, "expected at most {max}"),
+            ValueError::Range { .. } => write!(f, "value out of range"),
+            ValueError::NotAllowed(values) => {
+                write!(f, "expected one of {}", values.join(", "))
+            }
+        }
+    }
+}
+
+impl OptionSchema {
+    /// Whether `value` fits the type, range and allowed values of the option
+    pub fn check(&self, value: &str) -> Result<(), ValueError> {
+        let number = match self.ty {
+            OptionType::Int => Int::from_value(value).map(f64::from),
+            OptionType::Float => Float::from_value(value).map(f64::from),
+            OptionType::Bool => Bool::from_value(value).map(|_| 0.0),
+            OptionType::Color => Color::from_value(value).map(|_| 0.0),
+            OptionType::Gradient => Gradient::from_value(value).map(|_| 0.0),
+            OptionType::Vec2 => Vec2::from_value(value).map(|_| 0.0),
+            OptionType::String | OptionType::Enum => Some(0.0),
+        }
+        .ok_or(ValueError::Type(self.ty))?;
+
+        if !is_allowed(&self.values, value) {
+            return Err(ValueError::NotAllowed(self.values.clone()));
+        }
+
+        let below = self.min.is_some_and(|min| number < min);
+        let above = self.max.is_some_and(|max| number > max);
+
+        match below || above {
+            true => Err(ValueError::Range {
+                min: self.min,
+                max: self.max,
+            }),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Options that are no longer read by Hyprland, as `(path, replaced by, note)`
+const DEPRECATED: &[(&str, Option<&str>, &str)] = &[
+    (
+        "general:damage_tracking",
+        Some("debug:damage_tracking"),
+        "moved to the debug section",
+    ),
+    (
+        "general:main_mod",
+        None,
+        "removed, use a variable like `$mainMod = SUPER` instead",
+    ),
+    (
+        "misc:no_vfr",
+        Some("misc:vfr"),
+        "replaced by `misc:vfr`, with the opposite meaning",
+    ),
+    (
+        "dwindle:col.group_border",
+        Some("general:col.group_border"),
+        "moved to the general section",
+    ),
+    (
+        "dwindle:col.group_border_active",
+        Some("general:col.group_border_active"),
+        "moved to the general section",
+    ),
+    (
+        "decoration:blur",
+        Some("decoration:blur:enabled"),
+        "moved into `decoration { blur { ... } }`",
+    ),
+    (
+        "decoration:blur_size",
+        Some("decoration:blur:size"),
+        "moved into `decoration { blur { ... } }`",
+    ),
+    (
+        "decoration:blur_passes",
+        Some("decoration:blur:passes"),
+        "moved into `decoration { blur { ... } }`",
+    ),
+    (
+        "decoration:blur_ignore_opacity",
+        Some("decoration:blur:ignore_opacity"),
+        "moved into `decoration { blur { ... } }`",
+    ),
+    (
+        "decoration:blur_new_optimizations",
+        Some("decoration:blur:new_optimizations"),
+        "moved into `decoration { blur { ... } }`",
+    ),
+    (
+        "decoration:blur_xray",
+        Some("decoration:blur:xray"),
+        "moved into `decoration { blur { ... } }`",
+    ),
+    (
+        "animations:speed",
+        None,
+        "removed, use `animation = NAME, ON, SPEED, CURVE` instead",
+    ),
+    (
+        "animations:curve",
+        None,
+        "removed, use `animation = NAME, ON, SPEED, CURVE` instead",
+    ),
+    (
+        "animations:windows_speed",
+        None,
+        "removed, use `animation = windows, ON, SPEED, CURVE` instead",
+    ),
+    (
+        "animations:windows_curve",
+        None,
+        "removed, use `animation = windows, ON, SPEED, CURVE` instead",
+    ),
+    (
+        "animations:borders_speed",
+        None,
+        "removed, use `animation = border, ON, SPEED, CURVE` instead",
+    ),
+    (
+        "animations:borders_curve",
+        None,
+        "removed, use `animation = border, ON, SPEED, CURVE` instead",
+    ),
+    (
+        "animations:fadein_speed",
+        None,
+        "removed, use `animation = fadeIn, ON, SPEED, CURVE` instead",
+    ),
+    (
+        "animations:workspaces_speed",
+        None,
+        "removed, use `animation = workspaces, ON, SPEED, CURVE` instead",
+    ),
+];
+
+/// All known options
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Schema {
+    options: Vec<OptionSchema>,
+}
+
+impl Schema {
+    /// The schema of the options this crate knows about, built once
+    pub fn get() -> &'static Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+
+        SCHEMA.get_or_init(Self::build)
+    }
+
+    fn build() -> Self {
+        let mut options = [
+            GeneralData::schema("general:"),
+            DecorationData::schema("decoration:"),
+            BlurData::schema("decoration:blur:"),
+            AnimationData::schema("animations:"),
+            InputData::schema("input:"),
+            GesturesData::schema("gestures:"),
+            MiscData::schema("misc:"),
+            BindsData::schema("binds:"),
+            DebugData::schema("debug:"),
+            DwindleData::schema("dwindle:"),
+            MasterData::schema("master:"),
+            DeviceData::schema("device:*:"),
+        ]
+        .concat();
+
+        // Deprecated options that are still typed keep their type and default
+        for option in &mut options {
+            if let Some((_, replaced_by, note)) =
+                DEPRECATED.iter().find(|(path, ..)| *path == option.path)
+            {
+                option.deprecated = Some(Deprecation {
+                    replaced_by: *replaced_by,
+                    note,
+                });
+            }
+        }
+
+        let typed = options
+            .iter()
+            .map(|option| option.path.clone())
+            .collect::<Vec<_>>();
+
+        options.extend(
+            DEPRECATED
+                .iter()
+                .filter(|(path, ..)| !typed.iter().any(|typed| typed == path))
+                .map(|(path, replaced_by, note)| OptionSchema {
+                    path: path.to_string(),
+                    ty: OptionType::String,
+                    default: None,
+                    min: None,
+                    max: None,
+                    values: vec![],
+                    description: note.to_string(),
+                    deprecated: Some(Deprecation {
+                        replaced_by: *replaced_by,
+                        note,
+                    }),
+                }),
+        );
+
+        Self { options }
+    }
+
+    /// All options, including deprecated ones
+    pub fn options(&self) -> &[OptionSchema] {
+        &self.options
+    }
+
+    /// Option at `path`. Device options can be looked up with the device name,
+    /// e.g. `device:epic-mouse-v1:sensitivity`
+    pub fn option(&self, path: &str) -> Option<&OptionSchema> {
+        let path = match path.strip_prefix("device:") {
+            Some(rest) => match rest.rsplit_once(':') {
+                Some((_, key)) => format!("device:*:{key}"),
+                None => path.to_string(),
+            },
+            None => path.to_string(),
+        };
+
+        self.options.iter().find(|option| option.path == path)
+    }
+}
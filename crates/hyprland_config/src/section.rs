@@ -1,21 +1,44 @@
 use std::fmt::{Display, Formatter};
 
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take_until};
-use nom::character::complete::space0;
-use nom::combinator::{map, map_res};
-use nom::sequence::{delimited, tuple};
+use nom::bytes::complete::tag;
+use nom::combinator::map;
 use nom::IResult;
 use smart_default::SmartDefault;
 
 use crate::{
-    util::{Float, Int, Parse},
-    variable::{Bool, Color, Gradient},
+    cst::{self, Node, Trivia},
+    schema::{self, OptionSchema, OptionValue},
+    util::{Float, FromValue, Int, Parse},
+    variable::{Bool, Color, Gradient, Vec2},
+    ConfigElement,
 };
 
+/// `name {` ... `}` block. Nested sections are stored as nodes of the parent
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Section {
     pub name: String,
-    pub ty: SectionType,
+    /// Whitespace between the name and `{`
+    pub separator: String,
+    /// Trivia after `{` on the header line
+    pub opening: Trivia,
+    pub elements: Vec<Node>,
+    /// Whitespace before the closing `}`
+    pub closing_indent: String,
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{{", self.name, self.separator)?;
+        self.opening.fmt_end(f)?;
+
+        self.elements
+            .iter()
+            .try_for_each(|element| write!(f, "{element}"))?;
+
+        write!(f, "{}}}", self.closing_indent)
+    }
 }
 
 impl Parse for Section {
@@ -23,94 +46,639 @@ impl Parse for Section {
     where
         Self: Sized,
     {
-        let mut general = map(
-            tuple((
-                tag("general"),
-                space0,
-                delimited(
-                    tag("{"),
-                    map_res(take_until("}"), |s: &str| {
-                        GeneralData::parse(s).map(|(rest, data)| {
-                            (
-                                rest,
-                                Section {
-                                    name: "general".to_string(),
-                                    ty: SectionType::General(data),
-                                },
-                            )
-                        })
-                    }),
-                    tag("}"),
-                ),
-            )),
-            |(_, _, (_, s))| s,
-        );
-
-        // Todo: more
-
-        general(input)
+        cst::parse_section(input, input, false)
     }
 }
 
+impl Section {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            separator: " ".to_string(),
+            opening: Trivia::new(""),
+            elements: vec![],
+            closing_indent: String::new(),
+        }
+    }
+
+    /// Typed data of the section, built from its options and nested sections.
+    /// Unknown options are ignored, invalid values of known ones are errors
+    pub fn ty(&self) -> eyre::Result<SectionType> {
+        if let Some(name) = self.name.strip_prefix("device:") {
+            return Ok(SectionType::Device {
+                name: name.to_string(),
+                data: DeviceData::from_section(self)?,
+            });
+        }
+
+        Ok(match self.name.as_str() {
+            "general" => SectionType::General(GeneralData::from_section(self)?),
+            "decoration" => SectionType::Decoration(DecorationData::from_section(self)?),
+            "animations" => SectionType::Animation(AnimationData::from_section(self)?),
+            "input" => SectionType::Input(InputData::from_section(self)?),
+            "touchpad" => SectionType::Touchpad(TouchpadData::from_section(self)?),
+            "touchdevice" => SectionType::TouchDevice(TouchDeviceData::from_section(self)?),
+            "gestures" => SectionType::Gestures(GesturesData::from_section(self)?),
+            "misc" => SectionType::Misc(MiscData::from_section(self)?),
+            "binds" => SectionType::Binds(BindsData::from_section(self)?),
+            "debug" => SectionType::Debug(DebugData::from_section(self)?),
+            "dwindle" => SectionType::Dwindle(DwindleData::from_section(self)?),
+            "master" => SectionType::Master(MasterData::from_section(self)?),
+            name => return Err(eyre::eyre!("Unknown section `{name}`")),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SectionType {
     General(GeneralData),
-    Decoration,
-    Animation,
-    Input,
-    Touchpad,
-    TouchDevice,
-    Gestures,
-    Misc,
-    Binds,
+    Decoration(DecorationData),
+    Animation(AnimationData),
+    Input(InputData),
+    Touchpad(TouchpadData),
+    TouchDevice(TouchDeviceData),
+    Gestures(GesturesData),
+    Misc(MiscData),
+    Binds(BindsData),
     /// Only for developers
-    Debug,
-}
-
-#[derive(Debug, Clone, PartialEq, SmartDefault)]
-pub struct GeneralData {
-    #[default(1.0)]
-    pub sensitivity: Float,
-    #[default(1)]
-    pub border_size: Int,
-    #[default(Bool::True)]
-    pub no_border_radius: Bool,
-    #[default(5)]
-    pub gaps_in: Int,
-    #[default(20)]
-    pub gaps_out: Int,
-    #[default(Gradient { colors: vec![Color::Legacy(0xffffffff)], angle: None })]
-    pub col_inactive_border: Gradient,
-    #[default(Gradient { colors: vec![Color::Legacy(0xff444444)], angle: None })]
-    pub col_active_border: Gradient,
-    #[default(Gradient { colors: vec![Color::Legacy(0x66777700)], angle: None })]
-    pub col_group_border: Gradient,
-    #[default(Gradient { colors: vec![Color::Legacy(0x66ffff00)], angle: None })]
-    pub col_border_active: Gradient,
-    pub cursor_inactive_timeout: Int,
-    #[default(Layout::Dwindle)]
-    pub layout: Layout,
-    #[default(Bool::False)]
-    pub no_cursor_warps: Bool,
-    #[default(Bool::False)]
-    pub apply_sens_to_raw: Bool,
-    #[default(Bool::False)]
-    pub resize_on_border: Bool,
-    #[default(15)]
-    pub extend_border_grab_area: Int,
-    #[default(Bool::True)]
-    pub hover_icon_on_border: Bool,
-}
-
-impl Parse for GeneralData {
-    fn parse(input: &str) -> IResult<&str, Self>
-    where
-        Self: Sized,
-    {
-        todo!()
+    Debug(DebugData),
+    Dwindle(DwindleData),
+    Master(MasterData),
+    /// `device:<name> { ... }`
+    Device {
+        name: String,
+        data: DeviceData,
+    },
+}
+
+/// Typed data of a section
+pub trait SectionData: Default {
+    /// Sets the option `key`, which can also be `sub:key` for nested sections.
+    /// Returns `false` if there is no such option
+    fn set(&mut self, key: &str, value: &str) -> eyre::Result<bool>;
+
+    /// Applies a nested section. Returns `false` if there is no such nested section
+    fn set_section(&mut self, section: &Section) -> eyre::Result<bool>;
+
+    /// Schema of all options of the section and its nested sections,
+    /// with paths starting with `prefix`, e.g. `input:`
+    fn schema(prefix: &str) -> Vec<OptionSchema>;
+
+    fn apply(&mut self, section: &Section) -> eyre::Result<()> {
+        section
+            .elements
+            .iter()
+            .try_for_each(|node| match &node.element {
+                ConfigElement::Keyword(keyword) => {
+                    self.set(&keyword.name, &keyword.value).map(drop)
+                }
+                ConfigElement::Section(section) => self.set_section(section).map(drop),
+                _ => Ok(()),
+            })
+    }
+
+    fn from_section(section: &Section) -> eyre::Result<Self> {
+        let mut data = Self::default();
+        data.apply(section)?;
+
+        Ok(data)
+    }
+}
+
+/// Defines a section data struct together with the schema of its options.
+///
+/// Every field is followed by its description and optionally the option name, if it differs
+/// from the field name, the allowed range and the allowed values:
+/// `pub gaps_in: Int => "Gaps between windows", min = 0,`.
+/// Option names are matched against field names, with `.` and `-` in the option name
+/// replaced by `_` (`col.active_border`, `tap-to-click`)
+macro_rules! section_data {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                pub $field:ident: $ty:ty => $description:literal
+                    $(, name = $option:literal)?
+                    $(, min = $min:literal)?
+                    $(, max = $max:literal)?
+                    $(, values = [$($value:literal),* $(,)?])?,
+            )*
+        }
+        $(sections { $($section:ident: $section_ty:ty),* $(,)? })?
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, SmartDefault)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct $name {
+            $(
+                #[doc = $description]
+                $(#[$field_meta])*
+                pub $field: $ty,
+            )*
+            $($(pub $section: $section_ty,)*)?
+        }
+
+        impl SectionData for $name {
+            fn set(&mut self, key: &str, value: &str) -> eyre::Result<bool> {
+                if let Some((_section, _key)) = key.split_once(':') {
+                    return match _section {
+                        $($(stringify!($section) => self.$section.set(_key, value),)*)?
+                        _ => Ok(false),
+                    };
+                }
+
+                match key.replace(['.', '-'], "_").as_str() {
+                    $(
+                        stringify!($field) => {
+                            let values: &[&str] =
+                                [$(&[$($value),*][..],)? &[] as &[&str]][0];
+
+                            if !schema::is_allowed(values, value) {
+                                return Err(eyre::eyre!(
+                                    "Invalid value `{value}` for `{key}`, expected one of {}",
+                                    values.join(", ")
+                                ));
+                            }
+
+                            self.$field = FromValue::from_value(value).ok_or_else(|| {
+                                eyre::eyre!("Invalid value `{value}` for `{key}`")
+                            })?;
+                        }
+                    )*
+                    _ => return Ok(false),
+                }
+
+                Ok(true)
+            }
+
+            fn set_section(&mut self, _section: &Section) -> eyre::Result<bool> {
+                match _section.name.as_str() {
+                    $($(stringify!($section) => self.$section.apply(_section).map(|_| true),)*)?
+                    _ => Ok(false),
+                }
+            }
+
+            fn schema(prefix: &str) -> Vec<OptionSchema> {
+                let _default = Self::default();
+
+                #[allow(unused_mut)]
+                let mut options = vec![
+                    $(
+                        OptionSchema {
+                            path: format!(
+                                "{prefix}{}",
+                                [$($option,)? stringify!($field)][0]
+                            ),
+                            ty: <$ty as OptionValue>::TYPE,
+                            default: OptionValue::to_value(&_default.$field),
+                            min: [$(Some(f64::from($min)),)? None][0],
+                            max: [$(Some(f64::from($max)),)? None][0],
+                            values: match [$(&[$($value),*][..],)? &[] as &[&str]][0] {
+                                [] => <$ty as OptionValue>::VALUES,
+                                values => values,
+                            }
+                            .to_vec(),
+                            description: $description.trim().to_string(),
+                            deprecated: None,
+                        },
+                    )*
+                ];
+
+                $($(
+                    options.extend(<$section_ty>::schema(&format!(
+                        "{prefix}{}:",
+                        stringify!($section)
+                    )));
+                )*)?
+
+                options
+            }
+        }
+    };
+}
+
+section_data! {
+    /// `general { ... }`
+    pub struct GeneralData {
+        #[default(1.0)]
+        pub sensitivity: Float => "Mouse sensitivity, legacy, use `input:sensitivity` instead",
+            min = 0.0,
+        #[default(1)]
+        pub border_size: Int => "Size of the border around windows", min = 0,
+        #[default(Bool::False)]
+        pub no_border_on_floating: Bool => "Disables borders for floating windows",
+        #[default(5)]
+        pub gaps_in: Int => "Gaps between windows", min = 0,
+        #[default(20)]
+        pub gaps_out: Int => "Gaps between windows and monitor edges", min = 0,
+        #[default(Gradient { colors: vec![Color::legacy(0xffffffff)], angle: None })]
+        pub col_inactive_border: Gradient => "Border color for inactive windows",
+            name = "col.inactive_border",
+        #[default(Gradient { colors: vec![Color::legacy(0xff444444)], angle: None })]
+        pub col_active_border: Gradient => "Border color for the active window",
+            name = "col.active_border",
+        #[default(Gradient { colors: vec![Color::legacy(0x66777700)], angle: None })]
+        pub col_group_border: Gradient => "Inactive border color for window groups",
+            name = "col.group_border",
+        #[default(Gradient { colors: vec![Color::legacy(0x66ffff00)], angle: None })]
+        pub col_group_border_active: Gradient => "Active border color for window groups",
+            name = "col.group_border_active",
+        pub cursor_inactive_timeout: Int =>
+            "Hides the cursor after this many seconds of inactivity, 0 disables it", min = 0,
+        #[default(Layout::Dwindle)]
+        pub layout: Layout => "Layout to use",
+        #[default(Bool::False)]
+        pub no_cursor_warps: Bool => "Disables the cursor from warping to the focused window",
+        #[default(Bool::False)]
+        pub apply_sens_to_raw: Bool =>
+            "Applies the sensitivity to raw mouse output as well, not recommended",
+        #[default(Bool::False)]
+        pub resize_on_border: Bool => "Allows resizing windows by dragging their borders",
+        #[default(15)]
+        pub extend_border_grab_area: Int =>
+            "Extends the area around the border where it can be grabbed, in px", min = 0,
+        #[default(Bool::True)]
+        pub hover_icon_on_border: Bool => "Shows a cursor icon when hovering over borders",
+    }
+}
+
+section_data! {
+    /// `decoration { ... }`
+    pub struct DecorationData {
+        pub rounding: Int => "Radius of rounded window corners, in px", min = 0,
+        #[default(Bool::True)]
+        pub multisample_edges: Bool => "Smooths the edges of rounded corners",
+        #[default(1.0)]
+        pub active_opacity: Float => "Opacity of the active window", min = 0.0, max = 1.0,
+        #[default(1.0)]
+        pub inactive_opacity: Float => "Opacity of inactive windows", min = 0.0, max = 1.0,
+        #[default(1.0)]
+        pub fullscreen_opacity: Float => "Opacity of fullscreen windows", min = 0.0, max = 1.0,
+        #[default(Bool::True)]
+        pub blur: Bool => "Enables blur behind transparent windows",
+        #[default(8)]
+        pub blur_size: Int => "Blur distance", min = 1,
+        #[default(1)]
+        pub blur_passes: Int => "Number of blur passes", min = 1,
+        #[default(Bool::False)]
+        pub blur_ignore_opacity: Bool => "Ignores the opacity of windows when blurring",
+        #[default(Bool::True)]
+        pub blur_new_optimizations: Bool => "Enables further blur optimizations",
+        #[default(Bool::False)]
+        pub blur_xray: Bool => "Floating windows ignore tiled windows in their blur",
+        #[default(Bool::True)]
+        pub drop_shadow: Bool => "Enables drop shadows on windows",
+        #[default(4)]
+        pub shadow_range: Int => "Shadow range in px", min = 0,
+        #[default(3)]
+        pub shadow_render_power: Int => "Falloff of the shadow, higher is faster", min = 1, max = 4,
+        #[default(Bool::True)]
+        pub shadow_ignore_window: Bool => "Only renders the shadow outside of the window",
+        #[default(Color::legacy(0xee1a1a1a))]
+        pub col_shadow: Color => "Shadow color", name = "col.shadow",
+        pub col_shadow_inactive: Option<Color> =>
+            "Shadow color of inactive windows, falls back to `col.shadow` if not set",
+            name = "col.shadow_inactive",
+        #[default(Vec2(0.0, 0.0))]
+        pub shadow_offset: Vec2 => "Shadow offset",
+        #[default(1.0)]
+        pub shadow_scale: Float => "Shadow scale", min = 0.0, max = 1.0,
+        #[default(Bool::False)]
+        pub dim_inactive: Bool => "Dims inactive windows",
+        #[default(0.5)]
+        pub dim_strength: Float => "How much inactive windows are dimmed", min = 0.0, max = 1.0,
+        #[default(0.2)]
+        pub dim_special: Float =>
+            "How much the rest of the screen is dimmed when a special workspace is open",
+            min = 0.0, max = 1.0,
+        #[default(0.4)]
+        pub dim_around: Float => "How much the `dimaround` window rule dims", min = 0.0, max = 1.0,
+        pub screen_shader: String => "Path to a fragment shader applied to the whole screen",
+    }
+}
+
+section_data! {
+    /// `decoration { blur { ... } }`, the newer form of the `blur*` options of [DecorationData].
+    /// Only part of the [Schema](crate::schema::Schema) for now, [DecorationData] reads the
+    /// flat options
+    pub struct BlurData {
+        #[default(Bool::True)]
+        pub enabled: Bool => "Enables blur behind transparent windows",
+        #[default(8)]
+        pub size: Int => "Blur distance", min = 1,
+        #[default(1)]
+        pub passes: Int => "Number of blur passes", min = 1,
+        #[default(Bool::False)]
+        pub ignore_opacity: Bool => "Ignores the opacity of windows when blurring",
+        #[default(Bool::True)]
+        pub new_optimizations: Bool => "Enables further blur optimizations",
+        #[default(Bool::False)]
+        pub xray: Bool => "Floating windows ignore tiled windows in their blur",
+    }
+}
+
+section_data! {
+    /// `animations { ... }`, `bezier` and `animation` keywords are not options and are skipped
+    pub struct AnimationData {
+        #[default(Bool::True)]
+        pub enabled: Bool => "Enables animations",
+    }
+}
+
+section_data! {
+    /// `input { ... }`
+    pub struct InputData {
+        pub kb_model: String => "XKB keyboard model",
+        #[default("us".to_string())]
+        pub kb_layout: String => "XKB keyboard layouts",
+        pub kb_variant: String => "XKB keyboard variants",
+        pub kb_options: String => "XKB keyboard options",
+        pub kb_rules: String => "XKB keyboard rules",
+        pub kb_file: String => "Path to a custom .xkb file",
+        #[default(Bool::False)]
+        pub numlock_by_default: Bool => "Enables numlock on startup",
+        #[default(25)]
+        pub repeat_rate: Int => "Repeat rate of held keys, in repeats per second", min = 0,
+        #[default(600)]
+        pub repeat_delay: Int => "Delay before a held key starts repeating, in ms", min = 0,
+        #[default(0.0)]
+        pub sensitivity: Float => "Mouse sensitivity", min = -1.0, max = 1.0,
+        pub accel_profile: String => "Pointer acceleration profile, empty uses the libinput default",
+            values = ["adaptive", "flat"],
+        #[default(Bool::False)]
+        pub force_no_accel: Bool => "Disables pointer acceleration, not recommended",
+        #[default(Bool::False)]
+        pub left_handed: Bool => "Switches the left and right mouse buttons",
+        pub scroll_method: String => "Scroll method, empty uses the libinput default",
+            values = ["2fg", "edge", "on_button_down", "no_scroll"],
+        pub scroll_button: Int => "Scroll button for `on_button_down`, 0 is the default", min = 0,
+        #[default(Bool::False)]
+        pub natural_scroll: Bool => "Inverts the scroll direction",
+        #[default(1)]
+        pub follow_mouse: Int => "How the keyboard focus follows the mouse", min = 0, max = 3,
+        #[default(1)]
+        pub float_switch_override_focus: Int =>
+            "Whether switching between tiled and floating windows moves the focus",
+            min = 0, max = 2,
+    }
+    sections {
+        touchpad: TouchpadData,
+        touchdevice: TouchDeviceData,
+    }
+}
+
+section_data! {
+    /// `input { touchpad { ... } }`
+    pub struct TouchpadData {
+        #[default(Bool::True)]
+        pub disable_while_typing: Bool => "Disables the touchpad while typing",
+        #[default(Bool::False)]
+        pub natural_scroll: Bool => "Inverts the scroll direction",
+        #[default(1.0)]
+        pub scroll_factor: Float => "Multiplier of the scroll distance", min = 0.0,
+        #[default(Bool::False)]
+        pub middle_button_emulation: Bool =>
+            "Clicking with the left and right button at once sends a middle click",
+        pub tap_button_map: String => "Buttons sent by tapping with 1, 2 and 3 fingers",
+            values = ["lrm", "lmr"],
+        #[default(Bool::False)]
+        pub clickfinger_behavior: Bool =>
+            "Clicking with 1, 2 or 3 fingers sends a left, right or middle click",
+        #[default(Bool::True)]
+        pub tap_to_click: Bool => "Tapping sends a click", name = "tap-to-click",
+        #[default(Bool::False)]
+        pub drag_lock: Bool => "Lifting the finger during a tap-and-drag doesn't end the drag",
+    }
+}
+
+section_data! {
+    /// `input { touchdevice { ... } }`
+    pub struct TouchDeviceData {
+        pub transform: Int => "Transform of the input from touch devices", min = 0, max = 7,
+        pub output: String => "Monitor touch devices are bound to",
+    }
+}
+
+section_data! {
+    /// `gestures { ... }`
+    pub struct GesturesData {
+        #[default(Bool::False)]
+        pub workspace_swipe: Bool => "Enables workspace swipe gestures",
+        #[default(3)]
+        pub workspace_swipe_fingers: Int => "Number of fingers for the swipe", min = 1,
+        #[default(300)]
+        pub workspace_swipe_distance: Int => "Swipe distance in px", min = 0,
+        #[default(Bool::True)]
+        pub workspace_swipe_invert: Bool => "Inverts the swipe direction",
+        #[default(30)]
+        pub workspace_swipe_min_speed_to_force: Int =>
+            "Minimum speed in px per event to force a workspace change, 0 disables it", min = 0,
+        #[default(0.5)]
+        pub workspace_swipe_cancel_ratio: Float =>
+            "How far the swipe has to go to change the workspace", min = 0.0, max = 1.0,
+        #[default(Bool::True)]
+        pub workspace_swipe_create_new: Bool =>
+            "Swiping right on the last workspace creates a new one",
+        #[default(Bool::False)]
+        pub workspace_swipe_forever: Bool => "Swiping doesn't clamp at neighbouring workspaces",
+        #[default(Bool::False)]
+        pub workspace_swipe_numbered: Bool => "Swiping only goes through numbered workspaces",
+    }
+}
+
+section_data! {
+    /// `misc { ... }`
+    pub struct MiscData {
+        #[default(Bool::False)]
+        pub disable_hyprland_logo: Bool => "Disables the Hyprland logo background",
+        #[default(Bool::False)]
+        pub disable_splash_rendering: Bool => "Disables the splash text below the logo",
+        #[default(Bool::True)]
+        pub vfr: Bool => "Lowers the amount of frames sent when nothing happens on screen",
+        pub vrr: Int => "Adaptive sync, 0 is off, 1 is on and 2 is fullscreen only",
+            min = 0, max = 2,
+        #[default(Bool::False)]
+        pub mouse_move_enables_dpms: Bool => "Moving the mouse turns the monitors back on",
+        #[default(Bool::False)]
+        pub key_press_enables_dpms: Bool => "Pressing a key turns the monitors back on",
+        #[default(Bool::True)]
+        pub always_follow_on_dnd: Bool => "The focus follows the mouse while dragging and dropping",
+        #[default(Bool::True)]
+        pub layers_hog_keyboard_focus: Bool => "Layers with keyboard focus keep it",
+        #[default(Bool::False)]
+        pub animate_manual_resizes: Bool => "Animates resizes with the mouse",
+        #[default(Bool::False)]
+        pub animate_mouse_windowdragging: Bool => "Animates windows dragged with the mouse",
+        #[default(Bool::False)]
+        pub disable_autoreload: Bool => "Disables reloading the config when it changes",
+        #[default(Bool::False)]
+        pub enable_swallow: Bool => "Enables window swallowing",
+        pub swallow_regex: String => "Class regex of windows that swallow the windows they spawn",
+        #[default(Bool::False)]
+        pub focus_on_activate: Bool => "Focuses windows that request to be activated",
+        #[default(Bool::True)]
+        pub no_direct_scanout: Bool => "Disables direct scanout for fullscreen windows",
+        #[default(Bool::True)]
+        pub hide_cursor_on_touch: Bool => "Hides the cursor when the last input was a touch",
+        #[default(Bool::True)]
+        pub mouse_move_focuses_monitor: Bool => "Moving the mouse to a monitor focuses it",
+        #[default(Bool::False)]
+        pub suppress_portal_warnings: Bool => "Disables warnings about incompatible portals",
+        #[default(Bool::False)]
+        pub render_ahead_of_time: Bool => "Starts rendering before the monitor refreshes",
+        #[default(1)]
+        pub render_ahead_safezone: Int => "Rendering ahead of time safezone, in ms", min = 1,
+        #[default(1.0)]
+        pub cursor_zoom_factor: Float => "Zooms the monitor around the cursor", min = 1.0,
+        #[default(Bool::True)]
+        pub cursor_zoom_rigid: Bool => "The zoomed area follows the cursor rigidly",
+        #[default(Bool::False)]
+        pub allow_session_lock_restore: Bool =>
+            "Allows restarting a lockscreen app after it crashed",
+        #[default(8)]
+        pub groupbar_titles_font_size: Int => "Font size of the groupbar titles", min = 1,
+        #[default(Bool::True)]
+        pub groupbar_gradients: Bool => "Enables gradients in the groupbar",
+        #[default(Color::rgb(0x111111))]
+        pub background_color: Color => "Background color behind the wallpaper",
+    }
+}
+
+section_data! {
+    /// `binds { ... }`
+    pub struct BindsData {
+        #[default(Bool::False)]
+        pub pass_mouse_when_bound: Bool => "Passes mouse clicks to windows when they are bound",
+        #[default(300)]
+        pub scroll_event_delay: Int =>
+            "Delay in ms after a scroll event before another one is handled", min = 0,
+        #[default(Bool::False)]
+        pub workspace_back_and_forth: Bool =>
+            "Switching to the current workspace goes to the previous one",
+        #[default(Bool::False)]
+        pub allow_workspace_cycles: Bool => "The previous workspace isn't forgotten on switching",
+        pub focus_preferred_method: Int =>
+            "How `movefocus` picks a window, 0 by history and 1 by the longest shared edge",
+            min = 0, max = 1,
+    }
+}
+
+section_data! {
+    /// `debug { ... }`
+    pub struct DebugData {
+        #[default(Bool::False)]
+        pub overlay: Bool => "Shows the debug overlay",
+        #[default(Bool::False)]
+        pub damage_blink: Bool => "Flashes damaged areas, may cause seizures",
+        #[default(Bool::False)]
+        pub disable_logs: Bool => "Disables logging to a file",
+        #[default(Bool::True)]
+        pub disable_time: Bool => "Disables time logging",
+        #[default(2)]
+        pub damage_tracking: Int => "Damage tracking, 0 is none, 1 is monitor and 2 is full",
+            min = 0, max = 2,
+        #[default(Bool::False)]
+        pub enable_stdout_logs: Bool => "Also logs to stdout",
+        pub manual_crash: Int => "Crashes Hyprland when set to 1, for debugging", min = 0, max = 1,
+        #[default(Bool::False)]
+        pub suppress_errors: Bool => "Hides config parsing errors",
+    }
+}
+
+section_data! {
+    /// `dwindle { ... }`
+    pub struct DwindleData {
+        #[default(Bool::False)]
+        pub pseudotile: Bool => "Enables pseudotiling",
+        pub force_split: Int =>
+            "Split direction, 0 follows the mouse, 1 is always left or top and 2 right or bottom",
+            min = 0, max = 2,
+        #[default(Bool::False)]
+        pub preserve_split: Bool => "Keeps the split direction regardless of the container size",
+        #[default(0.8)]
+        pub special_scale_factor: Float => "Scale of windows on special workspaces",
+            min = 0.0, max = 1.0,
+        #[default(1.0)]
+        pub split_width_multiplier: Float => "Aspect ratio threshold for splitting", min = 0.0,
+        #[default(Bool::False)]
+        pub no_gaps_when_only: Bool => "Disables gaps when there is only one window",
+        #[default(Bool::True)]
+        pub use_active_for_splits: Bool => "Splits the active window instead of the hovered one",
+        #[default(1.0)]
+        pub default_split_ratio: Float => "Split ratio of new splits", min = 0.1, max = 1.9,
+    }
+}
+
+section_data! {
+    /// `master { ... }`
+    pub struct MasterData {
+        #[default(Bool::False)]
+        pub allow_small_split: Bool => "Allows additional masters to be added horizontally",
+        #[default(0.8)]
+        pub special_scale_factor: Float => "Scale of windows on special workspaces",
+            min = 0.0, max = 1.0,
+        #[default(0.55)]
+        pub mfact: Float => "Size of the master area", min = 0.0, max = 1.0,
+        #[default(Bool::True)]
+        pub new_is_master: Bool => "New windows become the master",
+        #[default(Bool::False)]
+        pub new_on_top: Bool => "New windows are added to the top of the stack",
+        #[default(Bool::False)]
+        pub no_gaps_when_only: Bool => "Disables gaps when there is only one window",
+        #[default("left".to_string())]
+        pub orientation: String => "Position of the master area",
+            values = ["left", "right", "top", "bottom", "center"],
+        #[default(Bool::True)]
+        pub inherit_fullscreen: Bool => "New windows inherit the fullscreen state",
+        #[default(Bool::False)]
+        pub always_center_master: Bool =>
+            "Keeps the master centered with the `center` orientation, even with one slave",
+    }
+}
+
+section_data! {
+    /// `device:<name> { ... }`, only the options that are set override the `input` ones
+    pub struct DeviceData {
+        pub sensitivity: Option<Float> => "Mouse sensitivity", min = -1.0, max = 1.0,
+        pub accel_profile: Option<String> => "Pointer acceleration profile",
+            values = ["adaptive", "flat"],
+        pub natural_scroll: Option<Bool> => "Inverts the scroll direction",
+        pub left_handed: Option<Bool> => "Switches the left and right mouse buttons",
+        pub scroll_method: Option<String> => "Scroll method",
+            values = ["2fg", "edge", "on_button_down", "no_scroll"],
+        pub scroll_button: Option<Int> => "Scroll button for `on_button_down`", min = 0,
+        pub kb_model: Option<String> => "XKB keyboard model",
+        pub kb_layout: Option<String> => "XKB keyboard layouts",
+        pub kb_variant: Option<String> => "XKB keyboard variants",
+        pub kb_options: Option<String> => "XKB keyboard options",
+        pub kb_rules: Option<String> => "XKB keyboard rules",
+        pub kb_file: Option<String> => "Path to a custom .xkb file",
+        pub numlock_by_default: Option<Bool> => "Enables numlock on startup",
+        pub repeat_rate: Option<Int> => "Repeat rate of held keys, in repeats per second",
+            min = 0,
+        pub repeat_delay: Option<Int> => "Delay before a held key starts repeating, in ms",
+            min = 0,
+        pub disable_while_typing: Option<Bool> => "Disables the touchpad while typing",
+        pub middle_button_emulation: Option<Bool> =>
+            "Clicking with the left and right button at once sends a middle click",
+        pub tap_button_map: Option<String> => "Buttons sent by tapping with 1, 2 and 3 fingers",
+            values = ["lrm", "lmr"],
+        pub clickfinger_behavior: Option<Bool> =>
+            "Clicking with 1, 2 or 3 fingers sends a left, right or middle click",
+        pub tap_to_click: Option<Bool> => "Tapping sends a click", name = "tap-to-click",
+        pub drag_lock: Option<Bool> =>
+            "Lifting the finger during a tap-and-drag doesn't end the drag",
+        pub transform: Option<Int> => "Transform of the input from touch devices",
+            min = 0, max = 7,
+        pub output: Option<String> => "Monitor the device is bound to",
+        pub enabled: Option<Bool> => "Enables the device",
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Layout {
     Master,
     Dwindle,
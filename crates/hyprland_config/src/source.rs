@@ -0,0 +1,339 @@
+//! Multi-file configs. `source = path` lines are followed recursively, every file keeps its own
+//! [Config], so edits can be written back to the file they were made in.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use eyre::WrapErr;
+use itertools::Itertools;
+
+use crate::{cst::Node, Config, ConfigElement};
+
+/// A single file of a [ConfigTree]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigFile {
+    /// Canonical path of the file
+    pub path: PathBuf,
+    pub config: Config,
+    /// Contents of the file when it was loaded or last saved
+    original: String,
+}
+
+impl ConfigFile {
+    /// Whether the config was edited since it was loaded or last saved
+    pub fn is_modified(&self) -> bool {
+        self.config.to_string() != self.original
+    }
+}
+
+/// `source = ...` line at `node` in `file` that includes `target`, all indices into the tree
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Include {
+    pub file: usize,
+    pub node: usize,
+    pub target: usize,
+}
+
+/// Position of a top-level node in a [ConfigTree]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeLocation {
+    pub file: usize,
+    pub node: usize,
+}
+
+/// Top-level node together with where it came from
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SourcedNode<'a> {
+    pub location: NodeLocation,
+    pub path: &'a Path,
+    pub node: &'a Node,
+}
+
+/// Root config with all files it sources, directly or through other sourced files
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigTree {
+    /// The root config is always the first file
+    files: Vec<ConfigFile>,
+    includes: Vec<Include>,
+}
+
+impl ConfigTree {
+    /// Loads the config at `path` and everything it sources. Fails on missing or unparsable
+    /// files and on cyclic `source` chains
+    pub fn load(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        Self::load_with(path.as_ref(), false)
+    }
+
+    /// Like [ConfigTree::load], but files are parsed with [Config::parse_recovering], so only
+    /// missing files and cyclic `source` chains are errors
+    pub fn load_recovering(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        Self::load_with(path.as_ref(), true)
+    }
+
+    fn load_with(path: &Path, recover: bool) -> eyre::Result<Self> {
+        let mut tree = Self {
+            files: vec![],
+            includes: vec![],
+        };
+        tree.load_file(path, &mut vec![], &mut BTreeMap::new(), recover)?;
+
+        Ok(tree)
+    }
+
+    pub fn root(&self) -> &ConfigFile {
+        &self.files[0]
+    }
+
+    pub fn files(&self) -> &[ConfigFile] {
+        &self.files
+    }
+
+    pub fn file_mut(&mut self, index: usize) -> Option<&mut ConfigFile> {
+        self.files.get_mut(index)
+    }
+
+    /// Index of the file with the given path
+    pub fn file_index(&self, path: impl AsRef<Path>) -> Option<usize> {
+        let path = path.as_ref().canonicalize().ok()?;
+
+        self.files.iter().position(|file| file.path == path)
+    }
+
+    /// Edges of the include graph, a file's includes are listed after those of the files it sources
+    pub fn includes(&self) -> &[Include] {
+        &self.includes
+    }
+
+    /// Top-level nodes of all files in the order Hyprland reads them, with the nodes of a
+    /// sourced file following its `source` line
+    pub fn nodes(&self) -> Vec<SourcedNode<'_>> {
+        let mut nodes = vec![];
+        self.collect_nodes(0, &mut nodes);
+
+        nodes
+    }
+
+    /// Single config with the nodes of all files in reading order, for queries across files.
+    /// Node spans refer to the files the nodes came from
+    pub fn merged(&self) -> Config {
+        let mut config = Config::default();
+        config
+            .nodes_mut()
+            .extend(self.nodes().into_iter().map(|node| node.node.clone()));
+
+        config
+    }
+
+    pub fn node(&self, location: NodeLocation) -> Option<&Node> {
+        self.files
+            .get(location.file)
+            .and_then(|file| file.config.nodes().get(location.node))
+    }
+
+    pub fn node_mut(&mut self, location: NodeLocation) -> Option<&mut Node> {
+        self.files
+            .get_mut(location.file)
+            .and_then(|file| file.config.nodes_mut().get_mut(location.node))
+    }
+
+    /// Files edited since they were loaded or last saved
+    pub fn modified(&self) -> impl Iterator<Item = &ConfigFile> {
+        self.files.iter().filter(|file| file.is_modified())
+    }
+
+    /// Writes every modified file back to its own path and returns the written paths
+    pub fn save(&mut self) -> eyre::Result<Vec<PathBuf>> {
+        let mut saved = vec![];
+
+        for file in self.files.iter_mut().filter(|file| file.is_modified()) {
+            let contents = file.config.to_string();
+
+            std::fs::write(&file.path, &contents)
+                .wrap_err_with(|| format!("Couldn't write {}", file.path.display()))?;
+
+            file.original = contents;
+            saved.push(file.path.clone());
+        }
+
+        Ok(saved)
+    }
+
+    fn collect_nodes<'a>(&'a self, file: usize, nodes: &mut Vec<SourcedNode<'a>>) {
+        let config_file = &self.files[file];
+
+        for (index, node) in config_file.config.nodes().iter().enumerate() {
+            nodes.push(SourcedNode {
+                location: NodeLocation { file, node: index },
+                path: &config_file.path,
+                node,
+            });
+
+            if let Some(include) = self
+                .includes
+                .iter()
+                .find(|include| include.file == file && include.node == index)
+            {
+                self.collect_nodes(include.target, nodes);
+            }
+        }
+    }
+
+    fn load_file(
+        &mut self,
+        path: &Path,
+        stack: &mut Vec<PathBuf>,
+        variables: &mut BTreeMap<String, String>,
+        recover: bool,
+    ) -> eyre::Result<usize> {
+        let path = path
+            .canonicalize()
+            .wrap_err_with(|| format!("Couldn't read {}", path.display()))?;
+
+        if let Some(start) = stack.iter().position(|sourced| *sourced == path) {
+            return Err(eyre::eyre!(
+                "Cyclic source: {}",
+                stack[start..]
+                    .iter()
+                    .chain([&path])
+                    .map(|path| path.display())
+                    .join(" -> ")
+            ));
+        }
+
+        // Files sourced from several places are only loaded once
+        if let Some(index) = self.files.iter().position(|file| file.path == path) {
+            return Ok(index);
+        }
+
+        let original = std::fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Couldn't read {}", path.display()))?;
+        let config = match recover {
+            true => Config::parse_recovering(&original),
+            false => original
+                .parse()
+                .wrap_err_with(|| format!("Couldn't parse {}", path.display()))?,
+        };
+
+        // Variables and `source` lines in reading order, a source path can use the variables
+        // defined above it, in this file or in files sourced before
+        let lines = config
+            .nodes()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| match &node.element {
+                ConfigElement::Variable(_) => Some((index, node.element.clone(), 0)),
+                ConfigElement::Keyword(keyword) if keyword.name == "source" => Some((
+                    index,
+                    node.element.clone(),
+                    original[..node.span.start].lines().count() + 1,
+                )),
+                _ => None,
+            })
+            .collect_vec();
+
+        let index = self.files.len();
+        self.files.push(ConfigFile {
+            path: path.clone(),
+            config,
+            original,
+        });
+
+        let dir = path.parent().unwrap_or(Path::new("/")).to_path_buf();
+        stack.push(path.clone());
+
+        for (node, element, line) in lines {
+            let keyword = match element {
+                ConfigElement::Variable(variable) => {
+                    let value =
+                        substitute(&variable.value, |name| Ok(variables.get(name).cloned()))?;
+                    variables.insert(variable.name, value);
+                    continue;
+                }
+                ConfigElement::Keyword(keyword) => keyword,
+                _ => continue,
+            };
+
+            let value = &keyword.value;
+            let target = expand_path(value, variables, &dir)
+                .and_then(|target| self.load_file(&target, stack, variables, recover))
+                .wrap_err_with(|| format!("{}:{line}: `source = {value}`", path.display()))?;
+
+            self.includes.push(Include {
+                file: index,
+                node,
+                target,
+            });
+        }
+
+        stack.pop();
+
+        Ok(index)
+    }
+}
+
+/// Expands the config `variables`, then a leading `~` and `$VAR`/`${VAR}` environment
+/// variables, the way Hyprland reads `source` lines. Relative paths are resolved against
+/// `base_dir`, the directory of the file with the `source` line
+pub fn expand_path(
+    value: &str,
+    variables: &BTreeMap<String, String>,
+    base_dir: &Path,
+) -> eyre::Result<PathBuf> {
+    let env = |name: &str| {
+        std::env::var(name).map_err(|_| eyre::eyre!("Environment variable `{name}` is not set"))
+    };
+
+    let value = substitute(value.trim(), |name| Ok(variables.get(name).cloned()))?;
+    let value = match value.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("{}{rest}", env("HOME")?),
+        _ => value,
+    };
+
+    Ok(base_dir.join(substitute(&value, |name| env(name).map(Some))?))
+}
+
+/// Replaces `$name` and `${name}` with the value `lookup` gives, references it gives `None`
+/// for are kept as written
+fn substitute(
+    value: &str,
+    lookup: impl Fn(&str) -> eyre::Result<Option<String>>,
+) -> eyre::Result<String> {
+    let mut expanded = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+
+        let braced = chars.next_if_eq(&'{').is_some();
+        let name: String = match braced {
+            true => chars.by_ref().take_while(|c| *c != '}').collect(),
+            false => {
+                std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_'))
+                    .collect()
+            }
+        };
+
+        if name.is_empty() {
+            expanded.push('$');
+            continue;
+        }
+
+        match lookup(&name)? {
+            Some(value) => expanded.push_str(&value),
+            None if braced => expanded.push_str(&format!("${{{name}}}")),
+            None => expanded.push_str(&format!("${name}")),
+        }
+    }
+
+    Ok(expanded)
+}
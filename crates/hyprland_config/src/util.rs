@@ -1,4 +1,14 @@
-use nom::{bytes::complete::take_till, combinator::map_res, IResult};
+use nom::{
+    bytes::complete::take_till,
+    combinator::{map_res, verify},
+    number::complete::float,
+    IResult,
+};
+
+use crate::{
+    section::Layout,
+    variable::{Bool, Color, Gradient, Vec2},
+};
 
 pub type Int = i32;
 pub type Float = f32;
@@ -19,3 +29,60 @@ impl Parse for Int {
         })(input)
     }
 }
+
+impl Parse for Float {
+    /// Finite floats only, `inf` and `nan` aren't values Hyprland accepts
+    fn parse(input: &str) -> IResult<&str, Self>
+    where
+        Self: Sized,
+    {
+        verify(float, |value: &Float| value.is_finite())(input)
+    }
+}
+
+/// Conversion of a whole raw option value into a typed one, where the expected type is known
+pub trait FromValue: Sized {
+    fn from_value(value: &str) -> Option<Self>;
+}
+
+macro_rules! from_value_via_parse {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl FromValue for $ty {
+                fn from_value(value: &str) -> Option<Self> {
+                    match <$ty as Parse>::parse(value.trim()) {
+                        Ok(("", value)) => Some(value),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_value_via_parse!(Int, Float, Bool, Color, Vec2, Layout);
+
+impl FromValue for String {
+    fn from_value(value: &str) -> Option<Self> {
+        Some(value.to_string())
+    }
+}
+
+impl FromValue for Gradient {
+    /// Unlike [Gradient::parse], also accepts a single color
+    fn from_value(value: &str) -> Option<Self> {
+        match Gradient::parse(value.trim()) {
+            Ok(("", gradient)) => Some(gradient),
+            _ => Color::from_value(value).map(|color| Gradient {
+                colors: vec![color],
+                angle: None,
+            }),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &str) -> Option<Self> {
+        T::from_value(value).map(Some)
+    }
+}
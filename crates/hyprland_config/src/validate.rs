@@ -0,0 +1,530 @@
+//! Checks a config against the [Schema] and the typed keyword parsers, collecting every problem
+//! as a [Diagnostic] instead of stopping at the first one.
+
+use std::collections::{HashMap, HashSet};
+
+use nom::combinator::all_consuming;
+
+use crate::{
+    animation::{Animation, Bezier},
+    bind::{Bind, BindFlags, Unbind},
+    cst::{Node, Span},
+    diagnostic::{Diagnostic, DiagnosticCode, LineSpan, Severity},
+    keyword::Keyword,
+    monitor::MonitorRule,
+    resolve::{ResolvedConfig, VariableIssueKind},
+    rule::{LayerRule, WindowRule},
+    schema::{OptionType, Schema, ValueError},
+    section::Section,
+    source::{ConfigTree, NodeLocation},
+    util::Parse,
+    Config, ConfigElement,
+};
+
+/// Keywords that aren't options, besides `bind` with any flags
+pub const KEYWORDS: &[&str] = &[
+    "monitor",
+    "workspace",
+    "exec",
+    "exec-once",
+    "source",
+    "env",
+    "bezier",
+    "animation",
+    "unbind",
+    "submap",
+    "windowrule",
+    "windowrulev2",
+    "layerrule",
+    "blurls",
+    "wsbind",
+];
+
+/// Sections whose contents are defined by plugins and aren't checked
+const PLUGIN_SECTION: &str = "plugin";
+
+pub(crate) fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+        || name
+            .strip_prefix("bind")
+            .is_some_and(|flags| all_consuming(BindFlags::parse)(flags).is_ok())
+}
+
+/// Whether `path` is a section with options, e.g. `input:touchpad` or `device:epic-mouse-v1`
+fn is_section(path: &str) -> bool {
+    let path = match path.strip_prefix("device:") {
+        Some(name) if !name.contains(':') => "device:*",
+        _ => path,
+    };
+
+    Schema::get()
+        .options()
+        .iter()
+        .any(|option| option.path.starts_with(path) && option.path[path.len()..].starts_with(':'))
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a != *b);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(previous + 1).min(row[j] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Closest of `candidates` to `name`, if it is close enough to be a typo
+fn suggestion<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= (name.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn did_you_mean(candidate: Option<&str>) -> Option<String> {
+    candidate.map(|candidate| format!("Did you mean `{candidate}`?"))
+}
+
+struct Validator<'a> {
+    /// Contents of every file, as printed
+    sources: Vec<String>,
+    /// Paths of the files, for pointing at earlier occurrences in other files
+    paths: Vec<String>,
+    resolved: &'a ResolvedConfig,
+    diagnostics: Vec<Diagnostic>,
+    /// Option path -> file and span of the keyword that set it last
+    set: HashMap<String, (usize, Span)>,
+    /// Names of the beziers defined so far
+    beziers: HashSet<String>,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(
+        sources: Vec<String>,
+        paths: Vec<String>,
+        nodes: Vec<(NodeLocation, &Node)>,
+        resolved: &'a ResolvedConfig,
+    ) -> Vec<Diagnostic> {
+        let mut validator = Self {
+            sources,
+            paths,
+            resolved,
+            diagnostics: vec![],
+            set: HashMap::new(),
+            beziers: HashSet::from([Bezier::DEFAULT.to_string()]),
+        };
+
+        for ((location, node), resolved) in nodes.into_iter().zip(resolved.config.nodes()) {
+            validator.node(location.file, "", node, resolved);
+        }
+
+        validator.variable_issues();
+
+        let mut diagnostics = validator.diagnostics;
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.file, diagnostic.span.bytes.start));
+
+        diagnostics
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        file: usize,
+        span: Span,
+        code: DiagnosticCode,
+        message: String,
+        help: Option<String>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            file,
+            span: LineSpan::new(&self.sources[file], span),
+            code,
+            message,
+            help,
+        });
+    }
+
+    /// `section` is the path of the enclosing section, empty at the top level.
+    /// `resolved` is the same node with variables substituted
+    fn node(&mut self, file: usize, section: &str, node: &Node, resolved: &Node) {
+        let start = node.element_start();
+
+        match (&node.element, &resolved.element) {
+            (ConfigElement::Invalid(line), _) => self.invalid(file, start, line),
+            (ConfigElement::Keyword(keyword), ConfigElement::Keyword(resolved)) => {
+                match (section, keyword.name.contains(':')) {
+                    ("", false) => self.keyword(file, start, keyword, resolved),
+                    ("", true) => self.option(file, start, &keyword.name, keyword, resolved),
+                    (section, _) => {
+                        let path = format!("{section}:{}", keyword.name);
+                        self.option(file, start, &path, keyword, resolved)
+                    }
+                }
+            }
+            (ConfigElement::Section(section_node), ConfigElement::Section(resolved)) => {
+                let path = match section {
+                    "" => section_node.name.clone(),
+                    section => format!("{section}:{}", section_node.name),
+                };
+
+                self.section(file, start, &path, section_node, resolved)
+            }
+            _ => {}
+        }
+    }
+
+    fn invalid(&mut self, file: usize, start: usize, line: &str) {
+        let line = line.trim_end();
+        let span = Span::new(start, start + line.len());
+
+        let (message, help) = if line.starts_with('}') {
+            ("Unmatched `}`".to_string(), None)
+        } else if let Some(name) = line.strip_suffix('{') {
+            (
+                format!("Section `{}` is never closed", name.trim()),
+                Some("Add a `}` on its own line after the section's options".to_string()),
+            )
+        } else {
+            (
+                format!("Couldn't parse `{line}`"),
+                Some("Expected `name = value`, `name {`, `}` or a `# comment`".to_string()),
+            )
+        };
+
+        self.push(
+            Severity::Error,
+            file,
+            span,
+            DiagnosticCode::Syntax,
+            message,
+            help,
+        );
+    }
+
+    fn section(
+        &mut self,
+        file: usize,
+        start: usize,
+        path: &str,
+        section: &Section,
+        resolved: &Section,
+    ) {
+        if path == PLUGIN_SECTION || path.starts_with(&format!("{PLUGIN_SECTION}:")) {
+            return;
+        }
+
+        if !is_section(path) {
+            let parent = path.rsplit_once(':').map_or("", |(parent, _)| parent);
+            let sections = Schema::get()
+                .options()
+                .iter()
+                .filter(|option| option.deprecated.is_none())
+                .filter_map(|option| option.path.rsplit_once(':').map(|(section, _)| section))
+                .filter(|section| {
+                    section.rsplit_once(':').map_or("", |(parent, _)| parent) == parent
+                })
+                .map(|section| section.rsplit(':').next().unwrap_or(section));
+
+            self.push(
+                Severity::Error,
+                file,
+                Span::new(start, start + section.name.len()),
+                DiagnosticCode::UnknownSection,
+                format!("Unknown section `{path}`"),
+                did_you_mean(suggestion(&section.name, sections)),
+            );
+
+            return;
+        }
+
+        for (node, resolved) in section.elements.iter().zip(&resolved.elements) {
+            self.node(file, path, node, resolved);
+        }
+    }
+
+    /// Top-level keyword that isn't an option
+    fn keyword(&mut self, file: usize, start: usize, keyword: &Keyword, resolved: &Keyword) {
+        if !is_keyword(&keyword.name) {
+            self.push(
+                Severity::Error,
+                file,
+                Span::new(start, start + keyword.name.len()),
+                DiagnosticCode::UnknownKeyword,
+                format!("Unknown keyword `{}`", keyword.name),
+                did_you_mean(suggestion(&keyword.name, KEYWORDS.iter().copied())),
+            );
+
+            return;
+        }
+
+        if resolved.value.contains('$') {
+            return;
+        }
+
+        let (code, error) = if let Some(bind) = Bind::from_keyword(resolved) {
+            (DiagnosticCode::MalformedBind, bind.err())
+        } else if let Some(unbind) = Unbind::from_keyword(resolved) {
+            (DiagnosticCode::MalformedBind, unbind.err())
+        } else if let Some(rule) = MonitorRule::from_keyword(resolved) {
+            (DiagnosticCode::MalformedRule, rule.err())
+        } else if let Some(rule) = WindowRule::from_keyword(resolved) {
+            (DiagnosticCode::MalformedRule, rule.err())
+        } else if let Some(rule) = LayerRule::from_keyword(resolved) {
+            (DiagnosticCode::MalformedRule, rule.err())
+        } else if let Some(bezier) = Bezier::from_keyword(resolved) {
+            let bezier = bezier.map(|bezier| {
+                self.beziers.insert(bezier.name);
+            });
+            (DiagnosticCode::MalformedAnimation, bezier.err())
+        } else if let Some(animation) = Animation::from_keyword(resolved) {
+            let animation = animation.map(|animation| self.curve(file, start, keyword, animation));
+            (DiagnosticCode::MalformedAnimation, animation.err())
+        } else {
+            return;
+        };
+
+        if let Some(error) = error {
+            self.push(
+                Severity::Error,
+                file,
+                self.value_span(start, keyword),
+                code,
+                format!("Malformed `{}`", keyword.name),
+                Some(error.to_string()),
+            );
+        }
+    }
+
+    /// Checks that the curve of `animation` is defined
+    fn curve(&mut self, file: usize, start: usize, keyword: &Keyword, animation: Animation) {
+        let Some(curve) = animation.curve else {
+            return;
+        };
+        if self.beziers.contains(&curve) {
+            return;
+        }
+
+        let span = self.value_span(start, keyword);
+        let span = keyword.value.rfind(&curve).map_or(span, |offset| {
+            Span::new(span.start + offset, span.start + offset + curve.len())
+        });
+
+        self.push(
+            Severity::Error,
+            file,
+            span,
+            DiagnosticCode::UndefinedBezier,
+            format!("Bezier `{curve}` is not defined"),
+            did_you_mean(suggestion(&curve, self.beziers.iter().map(String::as_str)))
+                .or_else(|| Some("Define it with a `bezier` line before this one".to_string())),
+        );
+    }
+
+    fn option(
+        &mut self,
+        file: usize,
+        start: usize,
+        path: &str,
+        keyword: &Keyword,
+        resolved: &Keyword,
+    ) {
+        let name_span = Span::new(start, start + keyword.name.len());
+
+        let Some(option) = Schema::get().option(path) else {
+            if is_keyword(&keyword.name) {
+                return self.keyword(file, start, keyword, resolved);
+            }
+
+            let (section, name) = path.rsplit_once(':').unwrap_or(("", path));
+            let schema_section = match section.strip_prefix("device:") {
+                Some(_) => "device:*",
+                None => section,
+            };
+            let options = Schema::get()
+                .options()
+                .iter()
+                .filter(|option| option.deprecated.is_none())
+                .filter_map(|option| option.path.strip_prefix(schema_section)?.strip_prefix(':'))
+                .filter(|option| !option.contains(':'));
+
+            return self.push(
+                Severity::Error,
+                file,
+                name_span,
+                DiagnosticCode::UnknownOption,
+                format!("Unknown option `{path}`"),
+                did_you_mean(
+                    suggestion(name, options)
+                        .map(|option| format!("{section}:{option}"))
+                        .as_deref(),
+                ),
+            );
+        };
+
+        if let Some(deprecated) = &option.deprecated {
+            let help = match deprecated.replaced_by {
+                Some(replaced_by) => format!("Use `{replaced_by}` instead, {}", deprecated.note),
+                None => capitalize(deprecated.note),
+            };
+
+            return self.push(
+                Severity::Warning,
+                file,
+                name_span,
+                DiagnosticCode::DeprecatedOption,
+                format!("`{path}` is deprecated"),
+                Some(help),
+            );
+        }
+
+        let span = Span::new(start, start + keyword.to_string().len());
+
+        if let Some((previous_file, previous)) = self.set.insert(path.to_string(), (file, span)) {
+            let line = LineSpan::new(&self.sources[previous_file], previous)
+                .start
+                .line;
+            let location = match previous_file == file {
+                true => format!("line {line}"),
+                false => format!("line {line} of {}", self.paths[previous_file]),
+            };
+
+            self.push(
+                Severity::Warning,
+                file,
+                span,
+                DiagnosticCode::DuplicateKey,
+                format!("`{path}` is set more than once, only the last value is used"),
+                Some(format!("Previously set on {location}")),
+            );
+        }
+
+        if resolved.value.contains('$') {
+            return;
+        }
+
+        if let Err(error) = option.check(&resolved.value) {
+            let code = match (&error, option.ty) {
+                (ValueError::Type(_), OptionType::Color | OptionType::Gradient) => {
+                    DiagnosticCode::InvalidColor
+                }
+                (ValueError::Type(_), _) => DiagnosticCode::TypeMismatch,
+                (ValueError::Range { .. }, _) => DiagnosticCode::OutOfRange,
+                (ValueError::NotAllowed(_), _) => DiagnosticCode::InvalidValue,
+            };
+
+            self.push(
+                Severity::Error,
+                file,
+                self.value_span(start, keyword),
+                code,
+                format!("Invalid value `{}` for `{path}`", resolved.value),
+                Some(capitalize(&error.to_string())),
+            );
+        }
+    }
+
+    fn value_span(&self, start: usize, keyword: &Keyword) -> Span {
+        let value_start = start + keyword.name.len() + keyword.separator.len();
+
+        Span::new(value_start, value_start + keyword.value.len())
+    }
+
+    fn variable_issues(&mut self) {
+        for issue in &self.resolved.issues {
+            let (severity, code) = match issue.kind {
+                VariableIssueKind::Undefined => {
+                    (Severity::Error, DiagnosticCode::UndefinedVariable)
+                }
+                VariableIssueKind::UsedBeforeDefinition => (
+                    Severity::Warning,
+                    DiagnosticCode::VariableUsedBeforeDefinition,
+                ),
+                VariableIssueKind::Unused | VariableIssueKind::Redefined { .. } => continue,
+            };
+
+            let file = issue.location.file;
+            let reference = format!("${}", issue.name);
+            let line = issue.span.slice(&self.sources[file]);
+            let span = line.find(&reference).map_or(issue.span, |offset| {
+                let start = issue.span.start + offset;
+                Span::new(start, start + reference.len())
+            });
+
+            self.diagnostics.push(Diagnostic {
+                severity,
+                file,
+                span: LineSpan::new(&self.sources[file], span),
+                code,
+                message: capitalize(&issue.to_string()),
+                help: None,
+            });
+        }
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+impl Config {
+    /// Checks every line against the option schema and the keyword parsers, after substituting
+    /// variables. Lines that couldn't be parsed (see [Config::parse_recovering]) are reported
+    /// as syntax errors. Spans refer to the source the config was parsed from
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let resolved = self.resolve();
+        let nodes = self
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(node, element)| (NodeLocation { file: 0, node }, element))
+            .collect();
+
+        Validator::validate(
+            vec![self.to_string()],
+            vec![String::new()],
+            nodes,
+            &resolved,
+        )
+    }
+}
+
+impl ConfigTree {
+    /// Validates all files together, so variables and duplicate options are tracked across
+    /// `source` lines, see [Config::validate]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let resolved = self.resolve();
+        let nodes = self
+            .nodes()
+            .into_iter()
+            .map(|sourced| (sourced.location, sourced.node))
+            .collect();
+
+        Validator::validate(
+            self.files()
+                .iter()
+                .map(|file| file.config.to_string())
+                .collect(),
+            self.files()
+                .iter()
+                .map(|file| file.path.display().to_string())
+                .collect(),
+            nodes,
+            &resolved,
+        )
+    }
+}
@@ -1,24 +1,21 @@
-use std::{
-    fmt::{Display, Formatter},
-    num::ParseIntError,
-};
+use std::fmt::{Display, Formatter};
 
 use itertools::Itertools;
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until, take_while, take_while1},
+    bytes::complete::{tag, take_until, take_while1},
     character::complete::{digit1, space1},
     combinator::verify,
-    combinator::{map, map_res, opt, value},
+    combinator::{all_consuming, map, map_res, opt, value},
     error::Error,
-    multi::separated_list1,
-    number::complete::float,
+    multi::{many0, separated_list1},
     sequence::{delimited, preceded, separated_pair, terminated, tuple},
     AsChar, IResult,
 };
 
 use crate::util::{Float, Int, Parse};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Variable {
     pub name: String,
     pub value: VariableValue,
@@ -46,7 +43,7 @@ impl Parse for Variable {
 }
 
 impl Variable {
-    fn parse_name(input: &str) -> IResult<&str, String> {
+    pub fn parse_name(input: &str) -> IResult<&str, String> {
         map(
             preceded(
                 tag("$"),
@@ -63,6 +60,7 @@ impl Variable {
 macro_rules! variable_value {
     ($($variant:ident($ty:ty) $(doc: $doc:literal)*),* $(,)*) => {
         #[derive(Debug, Clone, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub enum VariableValue {
             $(
                 $(#[doc = $doc])*
@@ -92,31 +90,49 @@ variable_value!(
     Float(Float),
     Color(Color),
     Vec2(Vec2) doc: "0 0 | -10.9 99.1",
-    Mod(Modifier),
+    Mod(ModifierSet) doc: "SHIFT | SUPER_SHIFT | $mainMod CTRL",
     String(String),
     Gradient(Gradient),
     Variable(String),
 );
 
 impl Parse for VariableValue {
+    /// Parses a value without knowing its option, trying in order:
+    ///
+    /// 1. modifiers with at least one modifier, `SUPER_SHIFT`, `$mainMod CTRL`
+    /// 2. `$variable`
+    /// 3. gradient of two or more colors, then a single color
+    /// 4. `X Y`, then an integer, then a finite float
+    /// 5. `true`/`false`, `yes`/`no`, `on`/`off`
+    /// 6. `"string"`
+    ///
+    /// So `1` is an [VariableValue::Int], never a [Bool::One], and `2.0` is a float. Use
+    /// [OptionType::parse_value](crate::schema::OptionType::parse_value) when the option is
+    /// known, which reads `1` as a bool or a float when the option expects one
     fn parse(input: &str) -> IResult<&str, Self> {
         let string_literal = delimited(tag("\""), take_until("\""), tag("\""));
 
+        // Before variables, so that `$mainMod CTRL` isn't cut after `$mainMod`
+        let modifiers = verify(ModifierSet::parse, |set: &ModifierSet| {
+            set.modifiers().next().is_some()
+        });
+
         alt((
+            map(modifiers, Self::Mod),
             map(Variable::parse_name, Self::Variable),
             map(Gradient::parse, Self::Gradient),
             map(Color::parse, Self::Color),
             map(Vec2::parse, Self::Vec2),
             map(Int::parse, Self::Int),
-            map(float, Self::Float),
+            map(Float::parse, Self::Float),
             map(Bool::parse, Self::Bool),
-            map(Modifier::parse, Self::Mod),
             map(string_literal, |s: &str| Self::String(s.to_string())),
         ))(input)
     }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Bool {
     True,
     False,
@@ -168,60 +184,169 @@ impl Parse for Bool {
     }
 }
 
+impl std::ops::Not for Bool {
+    type Output = Bool;
+
+    /// The opposite value, written in the same style
+    fn not(self) -> Self::Output {
+        match self {
+            Bool::True => Bool::False,
+            Bool::False => Bool::True,
+            Bool::Yes => Bool::No,
+            Bool::No => Bool::Yes,
+            Bool::On => Bool::Off,
+            Bool::Off => Bool::On,
+            Bool::One => Bool::Zero,
+            Bool::Zero => Bool::One,
+        }
+    }
+}
+
+impl From<Bool> for bool {
+    fn from(value: Bool) -> Self {
+        matches!(value, Bool::True | Bool::Yes | Bool::On | Bool::One)
+    }
+}
+
+/// How a color is written in the config
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorNotation {
+    /// `rgba(b3ff1aee)`
+    Rgba,
+    /// `rgb(b3ff1a)`, colors that aren't opaque are written as [ColorNotation::Rgba]
+    Rgb,
+    /// `0xeeb3ff1a`, in ARGB order
+    Legacy,
+    /// `#b3ff1a` or `#b3ff1aee`. Only useful outside of the config, where `#` starts a comment
+    Hex,
+}
+
+/// Color with 8-bit channels, which remembers the notation it was written in
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub enum Color {
-    /// rgba(b3ff1aee)
-    RGBA(u64),
-    /// rgb(b3ff1a)
-    RGB(u64),
-    /// 0xeeb3ff1a -> ARGB order
-    Legacy(u64),
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+    notation: ColorNotation,
 }
 
 impl Display for Color {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Color::RGBA(rgba) => write!(f, "rgba({rgba:x})"),
-            Color::RGB(rgb) => write!(f, "rgb({rgb:x})"),
-            Color::Legacy(argb) => write!(f, "{argb:#x}"),
+        let Self { r, g, b, a, .. } = self;
+
+        match self.notation {
+            ColorNotation::Rgb if *a == 0xff => write!(f, "rgb({r:02x}{g:02x}{b:02x})"),
+            ColorNotation::Rgba | ColorNotation::Rgb => {
+                write!(f, "rgba({r:02x}{g:02x}{b:02x}{a:02x})")
+            }
+            ColorNotation::Legacy => write!(f, "0x{a:02x}{r:02x}{g:02x}{b:02x}"),
+            ColorNotation::Hex if *a == 0xff => write!(f, "#{r:02x}{g:02x}{b:02x}"),
+            ColorNotation::Hex => write!(f, "#{r:02x}{g:02x}{b:02x}{a:02x}"),
         }
     }
 }
 
 impl Parse for Color {
     fn parse(input: &str) -> IResult<&str, Self> {
-        let rgba = delimited(
-            tag("rgba("),
-            map_res(take_until(")"), Color::rgba),
-            tag(")"),
-        );
-
-        let rgb = delimited(tag("rgb("), map_res(take_until(")"), Color::rgb), tag(")"));
+        /// Exactly `digits` hex digits as a number
+        fn hex<'a>(digits: usize) -> impl FnMut(&'a str) -> IResult<&'a str, u32> {
+            map_res(
+                verify(take_while1(|c: char| c.is_hex_digit()), move |s: &str| {
+                    s.len() == digits
+                }),
+                |s| u32::from_str_radix(s, 16),
+            )
+        }
 
-        let legacy = preceded(
-            tag("0x"),
-            map_res(take_while(|c: char| c.is_hex_digit()), Color::legacy),
-        );
+        let rgba = map(delimited(tag("rgba("), hex(8), tag(")")), Color::rgba);
+        let rgb = map(delimited(tag("rgb("), hex(6), tag(")")), Color::rgb);
+        let legacy = map(preceded(tag("0x"), hex(8)), Color::legacy);
+        let hex_rgba = map(preceded(tag("#"), hex(8)), |rgba| {
+            Color::rgba(rgba).with_notation(ColorNotation::Hex)
+        });
+        let hex_rgb = map(preceded(tag("#"), hex(6)), |rgb| {
+            Color::rgb(rgb).with_notation(ColorNotation::Hex)
+        });
 
-        alt((rgba, rgb, legacy))(input)
+        alt((rgba, rgb, legacy, hex_rgba, hex_rgb))(input)
     }
 }
 
 impl Color {
-    pub fn rgba(input: &str) -> Result<Self, ParseIntError> {
-        u64::from_str_radix(input, 16).map(Color::RGBA)
+    /// Color from its channels, written as `rgba()`
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self {
+            r,
+            g,
+            b,
+            a,
+            notation: ColorNotation::Rgba,
+        }
+    }
+
+    /// `0xRRGGBBAA`, written as `rgba(RRGGBBAA)`
+    pub const fn rgba(rgba: u32) -> Self {
+        let [r, g, b, a] = rgba.to_be_bytes();
+
+        Self::new(r, g, b, a)
+    }
+
+    /// Opaque `0xRRGGBB`, written as `rgb(RRGGBB)`
+    pub const fn rgb(rgb: u32) -> Self {
+        Self::rgba((rgb << 8) | 0xff).with_notation(ColorNotation::Rgb)
+    }
+
+    /// `0xAARRGGBB`, written the same way
+    pub const fn legacy(argb: u32) -> Self {
+        Self::rgba(argb.rotate_left(8)).with_notation(ColorNotation::Legacy)
+    }
+
+    /// The same color, written in `notation`
+    pub const fn with_notation(self, notation: ColorNotation) -> Self {
+        Self { notation, ..self }
+    }
+
+    pub fn r(&self) -> u8 {
+        self.r
+    }
+
+    pub fn g(&self) -> u8 {
+        self.g
+    }
+
+    pub fn b(&self) -> u8 {
+        self.b
+    }
+
+    pub fn a(&self) -> u8 {
+        self.a
+    }
+
+    pub fn notation(&self) -> ColorNotation {
+        self.notation
+    }
+
+    /// Color as `0xRRGGBBAA`
+    pub fn to_rgba(&self) -> u32 {
+        u32::from_be_bytes([self.r, self.g, self.b, self.a])
     }
 
-    pub fn rgb(input: &str) -> Result<Color, ParseIntError> {
-        u64::from_str_radix(input, 16).map(Color::RGB)
+    /// Color as `0xAARRGGBB`, the way Hyprland stores it
+    pub fn argb(&self) -> u32 {
+        self.to_rgba().rotate_right(8)
     }
 
-    pub fn legacy(input: &str) -> Result<Color, ParseIntError> {
-        u64::from_str_radix(input, 16).map(Color::Legacy)
+    /// Whether both colors have the same channels, regardless of their notation
+    pub fn same_color(&self, other: &Color) -> bool {
+        self.to_rgba() == other.to_rgba()
     }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec2(pub Float, pub Float);
 
 impl Display for Vec2 {
@@ -234,11 +359,15 @@ impl Display for Vec2 {
 
 impl Parse for Vec2 {
     fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_pair(float, tag(" "), float), |(x, y)| Vec2(x, y))(input)
+        map(
+            separated_pair(Float::parse, tag(" "), Float::parse),
+            |(x, y)| Vec2(x, y),
+        )(input)
     }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Modifier {
     SHIFT,
     CAPS,
@@ -288,7 +417,163 @@ impl Parse for Modifier {
     }
 }
 
+impl Modifier {
+    /// Bit of the modifier in a Hyprland modmask
+    pub fn mask(&self) -> u32 {
+        match self {
+            Modifier::SHIFT => 1 << 0,
+            Modifier::CAPS => 1 << 1,
+            Modifier::CTRL(_) => 1 << 2,
+            Modifier::ALT => 1 << 3,
+            Modifier::MOD2 => 1 << 4,
+            Modifier::MOD3 => 1 << 5,
+            Modifier::SUPER(_) => 1 << 6,
+            Modifier::MOD5 => 1 << 7,
+        }
+    }
+
+    /// The modifier with aliases replaced, `CONTROL` by `CTRL` and `WIN`, `LOGO`, `MOD4` by `SUPER`
+    pub fn normalized(&self) -> Self {
+        match self {
+            Modifier::CTRL(_) => Modifier::CTRL(ControlModifier::CTRL),
+            Modifier::SUPER(_) => Modifier::SUPER(SuperModifier::SUPER),
+            modifier => *modifier,
+        }
+    }
+}
+
+/// Part of a [ModifierSet]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierToken {
+    Modifier(Modifier),
+    /// `$mainMod`, the name is stored without the `$`
+    Variable(String),
+}
+
+impl Display for ModifierToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModifierToken::Modifier(modifier) => write!(f, "{modifier}"),
+            ModifierToken::Variable(name) => write!(f, "${name}"),
+        }
+    }
+}
+
+/// `$mainMod SHIFT`, `SUPER_SHIFT`, `CTRLALT` or nothing. The tokens are kept as written but
+/// not their separators, the set is displayed space separated (`SUPER SHIFT`). See
+/// [ModifierSet::same_as] to compare the combinations
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifierSet(pub Vec<ModifierToken>);
+
+impl Display for ModifierSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.iter().join(" "))
+    }
+}
+
+impl Parse for ModifierSet {
+    /// At least one token, see [ModifierSet::from_value] for the empty set
+    fn parse(input: &str) -> IResult<&str, Self> {
+        fn token(input: &str) -> IResult<&str, ModifierToken> {
+            alt((
+                map(Variable::parse_name, ModifierToken::Variable),
+                map(Modifier::parse, ModifierToken::Modifier),
+            ))(input)
+        }
+
+        let separator = opt(alt((tag("_"), space1)));
+
+        map(
+            tuple((token, many0(preceded(separator, token)))),
+            |(first, rest)| Self(std::iter::once(first).chain(rest).collect()),
+        )(input)
+    }
+}
+
+impl ModifierSet {
+    /// Modifiers of a Hyprland modmask, as reported by `hyprctl binds`
+    pub fn from_mask(mask: u32) -> Self {
+        let modifiers = [
+            Modifier::SUPER(SuperModifier::SUPER),
+            Modifier::SHIFT,
+            Modifier::CTRL(ControlModifier::CTRL),
+            Modifier::ALT,
+            Modifier::CAPS,
+            Modifier::MOD2,
+            Modifier::MOD3,
+            Modifier::MOD5,
+        ];
+
+        Self(
+            modifiers
+                .into_iter()
+                .filter(|modifier| mask & modifier.mask() != 0)
+                .map(ModifierToken::Modifier)
+                .collect(),
+        )
+    }
+
+    pub fn from_value(value: &str) -> eyre::Result<Self> {
+        match value.trim() {
+            "" => Ok(Self::default()),
+            value => match all_consuming(Self::parse)(value) {
+                Ok((_, set)) => Ok(set),
+                Err(_) => Err(eyre::eyre!("Invalid modifiers `{value}`")),
+            },
+        }
+    }
+
+    pub fn modifiers(&self) -> impl Iterator<Item = Modifier> + '_ {
+        self.0.iter().filter_map(|token| match token {
+            ModifierToken::Modifier(modifier) => Some(*modifier),
+            ModifierToken::Variable(_) => None,
+        })
+    }
+
+    /// Names of the variables, without the `$`
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|token| match token {
+            ModifierToken::Modifier(_) => None,
+            ModifierToken::Variable(name) => Some(name.as_str()),
+        })
+    }
+
+    /// Hyprland modmask of the set, `None` while it still contains variables
+    pub fn mask(&self) -> Option<u32> {
+        match self.variables().next() {
+            Some(_) => None,
+            None => Some(
+                self.modifiers()
+                    .fold(0, |mask, modifier| mask | modifier.mask()),
+            ),
+        }
+    }
+
+    /// The set with aliases replaced and duplicates removed, sorted variables first and then
+    /// modifiers in the order of [ModifierSet::from_mask]
+    pub fn normalized(&self) -> Self {
+        let mask = self
+            .modifiers()
+            .fold(0, |mask, modifier| mask | modifier.mask());
+        let variables = self
+            .variables()
+            .sorted()
+            .dedup()
+            .map(|name| ModifierToken::Variable(name.to_string()));
+
+        Self(variables.chain(Self::from_mask(mask).0).collect())
+    }
+
+    /// Whether both sets are the same combination, `SUPER_SHIFT` and `SHIFT WIN` are
+    pub fn same_as(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ControlModifier {
     CTRL,
     CONTROL,
@@ -317,6 +602,7 @@ impl Parse for ControlModifier {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SuperModifier {
     SUPER,
     WIN,
@@ -351,6 +637,7 @@ impl Parse for SuperModifier {
 }
 
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gradient {
     /// color1 color2 color3 ... (See [Color])
     pub colors: Vec<Color>,
@@ -0,0 +1,117 @@
+use hyprland_config::{
+    animation::{effective_animation, Animation, AnimationName, AnimationStyle, Bezier},
+    diagnostic::DiagnosticCode,
+    Config, DEFAULT_CONFIG_STR,
+};
+
+#[test]
+fn test_animation_parse() {
+    let config: Config = DEFAULT_CONFIG_STR.parse().unwrap();
+
+    let beziers = config.beziers().unwrap();
+    assert_eq!(
+        beziers,
+        vec![Bezier {
+            name: "myBezier".to_string(),
+            x1: 0.05,
+            y1: 0.9,
+            x2: 0.1,
+            y2: 1.05,
+        }]
+    );
+    assert_eq!(
+        beziers[0].to_string(),
+        "bezier = myBezier, 0.05, 0.9, 0.1, 1.05"
+    );
+
+    let animations = config.animations().unwrap();
+    assert_eq!(
+        animations[1],
+        Animation {
+            name: AnimationName::WindowsOut,
+            enabled: true,
+            speed: Some(7.0),
+            curve: Some("default".to_string()),
+            style: Some(AnimationStyle::Popin(Some(80.0))),
+        }
+    );
+    assert_eq!(
+        animations[1].to_string(),
+        "animation = windowsOut, 1, 7, default, popin 80%"
+    );
+
+    let windows_in = effective_animation(&animations, AnimationName::WindowsIn).unwrap();
+    assert_eq!(windows_in.name, AnimationName::Windows);
+    assert_eq!(windows_in.duration_ms(), Some(700.0));
+    assert!(effective_animation(&animations, AnimationName::SpecialWorkspace).is_some());
+
+    let off: Config = "animation = fadeDim, 0".parse().unwrap();
+    assert_eq!(off.animations().unwrap()[0].duration_ms(), None);
+}
+
+#[test]
+fn test_animation_invalid() {
+    for value in [
+        "windows, 1, 7, default, loop",
+        "borderangle, 1, 8, default, popin 80%",
+        "workspaces, 1, 6, default, slidefade 120%",
+        "wobble, 1, 7, default",
+        "windows, 1, -7, default",
+        "windows, 1",
+    ] {
+        let config: Config = format!("animation = {value}").parse().unwrap();
+        assert!(config.animations().is_err(), "{value}");
+    }
+
+    let config: Config = "bezier = bad, 1.5, 0, 0, 1".parse().unwrap();
+    assert!(config.beziers().is_err());
+
+    let config: Config = r#"animation = windows, 1, 7, myBezier
+bezier = myBezier, 0.05, 0.9, 0.1, 1.05
+animation = fade, 1, 7, myBezier
+animation = border, 1, 10, mybezier
+"#
+    .parse()
+    .unwrap();
+    let undefined = config
+        .validate()
+        .into_iter()
+        .filter(|diagnostic| diagnostic.code == DiagnosticCode::UndefinedBezier)
+        .map(|diagnostic| (diagnostic.span.start.line, diagnostic.help))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        undefined,
+        vec![
+            (
+                1,
+                Some("Define it with a `bezier` line before this one".to_string())
+            ),
+            (4, Some("Did you mean `myBezier`?".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn test_bezier_eval() {
+    let linear = Bezier {
+        name: "linear".to_string(),
+        x1: 0.0,
+        y1: 0.0,
+        x2: 1.0,
+        y2: 1.0,
+    };
+    let overshot = Bezier {
+        name: "overshot".to_string(),
+        x1: 0.05,
+        y1: 0.9,
+        x2: 0.1,
+        y2: 1.05,
+    };
+
+    for bezier in [&linear, &overshot, &Bezier::default_curve()] {
+        assert!(bezier.eval(0.0).abs() < 1e-4);
+        assert!((bezier.eval(1.0) - 1.0).abs() < 1e-4);
+    }
+    assert!((linear.eval(0.3) - 0.3).abs() < 1e-3);
+    assert!((0..100).any(|step| overshot.eval(step as f32 / 100.0) > 1.0));
+}
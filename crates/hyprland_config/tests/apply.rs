@@ -0,0 +1,101 @@
+mod common;
+
+use common::{float, int, live, string};
+use hyprland_config::{apply::OptionChange, Config};
+
+#[test]
+fn test_apply_changes() {
+    let config: Config = r#"$gaps = 10
+general {
+    gaps_in = 5
+    gaps_in = $gaps
+    border_size = 1
+    layout = master
+}
+decoration {
+    active_opacity = 0.9
+    col.shadow = rgba(444444ff)
+}
+input {
+    touchpad {
+        natural_scroll = no
+    }
+}
+device:epic-mouse-v1 {
+    sensitivity = -0.5
+}
+"#
+    .parse()
+    .unwrap();
+
+    let plan = config.apply_plan(live([
+        ("general:gaps_in", int(5)),
+        ("general:border_size", int(1)),
+        ("input:touchpad:natural_scroll", int(1)),
+        ("decoration:active_opacity", float(0.9)),
+        ("decoration:col.shadow", int(0xff444444)),
+        ("general:layout", string("dwindle")),
+    ]));
+
+    assert_eq!(
+        plan.changes,
+        [
+            OptionChange {
+                path: "general:gaps_in".to_string(),
+                value: "10".to_string(),
+                current: Some("5".to_string()),
+            },
+            OptionChange {
+                path: "general:layout".to_string(),
+                value: "master".to_string(),
+                current: Some("dwindle".to_string()),
+            },
+            OptionChange {
+                path: "input:touchpad:natural_scroll".to_string(),
+                value: "no".to_string(),
+                current: Some("true".to_string()),
+            },
+            OptionChange {
+                path: "device:epic-mouse-v1:sensitivity".to_string(),
+                value: "-0.5".to_string(),
+                current: None,
+            },
+        ]
+    );
+    assert_eq!(
+        plan.batch(),
+        "keyword general:gaps_in 10;keyword general:layout master;\
+         keyword input:touchpad:natural_scroll no;keyword device:epic-mouse-v1:sensitivity -0.5"
+    );
+    assert!(plan.skipped.is_empty());
+}
+
+#[test]
+fn test_apply_skipped() {
+    let config: Config = r#"exec-once = waybar
+bind = SUPER, Q, exec, kitty
+bind = SUPER, C, killactive,
+general {
+    gaps_out = 20
+}
+"#
+    .parse()
+    .unwrap();
+
+    let plan = config.apply_plan(live([("general:gaps_in", int(5))]));
+    let skipped = plan
+        .skipped
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    assert!(plan.changes.is_empty());
+    assert_eq!(
+        skipped,
+        [
+            "general:gaps_out: not an option of the running Hyprland",
+            "bind (2 lines): only applied by `hyprr reload`",
+            "exec-once: only read when Hyprland starts",
+        ]
+    );
+}
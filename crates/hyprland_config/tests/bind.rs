@@ -0,0 +1,111 @@
+use hyprland_config::{
+    bind::{Bind, BindFlags, Key},
+    util::Parse,
+    variable::{ControlModifier, Modifier, ModifierSet, ModifierToken, SuperModifier},
+    Config, DEFAULT_CONFIG_STR,
+};
+
+#[test]
+fn test_bind_parser() {
+    let input = "bind = $mainMod SHIFT, 1, movetoworkspace, 1";
+    let (rest, bind) = Bind::parse(input).unwrap();
+
+    assert_eq!(rest, "");
+    assert_eq!(
+        bind,
+        Bind {
+            flags: BindFlags::default(),
+            mods: ModifierSet(vec![
+                ModifierToken::Variable("mainMod".to_string()),
+                ModifierToken::Modifier(Modifier::SHIFT),
+            ]),
+            key: Key::Keysym("1".to_string()),
+            dispatcher: "movetoworkspace".to_string(),
+            arg: Some("1".to_string()),
+        }
+    );
+    assert_eq!(bind.to_string(), input);
+
+    let input = "bindm = SUPER_CTRL, mouse:272, movewindow";
+    let (_, bind) = Bind::parse(input).unwrap();
+
+    assert_eq!(
+        bind.flags,
+        BindFlags {
+            mouse: true,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        bind.mods,
+        ModifierSet(vec![
+            ModifierToken::Modifier(Modifier::SUPER(SuperModifier::SUPER)),
+            ModifierToken::Modifier(Modifier::CTRL(ControlModifier::CTRL)),
+        ])
+    );
+    assert_eq!(bind.key, Key::Mouse(272));
+    assert_eq!(bind.arg, None);
+    assert_eq!(
+        bind.to_string(),
+        "bindm = SUPER CTRL, mouse:272, movewindow"
+    );
+
+    let (_, bind) = Bind::parse(
+        "bindle=,XF86AudioRaiseVolume,exec,wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+, -l 1.0",
+    )
+    .unwrap();
+    assert!(bind.flags.locked && bind.flags.repeat);
+    assert_eq!(bind.mods, ModifierSet::default());
+    assert_eq!(
+        bind.arg.as_deref(),
+        Some("wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+, -l 1.0")
+    );
+
+    assert!(Bind::parse("bind = SUPER, Q").is_err());
+    assert!(Bind::parse("bind = HYPER, Q, exec, kitty").is_err());
+    assert!(Bind::parse("bindll = SUPER, Q, exec, kitty").is_err());
+}
+
+#[test]
+fn test_key_parser() {
+    assert_eq!(Key::parse("code:28"), Ok(("", Key::Code(28))));
+    assert_eq!(Key::parse("mouse:273"), Ok(("", Key::Mouse(273))));
+    assert_eq!(Key::parse("mouse_down"), Ok(("", Key::MouseDown)));
+    assert_eq!(Key::parse("mouse_up"), Ok(("", Key::MouseUp)));
+    assert_eq!(
+        Key::parse("Return"),
+        Ok(("", Key::Keysym("Return".to_string())))
+    );
+}
+
+#[test]
+fn test_submaps() {
+    let input = r"
+bind = ALT, R, submap, resize
+submap = resize
+binde = , right, resizeactive, 10 0
+bind = , escape, submap, reset
+submap = reset
+unbind = SUPER, Q
+";
+    let config: Config = input.parse().unwrap();
+    let submaps = config.submaps().unwrap();
+
+    assert_eq!(submaps.len(), 2);
+    assert_eq!(submaps[0].name, None);
+    assert_eq!(submaps[0].binds.len(), 1);
+    assert_eq!(submaps[0].unbinds.len(), 1);
+    assert_eq!(submaps[1].name.as_deref(), Some("resize"));
+    assert_eq!(submaps[1].binds.len(), 2);
+    assert!(submaps[1].binds[0].flags.repeat);
+
+    let config: Config = DEFAULT_CONFIG_STR.parse().unwrap();
+    let submaps = config.submaps().unwrap();
+
+    assert_eq!(submaps.len(), 1);
+    assert_eq!(submaps[0].binds.len(), 36);
+    assert_eq!(
+        submaps[0].binds[1].to_keyword().to_string(),
+        "bind = $mainMod, C, killactive,"
+    );
+}
@@ -1,6 +1,7 @@
 use hyprland_config::{
     comment::{Comment, MultiLineComment, OneLineComment},
     util::Parse,
+    Config,
 };
 
 #[test]
@@ -26,3 +27,27 @@ OR EDIT THIS ONE ACCORDING TO THE WIKI INSTRUCTIONS.
         Ok(("", Comment::MultiLine(MultiLineComment(input.to_string()))))
     );
 }
+
+#[test]
+fn test_comment_fences() {
+    let input = "## first\ngaps_in = 5\n## second\n";
+    let config: Config = input.parse().unwrap();
+
+    assert_eq!(config.to_string(), input);
+    assert_eq!(config.value("gaps_in"), Some("5"));
+
+    let input = "##\nnote ##\n#### not closed ##\n";
+    assert_eq!(
+        Comment::parse(input),
+        Ok((
+            "\n#### not closed ##\n",
+            Comment::MultiLine(MultiLineComment("##\nnote ##".to_string()))
+        ))
+    );
+
+    let input = "# comment\r\r\n";
+    assert_eq!(
+        OneLineComment::parse(input),
+        Ok(("\r\n", OneLineComment("# comment\r".to_string())))
+    );
+}
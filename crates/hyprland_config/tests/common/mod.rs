@@ -0,0 +1,38 @@
+//! Fixtures shared by the integration tests
+
+// Every test crate compiles this module, not all of them use every fixture
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use hyprland_config::apply::LiveOption;
+
+/// Live values looked up by option path, like `hyprctl -j getoption` replies
+pub fn live<const N: usize>(
+    options: [(&str, LiveOption); N],
+) -> impl Fn(&str) -> Option<LiveOption> + '_ {
+    let options = HashMap::from(options);
+
+    move |path| options.get(path).cloned()
+}
+
+pub fn int(int: i64) -> LiveOption {
+    LiveOption {
+        int,
+        ..Default::default()
+    }
+}
+
+pub fn float(float: f64) -> LiveOption {
+    LiveOption {
+        float,
+        ..Default::default()
+    }
+}
+
+pub fn string(str: &str) -> LiveOption {
+    LiveOption {
+        str: str.to_string(),
+        ..Default::default()
+    }
+}
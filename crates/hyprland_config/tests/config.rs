@@ -0,0 +1,160 @@
+use hyprland_config::{
+    comment::{Comment, OneLineComment},
+    cst::{Node, Span, Trivia},
+    keyword::Keyword,
+    variable::Bool,
+    Config, ConfigElement, DEFAULT_CONFIG_STR,
+};
+
+#[test]
+fn test_default_config_round_trip() {
+    let config: Config = DEFAULT_CONFIG_STR.parse().unwrap();
+
+    assert_eq!(config.to_string(), DEFAULT_CONFIG_STR);
+}
+
+#[test]
+fn test_config_parser() {
+    let input =
+        "autogenerated = 1 # remove this line\n\n\n$mainMod = SUPER\nmonitor=,preferred,auto,auto";
+    let config: Config = input.parse().unwrap();
+
+    assert_eq!(
+        config.nodes(),
+        [
+            Node {
+                span: Span::new(0, 37),
+                trivia: Trivia {
+                    indent: String::new(),
+                    trailing: " ".to_string(),
+                    comment: Some(OneLineComment("# remove this line".to_string())),
+                    line_ending: "\n".to_string(),
+                },
+                element: ConfigElement::AutoGenerated(Bool::One),
+            },
+            Node {
+                span: Span::new(37, 39),
+                trivia: Trivia::new(""),
+                element: ConfigElement::EmptyLines(2),
+            },
+            Node {
+                span: Span::new(39, 56),
+                trivia: Trivia::new(""),
+                element: ConfigElement::Variable(Keyword::new("mainMod", "SUPER")),
+            },
+            Node {
+                span: Span::new(56, 84),
+                trivia: Trivia {
+                    line_ending: String::new(),
+                    ..Trivia::new("")
+                },
+                element: ConfigElement::Keyword(Keyword {
+                    name: "monitor".to_string(),
+                    separator: "=".to_string(),
+                    value: ",preferred,auto,auto".to_string(),
+                }),
+            },
+        ]
+    );
+    assert_eq!(config.to_string(), input);
+}
+
+#[test]
+fn test_section_parser() {
+    let input = "input { # keyboard\n    kb_variant =\n    touchpad {\n        natural_scroll = no\n    }\n}\n\ndevice:epic mouse V1 {\n    sensitivity = -0.5\n}\n";
+    let config: Config = input.parse().unwrap();
+
+    let sections = config
+        .nodes()
+        .iter()
+        .filter_map(|node| match &node.element {
+            ConfigElement::Section(section) => Some((node, section)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(sections.len(), 2);
+
+    let (input_node, input_section) = sections[0];
+    assert_eq!(input_section.name, "input");
+    assert_eq!(
+        input_section.opening.comment,
+        Some(OneLineComment("# keyboard".to_string()))
+    );
+    assert_eq!(
+        input_node.span.slice(input),
+        &input[..input.find("\n}\n").unwrap() + 3]
+    );
+
+    let touchpad = input_section
+        .elements
+        .iter()
+        .find(|node| matches!(&node.element, ConfigElement::Section(touchpad) if touchpad.name == "touchpad"))
+        .unwrap();
+    assert_eq!(touchpad.trivia.indent, "    ");
+    assert_eq!(
+        touchpad.span.slice(input),
+        "    touchpad {\n        natural_scroll = no\n    }\n"
+    );
+
+    assert_eq!(sections[1].1.name, "device:epic mouse V1");
+    assert_eq!(config.to_string(), input);
+}
+
+#[test]
+fn test_edit_rewrites_only_one_line() {
+    let mut config: Config = DEFAULT_CONFIG_STR.parse().unwrap();
+
+    let input = config
+        .nodes_mut()
+        .iter_mut()
+        .find_map(|node| match &mut node.element {
+            ConfigElement::Section(section) if section.name == "input" => Some(section),
+            _ => None,
+        })
+        .unwrap();
+    let sensitivity = input
+        .elements
+        .iter_mut()
+        .find_map(|node| match &mut node.element {
+            ConfigElement::Keyword(keyword) if keyword.name == "sensitivity" => Some(keyword),
+            _ => None,
+        })
+        .unwrap();
+    sensitivity.value = "0.5".to_string();
+
+    let edited = config.to_string();
+    let changed = DEFAULT_CONFIG_STR
+        .lines()
+        .zip(edited.lines())
+        .filter(|(before, after)| before != after)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        changed,
+        [(
+            "    sensitivity = 0 # -1.0 - 1.0, 0 means no modification.",
+            "    sensitivity = 0.5 # -1.0 - 1.0, 0 means no modification."
+        )]
+    );
+}
+
+#[test]
+fn test_comment_nodes() {
+    let input = "    # indented\r\n";
+    let config: Config = input.parse().unwrap();
+
+    assert_eq!(
+        config.nodes()[0].element,
+        ConfigElement::Comment(Comment::OneLine(OneLineComment("# indented".to_string())))
+    );
+    assert_eq!(config.nodes()[0].trivia.line_ending, "\r\n");
+    assert_eq!(config.to_string(), input);
+}
+
+#[test]
+fn test_config_parse_error() {
+    let input = "general {\n    gaps_in = 5\n";
+
+    assert!(input.parse::<Config>().is_err());
+}
@@ -0,0 +1,121 @@
+use hyprland_config::{
+    diff::{DiffCategory, DiffKind},
+    format::FormatOptions,
+    Config, DEFAULT_CONFIG_STR,
+};
+
+#[test]
+fn test_diff_ignores_formatting() {
+    let config: Config = DEFAULT_CONFIG_STR.parse().unwrap();
+    let formatted = config.format(&FormatOptions {
+        align_binds: true,
+        ..Default::default()
+    });
+
+    assert!(config.diff(&formatted).unwrap().is_empty());
+
+    let old: Config = "general {\n    gaps_in = 5 # inner\n    no_border_on_floating = yes\n}\n"
+        .parse()
+        .unwrap();
+    let new: Config = "general:no_border_on_floating=true\ngeneral {\n  gaps_in=5.0\n}\n"
+        .parse()
+        .unwrap();
+
+    assert!(old.diff(&new).unwrap().is_empty());
+
+    let old: Config = "bind = SUPER_SHIFT, E, exit,\nbind = CONTROL ALT, T, exec, kitty\n"
+        .parse()
+        .unwrap();
+    let new: Config = "bind = SHIFT WIN, E, exit,\nbind = ALT CTRL, T, exec, kitty\n"
+        .parse()
+        .unwrap();
+    assert!(old.diff(&new).unwrap().is_empty());
+}
+
+#[test]
+fn test_diff_entries() {
+    let old: Config = r#"$mainMod = SUPER
+$term = kitty
+monitor = DP-1, 1920x1080, 0x0, 1
+general {
+    gaps_in = 5
+    border_size = 2
+}
+exec-once = waybar
+exec-once = mako
+bind = $mainMod, Q, exec, $term
+bind = $mainMod, C, killactive,
+submap = resize
+binde = , right, resizeactive, 10 0
+submap = reset
+windowrule = float, pavucontrol
+"#
+    .parse()
+    .unwrap();
+    let new: Config = r#"$mainMod = SUPER
+$term = foot
+monitor = DP-1, 2560x1440, 0x0, 1
+general {
+    gaps_in = 10
+}
+input {
+    kb_layout = de
+}
+exec-once = waybar
+exec-once = dunst
+bind = SUPER, Q, exec, foot
+bind = SUPER, C, killactive,
+bind = SUPER SHIFT, E, exit,
+windowrulev2 = float, class:pavucontrol
+windowrulev2 = center, class:pavucontrol
+"#
+    .parse()
+    .unwrap();
+
+    let diff = old.diff(&new).unwrap();
+    let entries = diff
+        .entries
+        .iter()
+        .map(|entry| (entry.category, entry.key.as_str(), entry.kind()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        entries,
+        [
+            (DiffCategory::Variable, "$term", DiffKind::Changed),
+            (
+                DiffCategory::Option,
+                "general:border_size",
+                DiffKind::Removed
+            ),
+            (DiffCategory::Option, "general:gaps_in", DiffKind::Changed),
+            (DiffCategory::Option, "input:kb_layout", DiffKind::Added),
+            (DiffCategory::Bind, "SUPER SHIFT, E", DiffKind::Added),
+            (DiffCategory::Bind, "SUPER, Q", DiffKind::Changed),
+            (DiffCategory::Bind, "[resize] right", DiffKind::Removed),
+            (DiffCategory::MonitorRule, "DP-1", DiffKind::Changed),
+            (
+                DiffCategory::WindowRule,
+                "class:pavucontrol",
+                DiffKind::Changed
+            ),
+            (DiffCategory::Keyword, "exec-once", DiffKind::Added),
+            (DiffCategory::Keyword, "exec-once", DiffKind::Removed),
+        ]
+    );
+
+    assert_eq!(
+        diff.to_string().lines().collect::<Vec<_>>()[..3],
+        [
+            "~ variable $term: kitty -> foot",
+            "- option general:border_size: 2",
+            "~ option general:gaps_in: 5 -> 10",
+        ]
+    );
+    assert_eq!(
+        diff.entries[8].to_string(),
+        "~ window rule class:pavucontrol: float -> center; float"
+    );
+    assert_eq!(diff.entries[9].new.as_deref(), Some("dunst"));
+    assert_eq!(diff.entries[10].old.as_deref(), Some("mako"));
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "serde")]
+
+use hyprland_config::{
+    document::{Document, Entry, Value},
+    Config, DEFAULT_CONFIG_STR,
+};
+
+#[test]
+fn test_to_document() {
+    let config = Config::parse_recovering(
+        "# comment\n$mainMod = SUPER\ngeneral {\n    gaps_in = 5 # inner\n    col.active_border = rgba(33ccffee)\n}\ndecoration {\n    rounding = 10\n    active_opacity = 0.9\n    blur {\n        enabled = yes\n    }\n}\nbind = $mainMod, Q, exec, kitty\n",
+    );
+
+    assert_eq!(
+        config.to_document().config,
+        vec![
+            Entry::new("$mainMod", Value::String("SUPER".to_string())),
+            Entry::new(
+                "general",
+                Value::Section(vec![
+                    Entry::new("gaps_in", Value::Int(5)),
+                    Entry::new(
+                        "col.active_border",
+                        Value::String("rgba(33ccffee)".to_string())
+                    ),
+                ])
+            ),
+            Entry::new(
+                "decoration",
+                Value::Section(vec![
+                    Entry::new("rounding", Value::Int(10)),
+                    Entry::new("active_opacity", Value::Float(0.9)),
+                    Entry::new(
+                        "blur",
+                        Value::Section(vec![Entry::new("enabled", Value::Bool(true))])
+                    ),
+                ])
+            ),
+            Entry::new(
+                "bind",
+                Value::String("$mainMod, Q, exec, kitty".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_document_json() {
+    let config = Config::parse_recovering(DEFAULT_CONFIG_STR);
+    let document = config.to_document();
+
+    let json = serde_json::to_string(&document).unwrap();
+    assert!(json.contains(r#"{"gaps_in":5}"#));
+    assert!(json.contains(r#"{"$mainMod":"SUPER"}"#));
+
+    let parsed: Document = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, document);
+
+    // Regenerating the config keeps every option, only comments and spacing are lost
+    let regenerated = parsed.to_config();
+    assert_eq!(regenerated.options().len(), config.options().len());
+    assert_eq!(regenerated.to_document(), document);
+    assert_eq!(
+        regenerated.get("general:gaps_out"),
+        config.get("general:gaps_out")
+    );
+
+    assert!(serde_json::from_str::<Document>(r#"{"config":[{"a":"1","b":"2"}]}"#).is_err());
+}
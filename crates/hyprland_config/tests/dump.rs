@@ -0,0 +1,107 @@
+mod common;
+
+use common::{int, live, string};
+use hyprland_config::{
+    bind::Submap,
+    dump::{DumpOptions, LiveState},
+    monitor::{MonitorRule, MonitorState, Transform},
+    variable::ModifierSet,
+    Config,
+};
+
+#[test]
+fn test_dump_options() {
+    let live = live([
+        ("general:gaps_in", int(10)),
+        ("general:border_size", int(2)),
+        ("input:touchpad:natural_scroll", int(1)),
+        ("input:kb_layout", string("us")),
+        ("decoration:col.shadow", int(0xee1a1a1a)),
+        // Deprecated, left out of the dump
+        ("general:main_mod", string("SUPER")),
+    ]);
+    let config = Config::dump(&LiveState::default(), &live, &DumpOptions::default());
+
+    assert_eq!(
+        config.to_string(),
+        r#"general {
+    border_size = 2
+    gaps_in = 10
+}
+
+decoration {
+    col.shadow = 0xee1a1a1a
+}
+
+input {
+    kb_layout = us
+
+    touchpad {
+        natural_scroll = true
+    }
+}
+"#
+    );
+
+    let non_default = Config::dump(
+        &LiveState::default(),
+        live,
+        &DumpOptions {
+            non_default_only: true,
+        },
+    );
+
+    assert_eq!(
+        non_default.to_string(),
+        "general {\n    border_size = 2\n    gaps_in = 10\n}\n\n\
+         input {\n    touchpad {\n        natural_scroll = true\n    }\n}\n"
+    );
+}
+
+#[test]
+fn test_dump_monitors_and_binds() {
+    let monitor = MonitorState {
+        name: "DP-1".to_string(),
+        width: 2560,
+        height: 1440,
+        refresh_rate: 144.0,
+        x: 1920,
+        scale: 1.0,
+        transform: Transform::Normal90,
+        ..Default::default()
+    };
+
+    let binds: Config =
+        "bind = SUPER SHIFT, Q, exec, kitty\nbindm = SUPER, mouse:272, movewindow\n"
+            .parse()
+            .unwrap();
+    let resize: Config = "bind = , escape, submap, reset\n".parse().unwrap();
+
+    let state = LiveState {
+        monitors: vec![MonitorRule::from(&monitor)],
+        submaps: vec![
+            binds.submaps().unwrap().remove(0),
+            Submap {
+                name: Some("resize".to_string()),
+                ..resize.submaps().unwrap().remove(0)
+            },
+        ],
+    };
+
+    let config = Config::dump(&state, |_| None, &DumpOptions::default());
+
+    assert_eq!(
+        config.to_string(),
+        r#"monitor = DP-1,2560x1440@144,1920x0,1,transform,1
+
+bind = SUPER SHIFT, Q, exec, kitty
+bindm = SUPER, mouse:272, movewindow
+
+submap = resize
+bind = , escape, submap, reset
+submap = reset
+"#
+    );
+    assert!(config.to_string().parse::<Config>().is_ok());
+    assert_eq!(ModifierSet::from_mask(65).to_string(), "SUPER SHIFT");
+}
@@ -0,0 +1,123 @@
+use hyprland_config::{
+    variable::{Bool, VariableValue},
+    Config,
+};
+
+#[test]
+fn test_get() {
+    let config: Config = r#"$radius = 10
+general {
+    gaps_in = 5
+    layout = dwindle
+}
+general:gaps_in = 8
+decoration {
+    rounding = $radius
+}
+"#
+    .parse()
+    .unwrap();
+
+    assert_eq!(config.get("general:gaps_in"), Some(VariableValue::Int(8)));
+    assert_eq!(
+        config.get("general:layout"),
+        Some(VariableValue::String("dwindle".to_string()))
+    );
+    assert_eq!(
+        config.get("decoration:rounding"),
+        Some(VariableValue::Variable("radius".to_string()))
+    );
+    assert_eq!(config.get("general:border_size"), None);
+}
+
+#[test]
+fn test_set() {
+    let mut config: Config = r#"general {
+  gaps_in = 5 # inner gaps
+
+  layout=dwindle
+}
+"#
+    .parse()
+    .unwrap();
+
+    config.set("general:gaps_in", VariableValue::Int(10));
+    config.set(
+        "general:layout",
+        VariableValue::String("master".to_string()),
+    );
+    config.set("general:border_size", VariableValue::Int(2));
+    config.set(
+        "input:touchpad:natural_scroll",
+        VariableValue::Bool(Bool::Yes),
+    );
+    config.set(
+        "device:epic-mouse-v1:sensitivity",
+        VariableValue::Float(-0.5),
+    );
+    config.set(
+        "decoration:rounding",
+        VariableValue::Variable("radius".to_string()),
+    );
+
+    assert_eq!(
+        config.to_string(),
+        r#"general {
+  gaps_in = 10 # inner gaps
+
+  layout=master
+  border_size = 2
+}
+
+input {
+    touchpad {
+        natural_scroll = yes
+    }
+}
+
+device:epic-mouse-v1 {
+    sensitivity = -0.5
+}
+
+decoration {
+    rounding = $radius
+}
+"#
+    );
+    assert_eq!(
+        config.get("device:epic-mouse-v1:sensitivity"),
+        Some(VariableValue::Float(-0.5))
+    );
+
+    let mut config: Config = "exec-once = waybar".parse().unwrap();
+    config.set("general:gaps_in", VariableValue::Int(5));
+    assert_eq!(
+        config.to_string(),
+        "exec-once = waybar\n\ngeneral {\n    gaps_in = 5\n}\n"
+    );
+}
+
+#[test]
+fn test_remove() {
+    let mut config: Config = r#"general {
+    gaps_in = 5
+    gaps_out = 20 # outer
+}
+input {
+    touchpad {
+        natural_scroll = yes
+    }
+}
+general:gaps_in = 8
+"#
+    .parse()
+    .unwrap();
+
+    assert!(config.remove("general:gaps_in"));
+    assert!(config.remove("input:touchpad:natural_scroll"));
+    assert!(!config.remove("general:border_size"));
+    assert_eq!(
+        config.to_string(),
+        "general {\n    gaps_out = 20 # outer\n}\n"
+    );
+}
@@ -0,0 +1,78 @@
+use hyprland_config::{format::FormatOptions, Config, DEFAULT_CONFIG_STR};
+
+#[test]
+fn test_format() {
+    let input = "\n\n$mainMod=SUPER   \ngeneral{ # layout\n\n  gaps_in=5\n\tcol.active_border =rgb(33ccff)   # blue\n\n\n\n  border_size   = 2\n    input:touchpad {\nnatural_scroll=yes\n\n}\n\n  }\r\n\n\n\n## multi\nline ##\nexec-once=waybar\nkb_variant =";
+    let config: Config = input.parse().unwrap();
+
+    let formatted = config.format(&FormatOptions::default()).to_string();
+    assert_eq!(
+        formatted,
+        "$mainMod = SUPER
+general { # layout
+    gaps_in = 5
+    col.active_border = rgb(33ccff) # blue
+
+    border_size = 2
+    input:touchpad {
+        natural_scroll = yes
+    }
+}
+
+## multi
+line ##
+exec-once = waybar
+kb_variant =
+"
+    );
+
+    let options = FormatOptions {
+        indent: 2,
+        max_blank_lines: 0,
+        ..Default::default()
+    };
+    assert_eq!(
+        config.format(&options).to_string(),
+        "$mainMod = SUPER\ngeneral { # layout\n  gaps_in = 5\n  col.active_border = rgb(33ccff) # blue\n  border_size = 2\n  input:touchpad {\n    natural_scroll = yes\n  }\n}\n## multi\nline ##\nexec-once = waybar\nkb_variant =\n"
+    );
+}
+
+#[test]
+fn test_format_align_binds() {
+    let input = "bind = SUPER, Q, exec, kitty\nbindm=SUPER,mouse:272,movewindow\nbind = SUPER SHIFT, C, killactive,\n\nbind = SUPER, left, movefocus, l\n";
+    let config: Config = input.parse().unwrap();
+    let options = FormatOptions {
+        align_binds: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        config.format(&options).to_string(),
+        "bind  = SUPER,       Q,         exec,       kitty
+bindm = SUPER,       mouse:272, movewindow
+bind  = SUPER SHIFT, C,         killactive,
+
+bind = SUPER, left, movefocus, l
+"
+    );
+}
+
+#[test]
+fn test_format_idempotent() {
+    for options in [
+        FormatOptions::default(),
+        FormatOptions {
+            align_binds: true,
+            ..Default::default()
+        },
+    ] {
+        let once = DEFAULT_CONFIG_STR
+            .parse::<Config>()
+            .unwrap()
+            .format(&options)
+            .to_string();
+        let twice = once.parse::<Config>().unwrap().format(&options).to_string();
+
+        assert_eq!(once, twice);
+    }
+}
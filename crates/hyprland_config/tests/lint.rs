@@ -0,0 +1,180 @@
+use hyprland_config::{
+    diagnostic::{DiagnosticCode, Severity},
+    lint::{Lint, LintLevel, LintLevels},
+    Config,
+};
+
+const INPUT: &str = r#"$mainMod = SUPER
+$unused = kitty
+general {
+    gaps_in = 5
+    col.active_border = rgba(33ccffee) rgba(33ccffee) 45deg
+}
+bind = $mainMod, Q, exec, foot
+exec-once = waybar & mako
+exec-once = [workspace 2 silent] not-installed --flag
+general {
+    border_size = 3
+}
+bind = SUPER, Q, exec, foot
+"#;
+
+fn lints(config: &Config, levels: &LintLevels) -> Vec<(Severity, Lint, usize, String)> {
+    config
+        .lint(levels, |program| program != "not-installed")
+        .into_iter()
+        .map(|lint| {
+            let diagnostic = lint.diagnostic;
+            let DiagnosticCode::Lint(code) = diagnostic.code else {
+                panic!("not a lint: {diagnostic}");
+            };
+
+            (
+                diagnostic.severity,
+                code,
+                diagnostic.span.start.line,
+                diagnostic.message,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_lint() {
+    let config: Config = INPUT.parse().unwrap();
+    let mut levels = LintLevels::default();
+
+    assert_eq!(
+        lints(&config, &levels),
+        vec![
+            (
+                Severity::Warning,
+                Lint::UnusedVariable,
+                2,
+                "`$unused` is never used".to_string()
+            ),
+            (
+                Severity::Warning,
+                Lint::SingleColorGradient,
+                5,
+                "Gradient of `general:col.active_border` only has a single color".to_string()
+            ),
+            (
+                Severity::Warning,
+                Lint::ShadowedBind,
+                7,
+                "Bind `SUPER, Q, exec, foot` is repeated on line 13".to_string()
+            ),
+            (
+                Severity::Warning,
+                Lint::MissingExecutable,
+                9,
+                "`not-installed` isn't on PATH".to_string()
+            ),
+            (
+                Severity::Warning,
+                Lint::DuplicateSection,
+                10,
+                "Section `general` is already opened on line 3".to_string()
+            ),
+        ]
+    );
+
+    levels.set(Lint::UnusedVariable, LintLevel::Allow);
+    levels.set(Lint::DefaultValue, LintLevel::Warn);
+    levels.set(Lint::MissingExecutable, LintLevel::Deny);
+
+    let found = lints(&config, &levels);
+    assert!(found
+        .iter()
+        .all(|(_, lint, ..)| *lint != Lint::UnusedVariable));
+    assert!(found.contains(&(
+        Severity::Warning,
+        Lint::DefaultValue,
+        4,
+        "`general:gaps_in` is set to its default value `5`".to_string()
+    )));
+    assert!(found.contains(&(
+        Severity::Error,
+        Lint::MissingExecutable,
+        9,
+        "`not-installed` isn't on PATH".to_string()
+    )));
+}
+
+#[test]
+fn test_lint_fix() {
+    let mut config: Config = INPUT.parse().unwrap();
+    let levels = LintLevels::default();
+
+    // Removing the repeated bind leaves `$mainMod` unused, which the next round removes
+    loop {
+        let lints = config.lint(&levels, |program| program != "not-installed");
+        if config.apply_fixes(&lints) == 0 {
+            break;
+        }
+    }
+
+    assert_eq!(
+        config.to_string(),
+        r#"general {
+    gaps_in = 5
+    col.active_border = rgba(33ccffee)
+    border_size = 3
+}
+exec-once = waybar & mako
+exec-once = [workspace 2 silent] not-installed --flag
+bind = SUPER, Q, exec, foot
+"#
+    );
+
+    // Hyprland runs both binds, neither is removed
+    let both = "bind = SUPER, E, exec, kitty\nbind = SUPER, E, exec, firefox\n";
+    let mut config: Config = both.parse().unwrap();
+    let lints = config.lint(&levels, |_| true);
+
+    assert!(lints.is_empty());
+    assert_eq!(config.apply_fixes(&lints), 0);
+    assert_eq!(config.to_string(), both);
+}
+
+#[test]
+fn test_lint_names() {
+    for lint in Lint::ALL {
+        assert_eq!(lint.to_string().parse::<Lint>().unwrap(), lint);
+    }
+
+    assert!("unused".parse::<Lint>().is_err());
+    assert_eq!(
+        DiagnosticCode::Lint(Lint::ShadowedBind).to_string(),
+        "shadowed-bind"
+    );
+}
+
+#[test]
+fn test_lint_duplicate_section_fix() {
+    let levels = LintLevels::default();
+    let duplicate = |input: &str| {
+        let config: Config = input.parse().unwrap();
+        config
+            .lint(&levels, |_| true)
+            .into_iter()
+            .find(|lint| lint.diagnostic.code == DiagnosticCode::Lint(Lint::DuplicateSection))
+            .unwrap()
+    };
+
+    assert!(
+        duplicate("general {\n    gaps_in = 2\n}\ngeneral {\n    gaps_out = 3\n}\n")
+            .fix
+            .is_some()
+    );
+
+    // Moving the second block up would let `general:gaps_in = 3` or `$gaps = 5` win
+    let set_between = "general {\n    gaps_in = 2\n}\ngeneral:gaps_in = 3\n\
+                       general {\n    gaps_in = 10\n}\n";
+    assert!(duplicate(set_between).fix.is_none());
+
+    let defined_between = "$gaps = 2\ngeneral {\n    gaps_in = $gaps\n}\n$gaps = 5\n\
+                           general {\n    gaps_out = $gaps\n}\n";
+    assert!(duplicate(defined_between).fix.is_none());
+}
@@ -0,0 +1,114 @@
+use hyprland_config::{migrate::Migration, Config};
+
+#[test]
+fn test_migrate_options() {
+    let mut config: Config = r#"general {
+    gaps_in = 5
+    damage_tracking = full
+}
+
+decoration {
+    rounding = 10
+    blur = yes # blur behind windows
+    blur_size = 3
+    drop_shadow = yes
+    blur_passes = 2
+}
+
+misc:no_vfr = false
+animations:speed = 3
+"#
+    .parse()
+    .unwrap();
+
+    let migrations = config.migrate();
+
+    assert_eq!(
+        config.to_string(),
+        r#"general {
+    gaps_in = 5
+}
+
+decoration {
+    rounding = 10
+    blur {
+        enabled = yes # blur behind windows
+        size = 3
+        passes = 2
+    }
+    drop_shadow = yes
+}
+
+misc:vfr = true
+animations:speed = 3
+
+debug {
+    damage_tracking = full
+}
+"#
+    );
+    assert_eq!(
+        migrations[0],
+        Migration {
+            from: "general:damage_tracking = full".to_string(),
+            to: Some("debug:damage_tracking = full".to_string()),
+            note: "moved to the debug section".to_string(),
+        }
+    );
+    assert_eq!(
+        migrations
+            .iter()
+            .map(|migration| &migration.from)
+            .collect::<Vec<_>>(),
+        [
+            "general:damage_tracking = full",
+            "decoration:blur = yes",
+            "decoration:blur_size = 3",
+            "decoration:blur_passes = 2",
+            "misc:no_vfr = false",
+        ]
+    );
+    assert!(config.migrate().is_empty());
+}
+
+#[test]
+fn test_migrate_keeps_existing_replacement() {
+    let mut config: Config =
+        "decoration {\n    blur_size = 3\n    blur {\n        size = 5\n    }\n}\n"
+            .parse()
+            .unwrap();
+
+    let migrations = config.migrate();
+
+    assert_eq!(
+        config.to_string(),
+        "decoration {\n    blur {\n        size = 5\n    }\n}\n"
+    );
+    assert_eq!(
+        migrations[0].to_string(),
+        "decoration:blur_size = 3 removed (`decoration:blur:size` is already set)"
+    );
+}
+
+#[test]
+fn test_migrate_window_rules() {
+    let mut config: Config = r#"windowrule = float, ^(kitty)$
+windowrule=opacity 0.9,title:^(vim)$ # editor
+windowrule = float
+windowrulev2 = float, class:^(pavucontrol)$
+"#
+    .parse()
+    .unwrap();
+
+    let migrations = config.migrate();
+
+    assert_eq!(
+        config.to_string(),
+        r#"windowrulev2 = float, class:^(kitty)$
+windowrulev2=opacity 0.9,title:^(vim)$ # editor
+windowrule = float
+windowrulev2 = float, class:^(pavucontrol)$
+"#
+    );
+    assert_eq!(migrations.len(), 2);
+}
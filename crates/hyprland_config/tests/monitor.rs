@@ -0,0 +1,128 @@
+use hyprland_config::{
+    monitor::{
+        applied_rule, compare_monitors, MonitorDivergence, MonitorMode, MonitorRule,
+        MonitorSettings, MonitorState, MonitorTarget, Position, Resolution, Scale, Transform, Vrr,
+    },
+    util::Parse,
+    Config, DEFAULT_CONFIG_STR,
+};
+
+#[test]
+fn test_monitor_rule_parser() {
+    let config: Config = DEFAULT_CONFIG_STR.parse().unwrap();
+    let rules = config.monitor_rules().unwrap();
+
+    assert_eq!(
+        rules,
+        vec![MonitorRule {
+            target: MonitorTarget::Any,
+            settings: MonitorSettings::Mode(MonitorMode {
+                resolution: Resolution::Preferred,
+                position: Position::Auto,
+                scale: Scale::Auto,
+                transform: None,
+                mirror: None,
+                bitdepth: None,
+                vrr: None,
+            }),
+        }]
+    );
+    assert_eq!(rules[0].to_string(), "monitor = ,preferred,auto,auto");
+
+    let input = "monitor = desc:Chimei Innolux Corporation 0x150C,2560x1440@143.9,-1920x0,1.5,transform,1,mirror,DP-1,bitdepth,10,vrr,2";
+    let (rest, rule) = MonitorRule::parse(input).unwrap();
+
+    assert_eq!(rest, "");
+    assert_eq!(
+        rule,
+        MonitorRule {
+            target: MonitorTarget::Description("Chimei Innolux Corporation 0x150C".to_string()),
+            settings: MonitorSettings::Mode(MonitorMode {
+                resolution: Resolution::Custom {
+                    width: 2560,
+                    height: 1440,
+                    refresh_rate: Some(143.9),
+                },
+                position: Position::Custom(-1920, 0),
+                scale: Scale::Custom(1.5),
+                transform: Some(Transform::Normal90),
+                mirror: Some("DP-1".to_string()),
+                bitdepth: Some(10),
+                vrr: Some(Vrr::Fullscreen),
+            }),
+        }
+    );
+    assert_eq!(rule.to_string(), input);
+
+    let (_, rule) = MonitorRule::parse("monitor=HDMI-A-1, disable").unwrap();
+    assert_eq!(rule.target, MonitorTarget::Name("HDMI-A-1".to_string()));
+    assert_eq!(rule.settings, MonitorSettings::Disable);
+
+    let (_, rule) = MonitorRule::parse("monitor = eDP-1, highrr, auto, 1").unwrap();
+    assert_eq!(rule.to_string(), "monitor = eDP-1,highrr,auto,1");
+
+    assert!(MonitorRule::parse("monitor = eDP-1, 1920x1080").is_err());
+    assert!(MonitorRule::parse("monitor = eDP-1, 1920x1080, 0x0, 1, transform, 8").is_err());
+    assert!(MonitorRule::parse("monitor = eDP-1, 1920x1080, 0x0, 1, rotate, 1").is_err());
+}
+
+#[test]
+fn test_monitor_comparison() {
+    let config: Config = r"
+monitor = , preferred, auto, 1
+monitor = DP-1, 2560x1440@144, 1920x0, 1
+monitor = DP-1, 2560x1440@144, 1920x0, 1.25
+monitor = desc:Dell, disable
+"
+    .parse()
+    .unwrap();
+    let rules = config.monitor_rules().unwrap();
+
+    let monitors = vec![
+        MonitorState {
+            name: "DP-1".to_string(),
+            width: 2560,
+            height: 1440,
+            refresh_rate: 143.97,
+            x: 1920,
+            scale: 1.0,
+            ..Default::default()
+        },
+        MonitorState {
+            name: "eDP-1".to_string(),
+            width: 1920,
+            height: 1080,
+            refresh_rate: 60.0,
+            scale: 1.0,
+            ..Default::default()
+        },
+        MonitorState {
+            name: "HDMI-A-1".to_string(),
+            description: "Dell Inc. U2719D".to_string(),
+            scale: 1.0,
+            transform: Transform::Normal270,
+            ..Default::default()
+        },
+    ];
+
+    assert_eq!(applied_rule(&rules, &monitors[0]), Some(&rules[2]));
+    assert_eq!(applied_rule(&rules, &monitors[1]), Some(&rules[0]));
+    assert_eq!(applied_rule(&rules, &monitors[2]), Some(&rules[3]));
+    assert_eq!(applied_rule(&[], &monitors[0]), None);
+
+    let reports = compare_monitors(&rules, &monitors);
+
+    assert_eq!(
+        reports[0].divergences,
+        vec![MonitorDivergence::Scale {
+            expected: 1.25,
+            actual: 1.0
+        }]
+    );
+    assert!(reports[1].divergences.is_empty());
+    assert_eq!(reports[2].divergences, vec![MonitorDivergence::Enabled]);
+    assert_eq!(
+        reports[0].divergences[0].to_string(),
+        "scale is 1, expected 1.25"
+    );
+}
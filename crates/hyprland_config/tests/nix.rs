@@ -0,0 +1,123 @@
+use hyprland_config::{nix::NixValue, Config, DEFAULT_CONFIG_STR};
+
+#[test]
+fn test_to_nix() {
+    let config = Config::parse_recovering(
+        "$mod = SUPER\ngeneral {\n    gaps_in = 5\n    col.active_border = rgba(33ccffee)\n}\ninput {\n    sensitivity = -0.5\n    touchpad {\n        natural_scroll = yes\n    }\n}\nbind = $mod, Q, exec, kitty\nexec-once = waybar\nbind = $mod, M, exit,\n",
+    );
+
+    assert_eq!(
+        config.to_nix().unwrap().to_string(),
+        r#"{
+  "$mod" = "SUPER";
+  general = {
+    gaps_in = 5;
+    "col.active_border" = "rgba(33ccffee)";
+  };
+  input = {
+    sensitivity = -0.5;
+    touchpad = {
+      natural_scroll = true;
+    };
+  };
+  bind = [
+    "$mod, Q, exec, kitty"
+    "$mod, M, exit,"
+  ];
+  exec-once = [
+    "waybar"
+  ];
+}"#
+    );
+
+    assert!(
+        Config::parse_recovering("submap = resize\nbinde = , l, resizeactive, 10 0\n")
+            .to_nix()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_parse_nix() {
+    let value: NixValue = r#"
+        # home-manager settings
+        {
+          "$mod" = "SUPER";
+          general.gaps_in = 5;
+          general = { border_size = 2; };
+          /* block */ input.sensitivity = -0.5;
+          bind = [ "$mod, Q, exec, kitty" "$mod, 1, workspace, 1" ];
+          misc.disable_hyprland_logo = true;
+          "col.shadow" = "rgba(1a1a1aee)";
+          animations.animation = [ ];
+          list = [ (-1) 2.0 "a \"quoted\" \${literal}" ];
+        }
+    "#
+    .parse()
+    .unwrap();
+
+    let NixValue::Attrs(attrs) = &value else {
+        panic!("expected an attribute set")
+    };
+    assert_eq!(
+        attrs
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>(),
+        [
+            "$mod",
+            "general",
+            "input",
+            "bind",
+            "misc",
+            "col.shadow",
+            "animations",
+            "list"
+        ]
+    );
+    assert_eq!(
+        attrs[1].1,
+        NixValue::Attrs(vec![
+            ("gaps_in".to_string(), NixValue::Int(5)),
+            ("border_size".to_string(), NixValue::Int(2)),
+        ])
+    );
+    assert_eq!(
+        attrs[7].1,
+        NixValue::List(vec![
+            NixValue::Int(-1),
+            NixValue::Float(2.0),
+            NixValue::String("a \"quoted\" ${literal}".to_string()),
+        ])
+    );
+    assert_eq!(value.to_string().parse::<NixValue>().unwrap(), value);
+
+    assert!("{ a = 1; a = 2; }".parse::<NixValue>().is_err());
+    assert!(r#"{ a = "${pkgs.kitty}/bin/kitty"; }"#.parse::<NixValue>().is_err());
+    let err = "{\n  a = 1;\n  b = ;\n}".parse::<NixValue>().unwrap_err();
+    assert!(err.to_string().contains("line 3"), "{err}");
+}
+
+#[test]
+fn test_nix_roundtrip() {
+    let config = Config::parse_recovering(DEFAULT_CONFIG_STR);
+    let nix = config.to_nix().unwrap().to_string();
+
+    let imported = Config::from_nix(&nix).unwrap();
+    assert_eq!(imported.to_nix().unwrap().to_string(), nix);
+    assert_eq!(imported.options().len(), config.options().len());
+    assert_eq!(
+        imported.get("decoration:rounding"),
+        config.get("decoration:rounding")
+    );
+    assert_eq!(
+        imported
+            .keywords()
+            .filter(|keyword| keyword.name == "bind")
+            .count(),
+        config
+            .keywords()
+            .filter(|keyword| keyword.name == "bind")
+            .count()
+    );
+}
@@ -0,0 +1,137 @@
+use hyprland_config::{
+    resolve::VariableIssueKind,
+    source::{ConfigTree, NodeLocation},
+    variable::{Modifier, ModifierSet, ModifierToken, SuperModifier},
+    Config, DEFAULT_CONFIG_STR,
+};
+
+#[test]
+fn test_resolve_default_config() {
+    let config: Config = DEFAULT_CONFIG_STR.parse().unwrap();
+    let resolved = config.resolve();
+
+    assert!(resolved.issues.is_empty());
+    assert_eq!(resolved.variables["mainMod"], "SUPER");
+    assert_eq!(config.resolved_value("$mainMod").as_deref(), Some("SUPER"));
+
+    let submaps = resolved.config.submaps().unwrap();
+    assert_eq!(
+        submaps[0].binds[0].mods,
+        ModifierSet(vec![ModifierToken::Modifier(Modifier::SUPER(
+            SuperModifier::SUPER
+        ))])
+    );
+    assert_eq!(resolved.config.to_string().matches("$mainMod").count(), 1);
+}
+
+#[test]
+fn test_resolve_order() {
+    let config: Config = r"
+$terminal = kitty
+$term = foot
+bind = SUPER, Q, exec, $terminal --single-instance
+bind = SUPER, T, exec, $term
+bind = SUPER, E, exec, $editor
+$editor = $terminal -e nvim
+$unused = 1
+$term = alacritty
+general {
+    gaps_in = $gaps
+    col.active_border = $color
+}
+$gaps = 5
+general {
+    gaps_out = $gaps
+}
+exec-once = price: 5$
+"
+    .parse()
+    .unwrap();
+    let resolved = config.resolve();
+
+    assert_eq!(
+        resolved.config.submaps().unwrap()[0]
+            .binds
+            .iter()
+            .map(|bind| bind.arg.clone().unwrap())
+            .collect::<Vec<_>>(),
+        ["kitty --single-instance", "foot", "$editor"]
+    );
+    assert_eq!(resolved.value("$editor"), Some("kitty -e nvim"));
+    assert_eq!(resolved.value("$term"), Some("alacritty"));
+    assert_eq!(resolved.value("general:gaps_in"), Some("$gaps"));
+    assert_eq!(resolved.value("general:gaps_out"), Some("5"));
+    assert_eq!(resolved.value("exec-once"), Some("price: 5$"));
+    assert_eq!(config.value("general:gaps_out"), Some("$gaps"));
+    assert_eq!(config.resolved_value("general:missing"), None);
+
+    let issues = resolved
+        .issues
+        .iter()
+        .map(|issue| (issue.kind.clone(), issue.name.as_str(), issue.location.node))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        issues,
+        [
+            (VariableIssueKind::UsedBeforeDefinition, "editor", 5),
+            (
+                VariableIssueKind::Redefined {
+                    previous: NodeLocation { file: 0, node: 2 }
+                },
+                "term",
+                8
+            ),
+            (VariableIssueKind::UsedBeforeDefinition, "gaps", 9),
+            (VariableIssueKind::Undefined, "color", 9),
+            (VariableIssueKind::Unused, "editor", 6),
+            (VariableIssueKind::Unused, "unused", 7),
+        ]
+    );
+    assert_eq!(resolved.issues[3].to_string(), "`$color` is not defined");
+
+    // Unknown names in shell commands are shell variables
+    let shell: Config = "exec-once = $HOME/bin/x\nexec = notify-send $USER\nexec = $mainMod\n\
+                         $mainMod = SUPER\nbind = $mainMod, Y, exec, y\n"
+        .parse()
+        .unwrap();
+    let resolved = shell.resolve();
+    let issues = resolved
+        .issues
+        .iter()
+        .map(|issue| (issue.kind.clone(), issue.name.as_str(), issue.location.node))
+        .collect::<Vec<_>>();
+
+    assert_eq!(resolved.value("exec-once"), Some("$HOME/bin/x"));
+    assert_eq!(
+        issues,
+        [(VariableIssueKind::UsedBeforeDefinition, "mainMod", 2)]
+    );
+    assert!(Config::parse_recovering("exec-once = $HOME/bin/x\n")
+        .validate()
+        .is_empty());
+}
+
+#[test]
+fn test_resolve_sourced_variables() {
+    let dir = std::env::temp_dir().join(format!("hyprland_config_resolve_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("colors.conf"), "$accent = rgb(ff0000)\n").unwrap();
+    std::fs::write(
+        dir.join("hyprland.conf"),
+        "source = colors.conf\ngeneral {\n    col.active_border = $accent\n}\n",
+    )
+    .unwrap();
+
+    let resolved = ConfigTree::load(dir.join("hyprland.conf"))
+        .unwrap()
+        .resolve();
+
+    assert!(resolved.issues.is_empty());
+    assert_eq!(
+        resolved.value("general:col.active_border"),
+        Some("rgb(ff0000)")
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
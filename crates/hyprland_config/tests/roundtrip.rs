@@ -0,0 +1,147 @@
+use hyprland_config::{
+    format::FormatOptions,
+    schema::OptionType,
+    variable::{
+        Bool, Color, ColorNotation, ControlModifier, Gradient, Modifier, ModifierSet,
+        ModifierToken, SuperModifier, VariableValue, Vec2,
+    },
+    Config,
+};
+use proptest::prelude::*;
+
+fn finite() -> impl Strategy<Value = f32> {
+    prop_oneof![
+        -1000.0f32..1000.0,
+        any::<f32>().prop_filter("finite", |x| x.is_finite())
+    ]
+}
+
+fn bool_value() -> impl Strategy<Value = Bool> {
+    prop::sample::select(vec![
+        Bool::True,
+        Bool::False,
+        Bool::Yes,
+        Bool::No,
+        Bool::On,
+        Bool::Off,
+        Bool::One,
+        Bool::Zero,
+    ])
+}
+
+fn color() -> impl Strategy<Value = Color> {
+    prop_oneof![
+        any::<u32>().prop_map(Color::rgba),
+        (0..=0xffffffu32).prop_map(Color::rgb),
+        any::<u32>().prop_map(Color::legacy),
+        any::<u32>().prop_map(|rgba| Color::rgba(rgba).with_notation(ColorNotation::Hex)),
+    ]
+}
+
+fn gradient() -> impl Strategy<Value = Gradient> {
+    (prop::collection::vec(color(), 2..5), any::<Option<u16>>())
+        .prop_map(|(colors, angle)| Gradient { colors, angle })
+}
+
+fn modifier_set() -> impl Strategy<Value = ModifierSet> {
+    let modifier = prop::sample::select(vec![
+        Modifier::SHIFT,
+        Modifier::CAPS,
+        Modifier::CTRL(ControlModifier::CTRL),
+        Modifier::CTRL(ControlModifier::CONTROL),
+        Modifier::ALT,
+        Modifier::MOD2,
+        Modifier::MOD3,
+        Modifier::SUPER(SuperModifier::SUPER),
+        Modifier::SUPER(SuperModifier::WIN),
+        Modifier::SUPER(SuperModifier::LOGO),
+        Modifier::SUPER(SuperModifier::MOD4),
+        Modifier::MOD5,
+    ]);
+    let token = prop_oneof![
+        modifier.prop_map(ModifierToken::Modifier),
+        "[a-zA-Z][a-zA-Z0-9_]{0,8}".prop_map(ModifierToken::Variable),
+    ];
+
+    prop::collection::vec(token, 1..5).prop_map(ModifierSet)
+}
+
+/// A value of the option type and the type
+fn typed_value() -> impl Strategy<Value = (VariableValue, OptionType)> {
+    prop_oneof![
+        any::<i32>().prop_map(|x| (VariableValue::Int(x), OptionType::Int)),
+        finite().prop_map(|x| (VariableValue::Float(x), OptionType::Float)),
+        bool_value().prop_map(|x| (VariableValue::Bool(x), OptionType::Bool)),
+        color().prop_map(|x| (VariableValue::Color(x), OptionType::Color)),
+        gradient().prop_map(|x| (VariableValue::Gradient(x), OptionType::Gradient)),
+        (finite(), finite()).prop_map(|(x, y)| (VariableValue::Vec2(Vec2(x, y)), OptionType::Vec2)),
+        "[a-z][a-z0-9 _-]{0,16}[a-z]".prop_map(|x| (VariableValue::String(x), OptionType::String)),
+        "[a-zA-Z][a-zA-Z0-9_]{0,8}".prop_map(|x| (VariableValue::Variable(x), OptionType::Int)),
+    ]
+}
+
+/// A line of a config, keywords, variables, comments, sections and blank lines
+fn line() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (
+            "[ \t]{0,4}",
+            "[a-z_.:]{1,12}",
+            "[ ]{0,2}",
+            "[ ]{0,2}",
+            "[^#\n\r]{0,20}"
+        )
+            .prop_map(|(indent, name, before, after, value)| format!(
+                "{indent}{name}{before}={after}{value}"
+            )),
+        ("[a-zA-Z][a-zA-Z0-9_]{0,8}", "[^#\n\r]{0,20}")
+            .prop_map(|(name, value)| format!("${name} = {value}")),
+        "[ \t]{0,4}#[^\n\r]{0,20}",
+        "[a-z]{1,8} \\{",
+        Just("}".to_string()),
+        Just(String::new()),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_value_roundtrip((value, ty) in typed_value()) {
+        let printed = match &value {
+            VariableValue::Variable(name) => format!("${name}"),
+            value => value.to_string(),
+        };
+
+        prop_assert_eq!(ty.parse_value(&printed), Some(value));
+    }
+
+    #[test]
+    fn test_modifier_set_roundtrip(set in modifier_set()) {
+        let parsed = ModifierSet::from_value(&set.to_string()).unwrap();
+
+        prop_assert!(parsed.same_as(&set));
+        prop_assert_eq!(parsed, set);
+    }
+
+    #[test]
+    fn test_config_roundtrip(lines in prop::collection::vec((line(), "\r{0,2}"), 0..20)) {
+        let input = lines
+            .into_iter()
+            .map(|(line, carriage_returns)| line + &carriage_returns)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let config = Config::parse_recovering(&input);
+        let recovered = config.to_string();
+        prop_assert_eq!(recovered.trim_end(), input.trim_end());
+
+        if let Ok(config) = input.parse::<Config>() {
+            prop_assert_eq!(config.to_string(), recovered);
+        }
+
+        let options = FormatOptions::default();
+        let formatted = config.format(&options).to_string();
+        prop_assert_eq!(
+            Config::parse_recovering(&formatted).format(&options).to_string(),
+            formatted
+        );
+    }
+}
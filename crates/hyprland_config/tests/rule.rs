@@ -0,0 +1,138 @@
+use hyprland_config::{
+    keyword::Keyword,
+    rule::{
+        matching_layer_rules, matching_window_rules, LayerRule, LayerRuleAction, RulePattern,
+        WindowMatcher, WindowRule, WindowRuleAction, WindowState, WorkspaceMatcher,
+    },
+    Config,
+};
+
+#[test]
+fn test_window_rule_parser() {
+    let config: Config = r"
+windowrule = float, ^(kitty)$
+windowrule = move 0 0, title:^(Firefox)(.*)$
+windowrulev2 = opacity 0.8 0.8, class:^(kitty|foot)$, title:a,b, floating:1, workspace:name:web
+windowrulev2 = workspace 3, class:^(steam)$, xwayland:0, workspace:2
+"
+    .parse()
+    .unwrap();
+    let rules = config.window_rules().unwrap();
+
+    assert_eq!(
+        rules[0],
+        WindowRule {
+            action: WindowRuleAction::Float,
+            matchers: vec![WindowMatcher::Class(RulePattern::new("^(kitty)$").unwrap())],
+            v2: false,
+        }
+    );
+    assert_eq!(
+        rules[1].matchers,
+        vec![WindowMatcher::Title(
+            RulePattern::new("^(Firefox)(.*)$").unwrap()
+        )]
+    );
+    assert_eq!(
+        rules[2],
+        WindowRule {
+            action: WindowRuleAction::Opacity("0.8 0.8".to_string()),
+            matchers: vec![
+                WindowMatcher::Class(RulePattern::new("^(kitty|foot)$").unwrap()),
+                WindowMatcher::Title(RulePattern::new("a,b").unwrap()),
+                WindowMatcher::Floating(true),
+                WindowMatcher::Workspace(WorkspaceMatcher::Name("web".to_string())),
+            ],
+            v2: true,
+        }
+    );
+    assert_eq!(
+        rules[3].to_string(),
+        "windowrulev2 = workspace 3, class:^(steam)$, xwayland:0, workspace:2"
+    );
+    assert_eq!(rules[0].to_string(), "windowrule = float, ^(kitty)$");
+    assert_eq!(
+        rules[1].to_string(),
+        "windowrule = move 0 0, title:^(Firefox)(.*)$"
+    );
+}
+
+#[test]
+fn test_rule_validation() {
+    let rule = |name: &str, value: &str| WindowRule::from_keyword(&Keyword::new(name, value));
+
+    assert!(rule("windowrule", "float, ^(kitty$").unwrap().is_err());
+    assert!(rule("windowrule", "levitate, ^(kitty)$").unwrap().is_err());
+    assert!(rule("windowrule", "float 1, ^(kitty)$").unwrap().is_err());
+    assert!(rule("windowrule", "move, ^(kitty)$").unwrap().is_err());
+    assert!(rule("windowrule", "float").unwrap().is_err());
+    assert!(rule("windowrulev2", "float, role:dialog").unwrap().is_err());
+    assert!(rule("windowrulev2", "float, class:a, class:b")
+        .unwrap()
+        .is_err());
+    assert!(rule("windowrulev2", "float, floating:maybe")
+        .unwrap()
+        .is_err());
+    assert!(rule("windowrulev2", "float, workspace:web")
+        .unwrap()
+        .is_err());
+    assert!(rule("bind", "SUPER, Q, exec, kitty").is_none());
+
+    let layer_rule = |value: &str| LayerRule::from_keyword(&Keyword::new("layerrule", value));
+
+    assert_eq!(
+        layer_rule("blur, waybar").unwrap().unwrap(),
+        LayerRule {
+            action: LayerRuleAction::Blur,
+            namespace: RulePattern::new("waybar").unwrap(),
+        }
+    );
+    assert!(layer_rule("blur").unwrap().is_err());
+    assert!(layer_rule("shine, waybar").unwrap().is_err());
+}
+
+#[test]
+fn test_rule_evaluation() {
+    let config: Config = r"
+windowrule = float, kitty
+windowrulev2 = pin, class:^(kitty)$, floating:1
+windowrulev2 = noblur, title:^vim, workspace:2
+layerrule = blur, ^(waybar|rofi)$
+layerrule = ignorezero, rofi
+"
+    .parse()
+    .unwrap();
+    let window_rules = config.window_rules().unwrap();
+    let layer_rules = config.layer_rules().unwrap();
+
+    let kitty = WindowState {
+        class: "kitty".to_string(),
+        title: "vim main.rs".to_string(),
+        floating: true,
+        workspace_id: 1,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        matching_window_rules(&window_rules, &kitty),
+        vec![&window_rules[0], &window_rules[1]]
+    );
+
+    let tiled_kitty = WindowState {
+        floating: false,
+        workspace_id: 2,
+        ..kitty
+    };
+
+    assert_eq!(
+        matching_window_rules(&window_rules, &tiled_kitty),
+        vec![&window_rules[0], &window_rules[2]]
+    );
+    assert!(matching_window_rules(&window_rules, &WindowState::default()).is_empty());
+
+    assert_eq!(
+        matching_layer_rules(&layer_rules, "rofi"),
+        vec![&layer_rules[0], &layer_rules[1]]
+    );
+    assert!(matching_layer_rules(&layer_rules, "notifications").is_empty());
+}
@@ -0,0 +1,118 @@
+use hyprland_config::{
+    schema::{OptionType, Schema, ValueError},
+    section::{InputData, MasterData, SectionData},
+};
+
+#[test]
+fn test_schema_options() {
+    let schema = Schema::get();
+
+    let gaps_in = schema.option("general:gaps_in").unwrap();
+    assert_eq!(gaps_in.ty, OptionType::Int);
+    assert_eq!(gaps_in.default.as_deref(), Some("5"));
+    assert_eq!(gaps_in.min, Some(0.0));
+    assert_eq!(gaps_in.max, None);
+    assert_eq!(gaps_in.description, "Gaps between windows");
+
+    let border = schema.option("general:col.active_border").unwrap();
+    assert_eq!(border.ty, OptionType::Gradient);
+    assert_eq!(border.default.as_deref(), Some("0xff444444"));
+
+    let layout = schema.option("general:layout").unwrap();
+    assert_eq!(layout.ty, OptionType::Enum);
+    assert_eq!(layout.values, ["dwindle", "master"]);
+
+    let tap = schema.option("input:touchpad:tap-to-click").unwrap();
+    assert_eq!(tap.ty, OptionType::Bool);
+    assert_eq!(tap.default.as_deref(), Some("true"));
+
+    let sensitivity = schema.option("device:epic-mouse-v1:sensitivity").unwrap();
+    assert_eq!(sensitivity.path, "device:*:sensitivity");
+    assert_eq!(sensitivity.ty, OptionType::Float);
+    assert_eq!(sensitivity.default, None);
+
+    assert!(schema.option("general:gaps").is_none());
+    assert!(schema
+        .options()
+        .iter()
+        .all(|option| !option.description.is_empty()));
+
+    let deprecated = schema.option("misc:no_vfr").unwrap().deprecated.as_ref();
+    assert_eq!(deprecated.unwrap().replaced_by, Some("misc:vfr"));
+    assert!(schema.option("misc:vfr").unwrap().deprecated.is_none());
+
+    // The flat blur options are still typed, once
+    let blur_size = schema.option("decoration:blur_size").unwrap();
+    assert_eq!(blur_size.ty, OptionType::Int);
+    assert_eq!(
+        blur_size.deprecated.as_ref().unwrap().replaced_by,
+        Some("decoration:blur:size")
+    );
+    assert_eq!(
+        schema.option("decoration:blur:size").unwrap().ty,
+        OptionType::Int
+    );
+    assert_eq!(
+        schema
+            .options()
+            .iter()
+            .filter(|option| option.path == "decoration:blur_size")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_schema_check() {
+    let schema = Schema::get();
+    let check = |path: &str, value: &str| schema.option(path).unwrap().check(value);
+
+    assert_eq!(check("general:gaps_in", "10"), Ok(()));
+    assert_eq!(
+        check("general:gaps_in", "ten"),
+        Err(ValueError::Type(OptionType::Int))
+    );
+    assert_eq!(
+        check("general:gaps_in", "-1"),
+        Err(ValueError::Range {
+            min: Some(0.0),
+            max: None
+        })
+    );
+    assert_eq!(
+        check("decoration:active_opacity", "1.5")
+            .unwrap_err()
+            .to_string(),
+        "expected a value between 0 and 1"
+    );
+    assert_eq!(check("input:accel_profile", "flat"), Ok(()));
+    assert_eq!(check("input:accel_profile", ""), Ok(()));
+    assert_eq!(
+        check("input:accel_profile", "linear")
+            .unwrap_err()
+            .to_string(),
+        "expected one of adaptive, flat"
+    );
+    assert_eq!(check("general:col.inactive_border", "rgb(ff0000)"), Ok(()));
+    assert_eq!(check("decoration:shadow_offset", "1 2"), Ok(()));
+}
+
+#[test]
+fn test_schema_defaults() {
+    let schema = Schema::get();
+    let default = |path: &str| schema.option(path).unwrap().default.clone();
+
+    assert_eq!(default("input:kb_layout").as_deref(), Some("us"));
+    assert_eq!(default("input:touchdevice:output").as_deref(), Some(""));
+    assert_eq!(default("master:orientation").as_deref(), Some("left"));
+    assert_eq!(default("decoration:col.shadow_inactive"), None);
+    assert_eq!(
+        InputData::schema("input:").len(),
+        InputData::schema("").len()
+    );
+
+    let mut master = MasterData::default();
+    assert!(master.set("orientation", "center").unwrap());
+    assert!(master.set("orientation", "middle").is_err());
+    assert_eq!(master.orientation, "center");
+}
@@ -0,0 +1,106 @@
+use hyprland_config::{
+    section::{
+        DeviceData, GeneralData, InputData, Layout, Section, SectionData, SectionType, TouchpadData,
+    },
+    util::Parse,
+    variable::{Bool, Color, Gradient},
+    Config, DEFAULT_CONFIG_STR,
+};
+
+fn default_sections() -> Vec<SectionType> {
+    let config: Config = DEFAULT_CONFIG_STR.parse().unwrap();
+
+    config
+        .sections()
+        .map(|section| section.ty().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_general_data() {
+    let general = default_sections()
+        .into_iter()
+        .find_map(|section| match section {
+            SectionType::General(general) => Some(general),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(
+        general,
+        GeneralData {
+            gaps_in: 5,
+            gaps_out: 20,
+            border_size: 2,
+            col_active_border: Gradient {
+                colors: vec![Color::rgba(0x33ccffee), Color::rgba(0x00ff99ee)],
+                angle: Some(45),
+            },
+            col_inactive_border: Gradient {
+                colors: vec![Color::rgba(0x595959aa)],
+                angle: None,
+            },
+            layout: Layout::Dwindle,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_nested_section_data() {
+    let input = default_sections()
+        .into_iter()
+        .find_map(|section| match section {
+            SectionType::Input(input) => Some(input),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(input.kb_layout, "us");
+    assert_eq!(input.kb_variant, "");
+    assert_eq!(input.follow_mouse, 1);
+    assert_eq!(input.sensitivity, 0.0);
+    assert_eq!(
+        input.touchpad,
+        TouchpadData {
+            natural_scroll: Bool::No,
+            ..Default::default()
+        }
+    );
+
+    let mut input = InputData::default();
+    assert!(input.set("touchpad:tap-to-click", "false").unwrap());
+    assert!(!input.set("touchpad:unknown", "1").unwrap());
+    assert_eq!(input.touchpad.tap_to_click, Bool::False);
+}
+
+#[test]
+fn test_device_section_data() {
+    let device = default_sections()
+        .into_iter()
+        .find_map(|section| match section {
+            SectionType::Device { name, data } => Some((name, data)),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(
+        device,
+        (
+            "epic mouse V1".to_string(),
+            DeviceData {
+                sensitivity: Some(-0.5),
+                ..Default::default()
+            }
+        )
+    );
+}
+
+#[test]
+fn test_section_data_errors() {
+    let (_, section) = Section::parse("general {\n    gaps_in = lots\n}").unwrap();
+    assert!(section.ty().is_err());
+
+    let (_, section) = Section::parse("unknown {\n}").unwrap();
+    assert!(section.ty().is_err());
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use hyprland_config::{
+    cst::Node,
+    keyword::Keyword,
+    source::{expand_path, ConfigTree, Include},
+    ConfigElement,
+};
+
+/// Fresh directory under the system temp dir with the given files
+fn config_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("hyprland_config_{name}_{}", std::process::id()));
+
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("conf.d")).unwrap();
+
+    for (path, contents) in files {
+        std::fs::write(dir.join(path), contents).unwrap();
+    }
+
+    dir.canonicalize().unwrap()
+}
+
+#[test]
+fn test_source_tree() {
+    let dir = config_dir(
+        "tree",
+        &[
+            (
+                "hyprland.conf",
+                "$mainMod = SUPER\nsource = ./conf.d/colors.conf\nbind = $mainMod, Q, exec, kitty\nsource = conf.d/binds.conf\n",
+            ),
+            ("conf.d/colors.conf", "general {\n    col.active_border = 0xffffffff\n}\n"),
+            ("conf.d/binds.conf", "source = colors.conf\nbind = $mainMod, C, killactive,\n"),
+        ],
+    );
+
+    let tree = ConfigTree::load(dir.join("hyprland.conf")).unwrap();
+
+    assert_eq!(tree.files().len(), 3);
+    assert_eq!(tree.root().path, dir.join("hyprland.conf"));
+    assert_eq!(
+        tree.includes(),
+        &[
+            Include {
+                file: 0,
+                node: 1,
+                target: 1
+            },
+            Include {
+                file: 2,
+                node: 0,
+                target: 1
+            },
+            Include {
+                file: 0,
+                node: 3,
+                target: 2
+            },
+        ]
+    );
+
+    let origins = tree
+        .nodes()
+        .iter()
+        .map(|node| node.path.strip_prefix(&dir).unwrap().to_path_buf())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        origins,
+        [
+            "hyprland.conf",
+            "hyprland.conf",
+            "conf.d/colors.conf",
+            "hyprland.conf",
+            "hyprland.conf",
+            "conf.d/binds.conf",
+            "conf.d/colors.conf",
+            "conf.d/binds.conf",
+        ]
+        .map(PathBuf::from)
+    );
+    assert_eq!(tree.merged().sections().count(), 2);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_source_errors() {
+    let dir = config_dir(
+        "cycle",
+        &[
+            ("hyprland.conf", "source = conf.d/a.conf\n"),
+            ("conf.d/a.conf", "\nsource = b.conf\n"),
+            ("conf.d/b.conf", "source = ../hyprland.conf\n"),
+        ],
+    );
+
+    let err = format!(
+        "{:#}",
+        ConfigTree::load(dir.join("hyprland.conf")).unwrap_err()
+    );
+
+    assert!(err.contains("a.conf:2: `source = b.conf`"));
+    assert!(err.contains(&format!(
+        "Cyclic source: {0}/hyprland.conf -> {0}/conf.d/a.conf -> {0}/conf.d/b.conf -> {0}/hyprland.conf",
+        dir.display()
+    )));
+
+    std::fs::write(dir.join("hyprland.conf"), "source = missing.conf\n").unwrap();
+    assert!(ConfigTree::load(dir.join("hyprland.conf")).is_err());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_source_write_back() {
+    let dir = config_dir(
+        "write_back",
+        &[
+            (
+                "hyprland.conf",
+                "source = conf.d/binds.conf # binds\nexec-once = waybar\n",
+            ),
+            ("conf.d/binds.conf", "bind = SUPER, Q, exec, kitty\n"),
+        ],
+    );
+
+    let mut tree = ConfigTree::load(dir.join("hyprland.conf")).unwrap();
+    let location = tree
+        .nodes()
+        .iter()
+        .find(|node| matches!(&node.node.element, ConfigElement::Keyword(keyword) if keyword.name == "bind"))
+        .unwrap()
+        .location;
+
+    tree.node_mut(location).unwrap().element =
+        ConfigElement::Keyword(Keyword::new("bind", "SUPER, Return, exec, foot"));
+    tree.file_mut(location.file)
+        .unwrap()
+        .config
+        .nodes_mut()
+        .push(Node::new(
+            "",
+            ConfigElement::Keyword(Keyword::new("bind", "SUPER, C, killactive,")),
+        ));
+
+    assert_eq!(tree.modified().count(), 1);
+    assert_eq!(tree.save().unwrap(), vec![dir.join("conf.d/binds.conf")]);
+    assert_eq!(tree.modified().count(), 0);
+
+    assert_eq!(
+        std::fs::read_to_string(dir.join("conf.d/binds.conf")).unwrap(),
+        "bind = SUPER, Return, exec, foot\nbind = SUPER, C, killactive,\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("hyprland.conf")).unwrap(),
+        "source = conf.d/binds.conf # binds\nexec-once = waybar\n"
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_expand_path() {
+    let home = std::env::var("HOME").unwrap();
+    let base = Path::new("/etc/hypr");
+    let none = BTreeMap::new();
+
+    assert_eq!(
+        expand_path("~/.config/hypr/colors.conf", &none, base).unwrap(),
+        PathBuf::from(format!("{home}/.config/hypr/colors.conf"))
+    );
+    assert_eq!(
+        expand_path("$HOME/a.conf", &none, base).unwrap(),
+        PathBuf::from(format!("{home}/a.conf"))
+    );
+    assert_eq!(
+        expand_path("${HOME}_x/a.conf", &none, base).unwrap(),
+        PathBuf::from(format!("{home}_x/a.conf"))
+    );
+    assert_eq!(
+        expand_path("colors.conf", &none, base).unwrap(),
+        PathBuf::from("/etc/hypr/colors.conf")
+    );
+    assert!(expand_path("$HYPRLAND_CONFIG_UNSET_VARIABLE/a.conf", &none, base).is_err());
+
+    // Config variables come first, their values can start with `~`
+    let variables = BTreeMap::from([
+        ("dir".to_string(), "~/.config/hypr".to_string()),
+        ("HOME".to_string(), "/home/config".to_string()),
+    ]);
+    assert_eq!(
+        expand_path("$dir/colors.conf", &variables, base).unwrap(),
+        PathBuf::from(format!("{home}/.config/hypr/colors.conf"))
+    );
+    assert_eq!(
+        expand_path("${HOME}/a.conf", &variables, base).unwrap(),
+        PathBuf::from("/home/config/a.conf")
+    );
+    assert!(expand_path("$dirs/a.conf", &variables, base).is_err());
+}
+
+#[test]
+fn test_source_variables() {
+    let dir = config_dir(
+        "variables",
+        &[
+            (
+                "hyprland.conf",
+                "$conf = conf.d\n$colors = $conf/colors.conf\nsource = $conf/binds.conf\n",
+            ),
+            ("conf.d/binds.conf", "source = ./../$colors\n"),
+            ("conf.d/colors.conf", "general {\n    border_size = 2\n}\n"),
+        ],
+    );
+
+    let tree = ConfigTree::load(dir.join("hyprland.conf")).unwrap();
+    let paths = tree
+        .files()
+        .iter()
+        .map(|file| file.path.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        paths,
+        [
+            dir.join("hyprland.conf"),
+            dir.join("conf.d/binds.conf"),
+            dir.join("conf.d/colors.conf"),
+        ]
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,190 @@
+use hyprland_config::{
+    diagnostic::{DiagnosticCode, Position, Severity},
+    Config, ConfigElement, DEFAULT_CONFIG_STR,
+};
+
+#[test]
+fn test_validate_default_config() {
+    let config: Config = DEFAULT_CONFIG_STR.parse().unwrap();
+    let diagnostics = config
+        .validate()
+        .into_iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.code, diagnostic.message))
+        .collect::<Vec<_>>();
+
+    // The default config still uses the flat `blur*` options
+    assert_eq!(
+        diagnostics,
+        [
+            "decoration:blur",
+            "decoration:blur_size",
+            "decoration:blur_passes",
+            "decoration:blur_new_optimizations",
+        ]
+        .map(|path| (
+            Severity::Warning,
+            DiagnosticCode::DeprecatedOption,
+            format!("`{path}` is deprecated")
+        ))
+    );
+}
+
+#[test]
+fn test_validate_diagnostics() {
+    let input = r#"$accent = rgb(33ccff)
+general {
+    gap_in = 5
+    gaps_out = -2
+    col.active_border = $accent
+    col.inactive_border = rgb(zzzzzz)
+    layout = spiral
+    border_size = 1
+    border_size = 2
+}
+dekoration {
+}
+input {
+    sensitivity = fast
+    touchpad {
+        natural_scroll = yes
+    }
+}
+misc:no_vfr = true
+bind = SUPER, Q
+bind = SUPER, Return, exec, $terminal
+windowrule = wobble, kitty
+wsbind = 1, DP-1
+exec_once = waybar
+"#;
+    let config: Config = input.parse().unwrap();
+
+    let diagnostics = config
+        .validate()
+        .into_iter()
+        .map(|diagnostic| {
+            (
+                diagnostic.severity,
+                diagnostic.code,
+                diagnostic.span.start.line,
+                diagnostic.span.bytes.slice(input).to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        diagnostics,
+        [
+            (Severity::Error, DiagnosticCode::UnknownOption, 3, "gap_in"),
+            (Severity::Error, DiagnosticCode::OutOfRange, 4, "-2"),
+            (
+                Severity::Error,
+                DiagnosticCode::InvalidColor,
+                6,
+                "rgb(zzzzzz)"
+            ),
+            (Severity::Error, DiagnosticCode::InvalidValue, 7, "spiral"),
+            (
+                Severity::Warning,
+                DiagnosticCode::DuplicateKey,
+                9,
+                "border_size = 2"
+            ),
+            (
+                Severity::Error,
+                DiagnosticCode::UnknownSection,
+                11,
+                "dekoration"
+            ),
+            (Severity::Error, DiagnosticCode::TypeMismatch, 14, "fast"),
+            (
+                Severity::Warning,
+                DiagnosticCode::DeprecatedOption,
+                19,
+                "misc:no_vfr"
+            ),
+            (
+                Severity::Error,
+                DiagnosticCode::MalformedBind,
+                20,
+                "SUPER, Q"
+            ),
+            (
+                Severity::Error,
+                DiagnosticCode::UndefinedVariable,
+                21,
+                "$terminal"
+            ),
+            (
+                Severity::Error,
+                DiagnosticCode::MalformedRule,
+                22,
+                "wobble, kitty"
+            ),
+            (
+                Severity::Error,
+                DiagnosticCode::UnknownKeyword,
+                24,
+                "exec_once"
+            ),
+        ]
+        .map(|(severity, code, line, text)| (severity, code, line, text.to_string()))
+    );
+
+    let diagnostics = config.validate();
+    assert_eq!(
+        diagnostics[0].help.as_deref(),
+        Some("Did you mean `general:gaps_in`?")
+    );
+    assert_eq!(diagnostics[0].span.start, Position { line: 3, column: 5 });
+    assert_eq!(
+        diagnostics[4].help.as_deref(),
+        Some("Previously set on line 8")
+    );
+    assert_eq!(
+        diagnostics[11].help.as_deref(),
+        Some("Did you mean `exec-once`?")
+    );
+}
+
+#[test]
+fn test_validate_recovery() {
+    let input =
+        "general {\n    gaps_in 5\n    border_size = 0\n}\n}\ndecoration {\n    rounding = 4\n";
+
+    assert!(input.parse::<Config>().is_err());
+
+    let config = Config::parse_recovering(input);
+    assert_eq!(config.to_string(), input);
+    assert!(matches!(
+        &config.nodes()[1].element,
+        ConfigElement::Invalid(line) if line == "}"
+    ));
+
+    let diagnostics = config.validate();
+    let messages = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.span.start.line, diagnostic.to_string()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        [
+            (2, "error[syntax]: Couldn't parse `gaps_in 5`"),
+            (5, "error[syntax]: Unmatched `}`"),
+            (6, "error[syntax]: Section `decoration` is never closed"),
+            (7, "error[unknown-keyword]: Unknown keyword `rounding`"),
+        ]
+        .map(|(line, message)| (line, message.to_string()))
+    );
+
+    assert_eq!(
+        diagnostics[0].render(input, "hyprland.conf"),
+        "error[syntax]: Couldn't parse `gaps_in 5`
+ --> hyprland.conf:2:5
+  |
+2 |     gaps_in 5
+  |     ^^^^^^^^^
+  = help: Expected `name = value`, `name {`, `}` or a `# comment`
+"
+    );
+}
@@ -1,8 +1,8 @@
 use hyprland_config::{
-    util::Parse,
+    util::{FromValue, Parse},
     variable::{
-        Bool, Color, ControlModifier, Gradient, Modifier, SuperModifier, Variable, VariableValue,
-        Vec2,
+        Bool, Color, ColorNotation, ControlModifier, Gradient, Modifier, ModifierSet,
+        ModifierToken, SuperModifier, Variable, VariableValue, Vec2,
     },
 };
 
@@ -45,15 +45,15 @@ fn test_variable_value_parser() {
     );
     assert_eq!(
         VariableValue::parse("rgba(8f4f9484)"),
-        Ok(("", VariableValue::Color(Color::RGBA(0x8f4f9484))))
+        Ok(("", VariableValue::Color(Color::rgba(0x8f4f9484))))
     );
     assert_eq!(
         VariableValue::parse("rgb(8f4f94)"),
-        Ok(("", VariableValue::Color(Color::RGB(0x8f4f94))))
+        Ok(("", VariableValue::Color(Color::rgb(0x8f4f94))))
     );
     assert_eq!(
         VariableValue::parse("0x8f4f94f7"),
-        Ok(("", VariableValue::Color(Color::Legacy(0x8f4f94f7))))
+        Ok(("", VariableValue::Color(Color::legacy(0x8f4f94f7))))
     );
     assert_eq!(
         VariableValue::parse("0 0"),
@@ -65,13 +65,18 @@ fn test_variable_value_parser() {
     );
     assert_eq!(
         VariableValue::parse("SHIFT"),
-        Ok(("", VariableValue::Mod(Modifier::SHIFT))),
+        Ok((
+            "",
+            VariableValue::Mod(ModifierSet(vec![ModifierToken::Modifier(Modifier::SHIFT)]))
+        )),
     );
     assert_eq!(
         VariableValue::parse("CTRL"),
         Ok((
             "",
-            VariableValue::Mod(Modifier::CTRL(ControlModifier::CTRL))
+            VariableValue::Mod(ModifierSet(vec![ModifierToken::Modifier(Modifier::CTRL(
+                ControlModifier::CTRL
+            ))]))
         )),
     );
     assert_eq!(
@@ -84,9 +89,9 @@ fn test_variable_value_parser() {
             "",
             VariableValue::Gradient(Gradient {
                 colors: vec![
-                    Color::RGB(0x8f4f94),
-                    Color::RGBA(0x8f4f9484),
-                    Color::Legacy(0x8f4f94f7),
+                    Color::rgb(0x8f4f94),
+                    Color::rgba(0x8f4f9484),
+                    Color::legacy(0x8f4f94f7),
                 ],
                 angle: Some(20)
             })
@@ -120,15 +125,66 @@ fn test_bool_parser() {
 fn test_color_parser() {
     let input = "rgba(b3ff1aee)";
     let rest_color = Color::parse(input);
-    assert!(matches!(rest_color, Ok(("", Color::RGBA(0xb3ff1aee)))));
+    assert_eq!(rest_color, Ok(("", Color::rgba(0xb3ff1aee))));
 
     let input = "rgb(b3ff1a)";
     let rest_color = Color::parse(input);
-    assert!(matches!(rest_color, Ok(("", Color::RGB(0xb3ff1a)))));
+    assert_eq!(rest_color, Ok(("", Color::rgb(0xb3ff1a))));
 
     let input = "0xeeb3ff1a";
     let rest_color = Color::parse(input);
-    assert!(matches!(rest_color, Ok(("", Color::Legacy(0xeeb3ff1a)))));
+    assert_eq!(rest_color, Ok(("", Color::legacy(0xeeb3ff1a))));
+}
+
+#[test]
+fn test_color_digits() {
+    for input in [
+        "rgb(123)",
+        "rgba(b3ff1a)",
+        "rgb(b3ff1aee)",
+        "0xb3ff1a",
+        "0xeeb3ff1a00",
+    ] {
+        assert!(
+            Color::from_value(input).is_none(),
+            "{input} should be rejected"
+        );
+    }
+
+    assert_eq!(
+        Color::parse("#b3ff1a"),
+        Ok(("", Color::rgb(0xb3ff1a).with_notation(ColorNotation::Hex)))
+    );
+}
+
+#[test]
+fn test_color_conversions() {
+    let color = Color::legacy(0xeeb3ff1a);
+
+    assert_eq!(
+        (color.r(), color.g(), color.b(), color.a()),
+        (0xb3, 0xff, 0x1a, 0xee)
+    );
+    assert_eq!(color.to_rgba(), 0xb3ff1aee);
+    assert_eq!(color.argb(), 0xeeb3ff1a);
+    assert!(color.same_color(&Color::rgba(0xb3ff1aee)));
+
+    assert_eq!(color.to_string(), "0xeeb3ff1a");
+    assert_eq!(
+        color.with_notation(ColorNotation::Rgba).to_string(),
+        "rgba(b3ff1aee)"
+    );
+    assert_eq!(
+        color.with_notation(ColorNotation::Hex).to_string(),
+        "#b3ff1aee"
+    );
+    // `rgb()` can't hold the alpha channel
+    assert_eq!(
+        color.with_notation(ColorNotation::Rgb).to_string(),
+        "rgba(b3ff1aee)"
+    );
+    assert_eq!(Color::rgb(0x00ff99).to_string(), "rgb(00ff99)");
+    assert_eq!(Color::new(0, 0x99, 0, 0xff).to_string(), "rgba(009900ff)");
 }
 
 #[test]
@@ -210,6 +266,52 @@ fn modifier_parse() {
     assert_eq!(rest_modifier, Ok(("", Modifier::MOD5)));
 }
 
+#[test]
+fn test_modifier_set() {
+    let set = ModifierSet::from_value("$mainMod CTRL_ALT").unwrap();
+    assert_eq!(
+        set,
+        ModifierSet(vec![
+            ModifierToken::Variable("mainMod".to_string()),
+            ModifierToken::Modifier(Modifier::CTRL(ControlModifier::CTRL)),
+            ModifierToken::Modifier(Modifier::ALT),
+        ])
+    );
+    assert_eq!(set.to_string(), "$mainMod CTRL ALT");
+    assert_eq!(set.mask(), None);
+    assert_eq!(
+        VariableValue::parse("$mainMod CTRL_ALT"),
+        Ok(("", VariableValue::Mod(set)))
+    );
+    assert_eq!(
+        VariableValue::parse("$mainMod"),
+        Ok(("", VariableValue::Variable("mainMod".to_string())))
+    );
+
+    let super_shift = ModifierSet::from_value("SUPER_SHIFT").unwrap();
+    assert_eq!(super_shift.to_string(), "SUPER SHIFT");
+    for same in ["SHIFT WIN", "LOGOSHIFT", "MOD4 SHIFT SUPER"] {
+        assert!(
+            super_shift.same_as(&ModifierSet::from_value(same).unwrap()),
+            "{same}"
+        );
+    }
+    assert!(!super_shift.same_as(&ModifierSet::from_value("SUPER").unwrap()));
+    assert_eq!(
+        ModifierSet::from_value("CONTROL WIN").unwrap().normalized(),
+        ModifierSet(vec![
+            ModifierToken::Modifier(Modifier::SUPER(SuperModifier::SUPER)),
+            ModifierToken::Modifier(Modifier::CTRL(ControlModifier::CTRL)),
+        ])
+    );
+
+    assert_eq!(super_shift.mask(), Some(65));
+    assert!(ModifierSet::from_mask(65).same_as(&super_shift));
+    assert_eq!(ModifierSet::from_value("").unwrap().mask(), Some(0));
+    assert!(ModifierSet::from_value("SUPER_").is_err());
+    assert!(ModifierSet::from_value("HYPER").is_err());
+}
+
 #[test]
 fn test_gradient_parser() {
     let input = "rgba(b3ff1aee) rgb(b3ff1a) 0xeeb3ff1a";
@@ -217,9 +319,9 @@ fn test_gradient_parser() {
         "",
         Gradient {
             colors: vec![
-                Color::RGBA(0xb3ff1aee),
-                Color::RGB(0xb3ff1a),
-                Color::Legacy(0xeeb3ff1a),
+                Color::rgba(0xb3ff1aee),
+                Color::rgb(0xb3ff1a),
+                Color::legacy(0xeeb3ff1a),
             ],
             angle: None,
         },
@@ -233,9 +335,9 @@ fn test_gradient_parser() {
         "",
         Gradient {
             colors: vec![
-                Color::RGBA(0xb3ff1aee),
-                Color::RGB(0xb3ff1a),
-                Color::Legacy(0xeeb3ff1a),
+                Color::rgba(0xb3ff1aee),
+                Color::rgb(0xb3ff1a),
+                Color::legacy(0xeeb3ff1a),
             ],
             angle: Some(20),
         },
@@ -0,0 +1,128 @@
+//! Completion of option, section and keyword names, `$variables`, bind dispatchers and
+//! the allowed values of options.
+
+use hyprland_config::{
+    bind::DISPATCHERS,
+    schema::{OptionSchema, OptionType, Schema},
+    validate::KEYWORDS,
+    Config, ConfigElement,
+};
+use itertools::Itertools;
+use lsp_types::{CompletionItem, CompletionItemKind, Documentation, Position};
+
+use crate::{
+    context::{option_path, section_options, section_path, word_at},
+    position,
+};
+
+/// Completions at `position`, the client filters them by what was typed already
+pub fn completions(text: &str, position: Position) -> Vec<CompletionItem> {
+    if let Some((_, word)) = word_at(text, position) {
+        if word.starts_with('$') {
+            return variables(text);
+        }
+    }
+
+    let line = position::line(text, position.line);
+    let cursor = position::offset(line, Position::new(0, position.character));
+    let before = &line[..cursor];
+    let section = section_path(text, position.line);
+
+    match before.split_once('=') {
+        Some((name, value)) => values(&section, name.trim(), value),
+        None => names(&section),
+    }
+}
+
+fn item(label: impl Into<String>, kind: CompletionItemKind) -> CompletionItem {
+    CompletionItem {
+        label: label.into(),
+        kind: Some(kind),
+        ..Default::default()
+    }
+}
+
+fn option_item(name: &str, option: &OptionSchema) -> CompletionItem {
+    CompletionItem {
+        detail: Some(option.ty.to_string()),
+        documentation: Some(Documentation::String(option.description.clone())),
+        deprecated: Some(option.deprecated.is_some()),
+        ..item(name, CompletionItemKind::PROPERTY)
+    }
+}
+
+/// Options and nested sections of the section, and keywords at the top level
+fn names(section: &str) -> Vec<CompletionItem> {
+    let mut items = section_options(section)
+        .map(|option| {
+            let name = option.path.rsplit(':').next().unwrap_or(&option.path);
+            option_item(name, option)
+        })
+        .collect_vec();
+
+    let prefix = option_path(section, "");
+    let sections = Schema::get()
+        .options()
+        .iter()
+        .filter_map(|option| option.path.strip_prefix(prefix.as_str()))
+        .filter_map(|rest| rest.split_once(':').map(|(name, _)| name))
+        .filter(|name| *name != "device")
+        .unique()
+        .map(|name| item(name, CompletionItemKind::MODULE));
+    items.extend(sections);
+
+    if section.is_empty() {
+        items.extend(
+            KEYWORDS
+                .iter()
+                .chain(&["bind"])
+                .map(|keyword| item(*keyword, CompletionItemKind::KEYWORD)),
+        );
+    }
+
+    items
+}
+
+/// Dispatchers in the third field of binds, and the allowed values of options
+fn values(section: &str, name: &str, value: &str) -> Vec<CompletionItem> {
+    if section.is_empty() && name.starts_with("bind") {
+        return match value.matches(',').count() {
+            2 => DISPATCHERS
+                .iter()
+                .map(|dispatcher| item(*dispatcher, CompletionItemKind::FUNCTION))
+                .collect(),
+            _ => vec![],
+        };
+    }
+
+    let Some(option) = Schema::get().option(&option_path(section, name)) else {
+        return vec![];
+    };
+
+    let values = match option.ty {
+        OptionType::Bool => vec!["true", "false"],
+        _ => option.values.to_vec(),
+    };
+
+    values
+        .into_iter()
+        .map(|value| item(value, CompletionItemKind::VALUE))
+        .collect()
+}
+
+/// Variables defined in the document, with their values
+fn variables(text: &str) -> Vec<CompletionItem> {
+    Config::parse_recovering(text)
+        .nodes()
+        .iter()
+        .filter_map(|node| match &node.element {
+            ConfigElement::Variable(variable) => Some(variable),
+            _ => None,
+        })
+        .unique_by(|variable| &variable.name)
+        .map(|variable| CompletionItem {
+            detail: Some(variable.value.clone()),
+            ..item(format!("${}", variable.name), CompletionItemKind::VARIABLE)
+        })
+        .collect()
+}
@@ -0,0 +1,86 @@
+//! What the cursor is on. Documents being edited are often incomplete, so the context is read
+//! from the text around the cursor instead of from a parsed config.
+
+use hyprland_config::schema::{OptionSchema, Schema};
+use lsp_types::Position;
+
+use crate::position;
+
+/// Characters of option paths, keyword names and `$variables`
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || "_-.:$".contains(c)
+}
+
+/// Line without its comment, `##` is an escaped `#`
+fn strip_comment(line: &str) -> &str {
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if c == '#' {
+            match chars.peek() {
+                Some((_, '#')) => {
+                    chars.next();
+                }
+                _ => return &line[..index],
+            }
+        }
+    }
+
+    line
+}
+
+/// Path of the sections the line is in, e.g. `input:touchpad`, empty at the top level
+pub fn section_path(text: &str, line: u32) -> String {
+    let mut sections = vec![];
+
+    for line in text.lines().take(line as usize) {
+        let line = strip_comment(line).trim();
+
+        if let Some(header) = line.strip_suffix('{') {
+            sections.push(header.trim().to_string());
+        } else if line.starts_with('}') {
+            sections.pop();
+        }
+    }
+
+    sections.join(":")
+}
+
+/// Full path of the option `name` set in the sections at `section`
+pub fn option_path(section: &str, name: &str) -> String {
+    match section {
+        "" => name.to_string(),
+        section => format!("{section}:{name}"),
+    }
+}
+
+/// Schema of the options directly in the sections at `section`, device sections included
+pub fn section_options(section: &str) -> impl Iterator<Item = &'static OptionSchema> {
+    let prefix = match section.strip_prefix("device:") {
+        Some(name) if !name.contains(':') => "device:*:".to_string(),
+        _ => option_path(section, ""),
+    };
+
+    Schema::get().options().iter().filter(move |option| {
+        option
+            .path
+            .strip_prefix(prefix.as_str())
+            .is_some_and(|name| !name.contains(':'))
+    })
+}
+
+/// Word under the cursor and the byte offset of its start in the line
+pub fn word_at(text: &str, position: Position) -> Option<(usize, &str)> {
+    let line = position::line(text, position.line);
+    let cursor = position::offset(line, Position::new(0, position.character));
+
+    let start = line[..cursor]
+        .char_indices()
+        .rfind(|(_, c)| !is_word(*c))
+        .map_or(0, |(index, c)| index + c.len_utf8());
+    let end = line[cursor..]
+        .find(|c| !is_word(c))
+        .map_or(line.len(), |index| cursor + index);
+
+    (start < end).then(|| (start, &line[start..end]))
+}
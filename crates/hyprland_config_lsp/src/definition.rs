@@ -0,0 +1,72 @@
+//! Go-to-definition of `$variables` and of the files of `source` lines.
+
+use hyprland_config::{cst::Node, source::expand_path, Config, ConfigElement};
+use lsp_types::{Location, Position, Range, Url};
+
+use crate::{
+    context::word_at,
+    position::{self, range},
+};
+
+/// Where the variable or the sourced file under the cursor is defined. Variables go to
+/// the last definition before the cursor, or the first one if they are used before it
+pub fn definition(text: &str, position: Position, uri: &Url) -> Option<Location> {
+    let (_, word) = word_at(text, position)?;
+
+    match word.strip_prefix('$') {
+        Some(name) => variable(text, position, name).map(|range| Location::new(uri.clone(), range)),
+        None => source(text, position.line, uri),
+    }
+}
+
+/// Definition of `$name` that applies at `cursor`: the last one before it, or the first one
+/// if the variable is used before it
+pub(crate) fn variable_definition<'a>(
+    config: &'a Config,
+    cursor: usize,
+    name: &str,
+) -> Option<&'a Node> {
+    let definitions = config
+        .nodes()
+        .iter()
+        .filter(|node| matches!(&node.element, ConfigElement::Variable(variable) if variable.name == name))
+        .collect::<Vec<_>>();
+
+    definitions
+        .iter()
+        .rev()
+        .find(|node| node.span.start <= cursor)
+        .or(definitions.first())
+        .copied()
+}
+
+fn variable(text: &str, position: Position, name: &str) -> Option<Range> {
+    let config = Config::parse_recovering(text);
+    let node = variable_definition(&config, position::offset(text, position), name)?;
+
+    let mut span = node.span;
+    span.start = node.element_start();
+    span.end = span.start + node.element.to_string().len();
+
+    Some(range(text, span))
+}
+
+fn source(text: &str, line: u32, uri: &Url) -> Option<Location> {
+    let (name, value) = position::line(text, line).split_once('=')?;
+
+    if name.trim() != "source" {
+        return None;
+    }
+
+    // The path can use the variables defined above the line
+    let start = position::offset(text, Position::new(line, 0));
+    let variables = Config::parse_recovering(&text[..start]).resolve().variables;
+
+    let document = uri.to_file_path().ok()?;
+    let path = expand_path(value, &variables, document.parent()?).ok()?;
+
+    Some(Location::new(
+        Url::from_file_path(path).ok()?,
+        Range::default(),
+    ))
+}
@@ -0,0 +1,36 @@
+//! Problems found by [Config::validate], as LSP diagnostics.
+
+use hyprland_config::{
+    diagnostic::{DiagnosticCode, Severity},
+    Config,
+};
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+use crate::position::range;
+
+/// Problems in the document. Only the document itself is checked, so when it has `source` lines
+/// undefined variables aren't reported, they may be defined in the sourced files
+pub fn diagnostics(text: &str) -> Vec<Diagnostic> {
+    let config = Config::parse_recovering(text);
+    let sources = config.keywords().any(|keyword| keyword.name == "source");
+
+    config
+        .validate()
+        .into_iter()
+        .filter(|diagnostic| !(sources && diagnostic.code == DiagnosticCode::UndefinedVariable))
+        .map(|diagnostic| Diagnostic {
+            range: range(text, diagnostic.span.bytes),
+            severity: Some(match diagnostic.severity {
+                Severity::Warning => DiagnosticSeverity::WARNING,
+                Severity::Error => DiagnosticSeverity::ERROR,
+            }),
+            code: Some(NumberOrString::String(diagnostic.code.to_string())),
+            source: Some("hyprland-config".to_string()),
+            message: match &diagnostic.help {
+                Some(help) => format!("{}\n{help}", diagnostic.message),
+                None => diagnostic.message,
+            },
+            ..Default::default()
+        })
+        .collect()
+}
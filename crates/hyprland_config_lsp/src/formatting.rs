@@ -0,0 +1,25 @@
+//! Document formatting with [Config::format].
+
+use hyprland_config::{format::FormatOptions, Config};
+use lsp_types::{FormattingOptions, Position, Range, TextEdit};
+
+use crate::position::position;
+
+/// Edit replacing the document with its formatted text, `None` if the document doesn't parse
+pub fn format(text: &str, options: &FormattingOptions) -> Option<Vec<TextEdit>> {
+    let config = text.parse::<Config>().ok()?;
+    let formatted = config
+        .format(&FormatOptions {
+            indent: options.tab_size as usize,
+            ..Default::default()
+        })
+        .to_string();
+
+    Some(match formatted == text {
+        true => vec![],
+        false => vec![TextEdit::new(
+            Range::new(Position::new(0, 0), position(text, text.len())),
+            formatted,
+        )],
+    })
+}
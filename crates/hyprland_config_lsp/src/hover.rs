@@ -0,0 +1,83 @@
+//! Documentation of options from the [Schema] and values of `$variables`.
+
+use hyprland_config::{
+    schema::{OptionSchema, Schema},
+    Config,
+};
+use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position, Range};
+
+use crate::{
+    context::{option_path, section_path, word_at},
+    definition::variable_definition,
+};
+
+pub fn hover(text: &str, position: Position) -> Option<Hover> {
+    let (start, word) = word_at(text, position)?;
+    let line = crate::position::line(text, position.line);
+
+    let contents = match word.strip_prefix('$') {
+        // The value at the definition that applies here, variables are substituted in reading order
+        Some(name) => {
+            let config = Config::parse_recovering(text);
+            let cursor = crate::position::offset(text, position);
+            let node = variable_definition(&config, cursor, name)?;
+            let resolved = Config::parse_recovering(&text[..node.span.end]).resolve();
+
+            format!("```\n${name} = {}\n```", resolved.variables.get(name)?)
+        }
+        // Only the name of an option, not words in its value
+        None if !line[..start].contains('=') => {
+            let path = option_path(&section_path(text, position.line), word);
+            documentation(Schema::get().option(&path)?)
+        }
+        None => return None,
+    };
+
+    let start = Position::new(position.line, line[..start].encode_utf16().count() as u32);
+    let end = Position::new(
+        start.line,
+        start.character + word.encode_utf16().count() as u32,
+    );
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: contents,
+        }),
+        range: Some(Range::new(start, end)),
+    })
+}
+
+/// Markdown documentation of an option: path, type, description, default, range and values
+pub fn documentation(option: &OptionSchema) -> String {
+    let mut lines = vec![
+        format!("**{}** `{}`", option.path, option.ty),
+        option.description.clone(),
+    ];
+
+    if let Some(default) = &option.default {
+        lines.push(format!("Default: `{default}`"));
+    }
+
+    match (option.min, option.max) {
+        (Some(min), Some(max)) => lines.push(format!("Range: {min} to {max}")),
+        (Some(min), None) => lines.push(format!("Range: at least {min}")),
+        (None, Some(max)) => lines.push(format!("Range: at most {max}")),
+        (None, None) => {}
+    }
+
+    if !option.values.is_empty() {
+        lines.push(format!("Values: `{}`", option.values.join("`, `")));
+    }
+
+    if let Some(deprecated) = &option.deprecated {
+        lines.push(match deprecated.replaced_by {
+            Some(replacement) => {
+                format!("**Deprecated**, use `{replacement}`. {}", deprecated.note)
+            }
+            None => format!("**Deprecated**. {}", deprecated.note),
+        });
+    }
+
+    lines.join("\n\n")
+}
@@ -0,0 +1,11 @@
+//! Language features for `hyprland.conf` built on [hyprland_config]. Every feature is a function
+//! of the document text and a position, [server] wires them up to the LSP requests.
+
+pub mod completion;
+pub mod context;
+pub mod definition;
+pub mod diagnostics;
+pub mod formatting;
+pub mod hover;
+pub mod position;
+pub mod server;
@@ -0,0 +1,5 @@
+//! `hyprland-config-lsp`, a language server for `hyprland.conf` that talks LSP over stdio
+
+fn main() -> eyre::Result<()> {
+    hyprland_config_lsp::server::run()
+}
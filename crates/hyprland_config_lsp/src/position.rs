@@ -0,0 +1,49 @@
+//! Conversions between byte offsets and LSP positions, whose columns count UTF-16 code units.
+
+use hyprland_config::cst::Span;
+use lsp_types::{Position, Range};
+
+/// Byte offset of `position` in `text`. Columns past the end of the line are clamped to it
+pub fn offset(text: &str, position: Position) -> usize {
+    let mut offset = 0;
+
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        if index == position.line as usize {
+            let mut units = 0;
+
+            for (byte, c) in line.char_indices() {
+                if units >= position.character as usize || c == '\n' || c == '\r' {
+                    return offset + byte;
+                }
+
+                units += c.len_utf16();
+            }
+
+            return offset + line.len();
+        }
+
+        offset += line.len();
+    }
+
+    text.len()
+}
+
+/// Position of the byte `offset` in `text`
+pub fn position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+pub fn range(text: &str, span: Span) -> Range {
+    Range::new(position(text, span.start), position(text, span.end))
+}
+
+/// Text of line `line`, without the line ending
+pub fn line(text: &str, line: u32) -> &str {
+    text.lines().nth(line as usize).unwrap_or_default()
+}
@@ -0,0 +1,197 @@
+//! The LSP server: keeps the open documents and answers requests with the language features.
+
+use std::collections::HashMap;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as LspNotification, PublishDiagnostics,
+    },
+    request::{Completion, Formatting, GotoDefinition, HoverRequest, Request as LspRequest},
+    CompletionOptions, CompletionResponse, GotoDefinitionResponse, HoverProviderCapability, OneOf,
+    PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
+    Url,
+};
+
+use crate::{completion, definition, diagnostics, formatting, hover};
+
+/// Serves LSP over stdin and stdout until the client shuts the server down
+pub fn run() -> eyre::Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(["$", ":", ",", "="].map(ToString::to_string).to_vec()),
+            ..Default::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    };
+
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+    Server::default().serve(&connection)?;
+    io_threads.join()?;
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct Server {
+    /// Text of the open documents
+    documents: HashMap<Url, String>,
+}
+
+impl Server {
+    fn serve(&mut self, connection: &Connection) -> eyre::Result<()> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+
+                    let response = self.request(request);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    self.notification(connection, notification)?
+                }
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn document(&self, uri: &Url) -> &str {
+        self.documents.get(uri).map_or("", String::as_str)
+    }
+
+    fn request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+
+        let result = match request.method.as_str() {
+            Completion::METHOD => self.handle::<Completion>(request, |server, params| {
+                let position = params.text_document_position;
+                let text = server.document(&position.text_document.uri);
+
+                Some(CompletionResponse::Array(completion::completions(
+                    text,
+                    position.position,
+                )))
+            }),
+            HoverRequest::METHOD => self.handle::<HoverRequest>(request, |server, params| {
+                let position = params.text_document_position_params;
+                let text = server.document(&position.text_document.uri);
+
+                hover::hover(text, position.position)
+            }),
+            GotoDefinition::METHOD => self.handle::<GotoDefinition>(request, |server, params| {
+                let position = params.text_document_position_params;
+                let uri = &position.text_document.uri;
+
+                definition::definition(server.document(uri), position.position, uri)
+                    .map(GotoDefinitionResponse::Scalar)
+            }),
+            Formatting::METHOD => self.handle::<Formatting>(request, |server, params| {
+                let text = server.document(&params.text_document.uri);
+
+                formatting::format(text, &params.options)
+            }),
+            method => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("Unsupported request `{method}`"),
+                )
+            }
+        };
+
+        match result {
+            Ok(result) => Response::new_ok(id, result),
+            Err(err) => Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()),
+        }
+    }
+
+    fn handle<R: LspRequest>(
+        &self,
+        request: Request,
+        handler: impl FnOnce(&Self, R::Params) -> R::Result,
+    ) -> eyre::Result<serde_json::Value> {
+        let params = serde_json::from_value(request.params)?;
+
+        Ok(serde_json::to_value(handler(self, params))?)
+    }
+
+    /// Updates the documents and publishes the diagnostics of the changed one. Notifications
+    /// that can't be read are logged to stderr and skipped, they don't stop the server
+    fn notification(
+        &mut self,
+        connection: &Connection,
+        notification: Notification,
+    ) -> eyre::Result<()> {
+        let method = notification.method.clone();
+        let uri = match self.update(notification) {
+            Ok(Some(uri)) => uri,
+            Ok(None) => return Ok(()),
+            Err(err) => {
+                eprintln!("Skipped `{method}` notification: {err}");
+                return Ok(());
+            }
+        };
+
+        let params = PublishDiagnosticsParams {
+            diagnostics: diagnostics::diagnostics(self.document(&uri)),
+            uri,
+            version: None,
+        };
+
+        connection
+            .sender
+            .send(Message::Notification(Notification::new(
+                PublishDiagnostics::METHOD.to_string(),
+                params,
+            )))?;
+
+        Ok(())
+    }
+
+    /// Applies a document notification, returns the document it changed
+    fn update(&mut self, notification: Notification) -> eyre::Result<Option<Url>> {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: <DidOpenTextDocument as LspNotification>::Params =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+
+                self.documents.insert(document.uri.clone(), document.text);
+                document.uri
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: <DidChangeTextDocument as LspNotification>::Params =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+
+                // Documents are synced in full, so the last change has the whole text
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(uri.clone(), change.text);
+                }
+                uri
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: <DidCloseTextDocument as LspNotification>::Params =
+                    serde_json::from_value(notification.params)?;
+
+                self.documents.remove(&params.text_document.uri);
+                params.text_document.uri
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(uri))
+    }
+}
@@ -0,0 +1,66 @@
+use hyprland_config_lsp::completion::completions;
+use lsp_types::{CompletionItem, CompletionItemKind, Position};
+
+const CONFIG: &str = r#"$mainMod = SUPER
+$term = kitty
+
+general {
+    gaps_in = 5
+    layout = 
+}
+
+input {
+    touchpad {
+        
+    }
+}
+
+bind = $mainMod, Q, 
+"#;
+
+fn labels(items: &[CompletionItem], kind: CompletionItemKind) -> Vec<&str> {
+    items
+        .iter()
+        .filter(|item| item.kind == Some(kind))
+        .map(|item| item.label.as_str())
+        .collect()
+}
+
+#[test]
+fn test_complete_names() {
+    let top = completions(CONFIG, Position::new(2, 0));
+    assert!(labels(&top, CompletionItemKind::KEYWORD).contains(&"exec-once"));
+    assert!(labels(&top, CompletionItemKind::MODULE).contains(&"general"));
+    assert!(!labels(&top, CompletionItemKind::MODULE).contains(&"touchpad"));
+
+    let general = completions(CONFIG, Position::new(4, 6));
+    let gaps_out = general
+        .iter()
+        .find(|item| item.label == "gaps_out")
+        .unwrap();
+    assert_eq!(gaps_out.kind, Some(CompletionItemKind::PROPERTY));
+    assert_eq!(gaps_out.detail.as_deref(), Some("int"));
+    assert!(labels(&general, CompletionItemKind::KEYWORD).is_empty());
+
+    let touchpad = completions(CONFIG, Position::new(10, 8));
+    assert!(labels(&touchpad, CompletionItemKind::PROPERTY).contains(&"natural_scroll"));
+    assert!(!labels(&touchpad, CompletionItemKind::PROPERTY).contains(&"kb_layout"));
+}
+
+#[test]
+fn test_complete_values() {
+    let layout = completions(CONFIG, Position::new(5, 13));
+    assert_eq!(
+        labels(&layout, CompletionItemKind::VALUE),
+        ["dwindle", "master"]
+    );
+
+    let dispatchers = completions(CONFIG, Position::new(14, 20));
+    assert!(labels(&dispatchers, CompletionItemKind::FUNCTION).contains(&"killactive"));
+
+    let variables = completions(CONFIG, Position::new(14, 8));
+    assert_eq!(
+        labels(&variables, CompletionItemKind::VARIABLE),
+        ["$mainMod", "$term"]
+    );
+}
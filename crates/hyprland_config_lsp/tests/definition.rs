@@ -0,0 +1,79 @@
+use hyprland_config_lsp::definition::definition;
+use lsp_types::{Location, Position, Range, Url};
+
+const CONFIG: &str = r#"$gaps = 5
+general {
+    gaps_in = $gaps # inner
+}
+$gaps = 10
+general:gaps_out = $gaps
+source = ./colors.conf
+$themes = /usr/share/hypr
+source = $themes/dark.conf
+bind = SUPER, Q, exec, $terminal
+$terminal = kitty
+"#;
+
+fn uri() -> Url {
+    Url::parse("file:///home/user/.config/hypr/hyprland.conf").unwrap()
+}
+
+fn definition_at(line: u32, character: u32) -> Option<Location> {
+    definition(CONFIG, Position::new(line, character), &uri())
+}
+
+#[test]
+fn test_definition_variables() {
+    let first = definition_at(2, 16).unwrap();
+    assert_eq!(first.uri, uri());
+    assert_eq!(
+        first.range,
+        Range::new(Position::new(0, 0), Position::new(0, 9))
+    );
+
+    // The last definition before the cursor
+    let second = definition_at(5, 22).unwrap();
+    assert_eq!(
+        second.range,
+        Range::new(Position::new(4, 0), Position::new(4, 10))
+    );
+
+    // Used before it is defined
+    let terminal = definition_at(9, 25).unwrap();
+    assert_eq!(
+        terminal.range,
+        Range::new(Position::new(10, 0), Position::new(10, 17))
+    );
+
+    assert_eq!(
+        definition(
+            CONFIG.replace("$terminal = kitty\n", "").as_str(),
+            Position::new(9, 25),
+            &uri()
+        ),
+        None
+    );
+    assert_eq!(definition_at(2, 6), None);
+}
+
+#[test]
+fn test_definition_source() {
+    let relative = definition_at(6, 12).unwrap();
+    assert_eq!(
+        relative.uri.as_str(),
+        "file:///home/user/.config/hypr/colors.conf"
+    );
+    assert_eq!(relative.range, Range::default());
+
+    let with_variable = definition_at(8, 20).unwrap();
+    assert_eq!(
+        with_variable.uri.as_str(),
+        "file:///usr/share/hypr/dark.conf"
+    );
+
+    // The variable of the path goes to its definition
+    assert_eq!(
+        definition_at(8, 12).unwrap().range.start,
+        Position::new(7, 0)
+    );
+}
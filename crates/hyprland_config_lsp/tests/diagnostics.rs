@@ -0,0 +1,52 @@
+use hyprland_config_lsp::{diagnostics::diagnostics, formatting::format};
+use lsp_types::{DiagnosticSeverity, FormattingOptions, NumberOrString, Position, Range};
+
+#[test]
+fn test_diagnostics() {
+    let text = "general {\n    gap_in = 5\n    gaps_out = lots\n}\nbind = $mod, Q, exec, kitty\n";
+    let found = diagnostics(text);
+
+    let codes = found
+        .iter()
+        .map(|diagnostic| match &diagnostic.code {
+            Some(NumberOrString::String(code)) => code.as_str(),
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        ["unknown-option", "type-mismatch", "undefined-variable"]
+    );
+
+    assert_eq!(
+        found[0].range,
+        Range::new(Position::new(1, 4), Position::new(1, 10))
+    );
+    assert_eq!(found[0].severity, Some(DiagnosticSeverity::ERROR));
+    assert!(found[0]
+        .message
+        .ends_with("Did you mean `general:gaps_in`?"));
+
+    let sourced = format!("source = ./vars.conf\n{text}");
+    assert_eq!(diagnostics(&sourced).len(), 2);
+}
+
+#[test]
+fn test_formatting() {
+    let options = FormattingOptions {
+        tab_size: 2,
+        insert_spaces: true,
+        ..Default::default()
+    };
+
+    let edits = format("general {\ngaps_in=5\n}", &options).unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].new_text, "general {\n  gaps_in = 5\n}\n");
+    assert_eq!(edits[0].range.end, Position::new(2, 1));
+
+    assert_eq!(
+        format("general {\n  gaps_in = 5\n}\n", &options),
+        Some(vec![])
+    );
+    assert_eq!(format("general {\n", &options), None);
+}
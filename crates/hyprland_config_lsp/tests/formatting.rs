@@ -0,0 +1,34 @@
+use hyprland_config_lsp::formatting::format;
+use lsp_types::{FormattingOptions, Position, Range, TextEdit};
+
+fn options(tab_size: u32) -> FormattingOptions {
+    FormattingOptions {
+        tab_size,
+        insert_spaces: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_formatting() {
+    let text = "general{\n  gaps_in=5\n}\n\n\nexec-once=waybar";
+
+    assert_eq!(
+        format(text, &options(4)).unwrap(),
+        [TextEdit::new(
+            Range::new(Position::new(0, 0), Position::new(5, 16)),
+            "general {\n    gaps_in = 5\n}\n\nexec-once = waybar\n".to_string()
+        )]
+    );
+    assert_eq!(
+        format(text, &options(2)).unwrap()[0].new_text,
+        "general {\n  gaps_in = 5\n}\n\nexec-once = waybar\n"
+    );
+
+    // Formatted documents need no edit, and broken ones aren't formatted
+    assert_eq!(
+        format("general {\n    gaps_in = 5\n}\n", &options(4)),
+        Some(vec![])
+    );
+    assert_eq!(format("general {\n    gaps_in = 5\n", &options(4)), None);
+}
@@ -0,0 +1,42 @@
+use hyprland_config_lsp::hover::hover;
+use lsp_types::{HoverContents, Position, Range};
+
+const CONFIG: &str = r#"$gaps = 5
+general {
+    gaps_in = $gaps # inner
+}
+$gaps = 10
+general:gaps_out = $gaps
+source = ./colors.conf
+"#;
+
+fn hover_text(position: Position) -> Option<String> {
+    hover(CONFIG, position).map(|hover| match hover.contents {
+        HoverContents::Markup(markup) => markup.value,
+        _ => unreachable!(),
+    })
+}
+
+#[test]
+fn test_hover() {
+    let gaps_in = hover_text(Position::new(2, 6)).unwrap();
+    assert!(gaps_in.starts_with("**general:gaps_in** `int`\n\nGaps between windows"));
+    assert!(gaps_in.contains("Default: `5`"));
+
+    let gaps_out = hover_text(Position::new(5, 10)).unwrap();
+    assert!(gaps_out.starts_with("**general:gaps_out**"));
+
+    assert_eq!(
+        hover_text(Position::new(2, 16)).unwrap(),
+        "```\n$gaps = 5\n```"
+    );
+    assert_eq!(
+        hover_text(Position::new(5, 22)).unwrap(),
+        "```\n$gaps = 10\n```"
+    );
+    assert_eq!(hover_text(Position::new(2, 22)), None);
+    assert_eq!(
+        hover(CONFIG, Position::new(2, 6)).unwrap().range,
+        Some(Range::new(Position::new(2, 4), Position::new(2, 11)))
+    );
+}
@@ -0,0 +1,51 @@
+use hyprland::{
+    dispatch::{Dispatch, DispatchType, WindowIdentifier},
+    shared::{Address, HResult},
+};
+
+/// Actions that can be performed on a single client, shared by the GUI and the TUI
+#[derive(Debug, Copy, Clone, Eq, PartialEq, enum_iterator::Sequence)]
+pub(crate) enum ClientAction {
+    Focus,
+    ToggleFloating,
+    Pin,
+    Close,
+}
+
+impl std::fmt::Display for ClientAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ClientAction::Focus => "Focus",
+                ClientAction::ToggleFloating => "Toggle Floating",
+                ClientAction::Pin => "Pin",
+                ClientAction::Close => "Close",
+            }
+        )
+    }
+}
+
+impl ClientAction {
+    /// Key used to trigger the action in the TUI
+    pub fn key(&self) -> char {
+        match self {
+            ClientAction::Focus => 'f',
+            ClientAction::ToggleFloating => 't',
+            ClientAction::Pin => 'p',
+            ClientAction::Close => 'c',
+        }
+    }
+
+    pub fn execute(&self, address: &Address) -> HResult<()> {
+        let window = WindowIdentifier::Address(address.clone());
+
+        Dispatch::call(match self {
+            ClientAction::Focus => DispatchType::FocusWindow(window),
+            ClientAction::ToggleFloating => DispatchType::ToggleFloating(Some(window)),
+            ClientAction::Pin => DispatchType::Pin(Some(window)),
+            ClientAction::Close => DispatchType::CloseWindow(window),
+        })
+    }
+}
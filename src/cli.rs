@@ -14,6 +14,7 @@ use socket2::{Domain, Socket, Type};
 pub(crate) const USAGE: &str = r"
 usage hyprr [(opt)flags] [command] [(opt) args] # same as hyprctl
       hyppr -g (--gui) # gui application
+      hyppr --tui # terminal ui application
 
 commands:
     monitors
@@ -36,9 +37,12 @@ commands:
     switchxkblayout
     seterror
     setprop
+    config -> work with hyprland.conf, see `hyprr config --help`
+    rules -> test window and layer rules, see `hyprr rules --help`
 
 flags:
     -g, --gui -> run the gui application
+    --tui -> run the terminal ui application
     -j -> output in JSON
     --batch -> execute a batch of commands, separated by ';'
 ";
@@ -79,9 +83,16 @@ pub fn execute(args: &[String]) -> eyre::Result<()> {
     }
 
     let full_request = format!("{full_args}/{}", full_request.trim_end());
+    // hyprr's own commands are matched exactly, arguments like `~/.config/x` are for hyprctl
+    let command = args
+        .iter()
+        .find(|arg| !arg.starts_with('-'))
+        .map(String::as_str);
 
     kiam::when! {
         full_request.contains("/--batch") => batch_request(&full_request),
+        command == Some("config") => config_request(args),
+        command == Some("rules") => rules_request(args),
         [
             "/monitors",
             "/clients",
@@ -138,6 +149,13 @@ fn request(arg: &str, min_args: usize) -> eyre::Result<()> {
         ));
     }
 
+    println!("{}", send_request(arg)?);
+
+    Ok(())
+}
+
+/// Sends `arg` to the Hyprland socket and returns the reply
+pub(crate) fn send_request(arg: &str) -> eyre::Result<String> {
     let Ok(server_socket) = Socket::new(Domain::UNIX, Type::STREAM, None) else {
         return Err(eyre::eyre!("Couldn't open a socket (1)"));
     };
@@ -179,9 +197,7 @@ fn request(arg: &str, min_args: usize) -> eyre::Result<()> {
 
     close(server_socket.as_raw_fd())?;
 
-    println!("{reply}");
-
-    Ok(())
+    Ok(reply)
 }
 
 fn output_request(args: &[String]) -> eyre::Result<()> {
@@ -254,6 +270,24 @@ fn keyword_request(args: &[String]) -> eyre::Result<()> {
     request(&rq, 0)
 }
 
+fn config_request(args: &[String]) -> eyre::Result<()> {
+    let position = args
+        .iter()
+        .position(|arg| arg == "config")
+        .unwrap_or_default();
+
+    crate::config::execute(&args[position + 1..])
+}
+
+fn rules_request(args: &[String]) -> eyre::Result<()> {
+    let position = args
+        .iter()
+        .position(|arg| arg == "rules")
+        .unwrap_or_default();
+
+    crate::rules::execute(&args[position + 1..])
+}
+
 fn hyprpaper_request(args: &[String]) -> eyre::Result<()> {
     if args.len() < 4 {
         return Err(eyre::eyre!(
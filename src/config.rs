@@ -0,0 +1,871 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use color_eyre::eyre;
+use hyprland::{
+    data::{Monitor, Monitors, Transforms},
+    prelude::*,
+};
+use hyprland_config::{
+    animation::{Animation, Bezier},
+    apply::LiveOption,
+    bind::{Bind, BindFlags, Key, Submap},
+    diagnostic::Severity,
+    document::Document,
+    dump::{DumpOptions, LiveState},
+    format::FormatOptions,
+    lint::{is_executable, LintLevel, LintLevels},
+    monitor::{compare_monitors, MonitorRule, MonitorState, Transform},
+    schema::{OptionSchema, Schema},
+    source::ConfigTree,
+    variable::{ModifierSet, VariableValue},
+    Config,
+};
+use itertools::Itertools;
+
+use crate::cli::send_request;
+
+/// Rounds of `lint --fix`, each applies the fixes that don't overlap
+const MAX_FIX_ROUNDS: usize = 10;
+
+pub(crate) const USAGE: &str = r"
+usage hyprr config [command] [(opt) args]
+
+commands:
+    monitors [path] -> show which monitor rule applies to each connected monitor
+                       and where the monitor diverges from it
+    sources [path] -> show the files sourced by the config
+    check [path] -> validate the config and everything it sources, showing every problem
+                    with the line it is on
+    lint [--fix] [--allow|--warn|--deny <lint>]... [path] -> report unused variables, repeated
+                    binds, missing programs, duplicate sections and other likely mistakes,
+                    with --fix rewrite the lines that can be fixed automatically
+    diff [--json] <old> <new> -> compare two configs by the options, binds and rules they set,
+                    ignoring formatting and comments
+    fmt [--check] [--align-binds] [path] -> format the config and everything it sources,
+                    with --check only list the files that aren't formatted and fail if any
+    get <option> [path] -> print the value the config sets the option to, e.g. `general:gaps_in`
+    set <option> <value> [path] -> set the option in the config file, editing its line in place
+                    or adding it to its section
+    migrate [--dry-run] [path] -> rewrite deprecated options and `windowrule` lines of the config
+                    and everything it sources to their current form, with --dry-run only list them
+    apply <path> -> set the options of the config that differ from the running Hyprland,
+                    in one batch, and list what can only be applied by a reload or restart
+    dump [--non-default] -> print the options, monitors and binds of the running Hyprland
+                    as a config, with --non-default only options that differ from their defaults
+    export [--to json|toml|yaml|nix] [path] -> print the config as JSON (default), TOML, YAML
+                    or home-manager settings, known options typed by the schema, comments are dropped
+    import [--from json|toml|yaml|nix] <file> -> print the config of an exported file, the format
+                    is taken from the file extension unless given
+    options [filter] -> list the known options with their types, defaults and
+                        descriptions, optionally only those whose path contains filter
+";
+
+/// `hyprr config ...`, `args` start after `config`
+pub fn execute(args: &[String]) -> eyre::Result<()> {
+    let Some(command) = args.first() else {
+        return Err(eyre::eyre!("{USAGE}"));
+    };
+
+    match command.as_str() {
+        "monitors" => monitors_request(&args[1..]),
+        "sources" => sources_request(&args[1..]),
+        "check" => check_request(&args[1..]),
+        "lint" => lint_request(&args[1..]),
+        "diff" => diff_request(&args[1..]),
+        "fmt" => fmt_request(&args[1..]),
+        "get" => get_request(&args[1..]),
+        "set" => set_request(&args[1..]),
+        "migrate" => migrate_request(&args[1..]),
+        "apply" => apply_request(&args[1..]),
+        "dump" => dump_request(&args[1..]),
+        "export" => export_request(&args[1..]),
+        "import" => import_request(&args[1..]),
+        "options" => options_request(&args[1..]),
+        "--help" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        _ => Err(eyre::eyre!("{USAGE}")),
+    }
+}
+
+/// `$XDG_CONFIG_HOME/hypr/hyprland.conf`, falling back to `~/.config/hypr/hyprland.conf`
+pub fn default_config_path() -> eyre::Result<PathBuf> {
+    let config_dir = match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => match std::env::var("HOME") {
+            Ok(home) => PathBuf::from(home).join(".config"),
+            Err(_) => return Err(eyre::eyre!("Neither XDG_CONFIG_HOME nor HOME are set")),
+        },
+    };
+
+    Ok(config_dir.join("hypr").join("hyprland.conf"))
+}
+
+/// Config at the given path, or at [default_config_path]
+pub(crate) fn load_tree(path: Option<&String>) -> eyre::Result<ConfigTree> {
+    let path = config_path(path)?;
+
+    ConfigTree::load(&path).map_err(|err| eyre::eyre!("Couldn't load {}: {err:#}", path.display()))
+}
+
+/// Path given on the command line, or [default_config_path]
+fn config_path(path: Option<&String>) -> eyre::Result<PathBuf> {
+    match path {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => default_config_path(),
+    }
+}
+
+/// Config at the given path, or at [default_config_path], with all sourced files merged in
+/// and variables substituted
+pub(crate) fn load_config(path: Option<&String>) -> eyre::Result<Config> {
+    load_tree(path).map(|tree| tree.resolve().config)
+}
+
+/// How often [ConfigCache] checks whether the files of the config changed
+const STALE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Config at [default_config_path] as shown by the GUI and TUI tabs
+pub(crate) struct LoadedConfig {
+    pub config: Result<Config, String>,
+    pub beziers: Result<Vec<Bezier>, String>,
+    pub animations: Result<Vec<Animation>, String>,
+    /// Files the config was read from, with their modification times when they were read
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl LoadedConfig {
+    fn load() -> Self {
+        let tree = load_tree(None);
+        let paths = match &tree {
+            Ok(tree) => tree.files().iter().map(|file| file.path.clone()).collect(),
+            Err(_) => default_config_path().into_iter().collect_vec(),
+        };
+
+        let config = tree
+            .map(|tree| tree.resolve().config)
+            .map_err(|err| err.to_string());
+
+        Self {
+            beziers: match &config {
+                Ok(config) => config.beziers().map_err(|err| err.to_string()),
+                Err(err) => Err(err.clone()),
+            },
+            animations: match &config {
+                Ok(config) => config.animations().map_err(|err| err.to_string()),
+                Err(err) => Err(err.clone()),
+            },
+            config,
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let modified = modified(&path);
+                    (path, modified)
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether a file of the config was changed, created or removed since it was read
+    fn is_stale(&self) -> bool {
+        self.files
+            .iter()
+            .any(|(path, modified)| self::modified(path) != *modified)
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The config is loaded on first use and then kept, instead of being read again on every
+/// repaint. It's reloaded on [ConfigCache::reload] or when one of its files changes
+#[derive(Default)]
+pub(crate) struct ConfigCache {
+    loaded: Option<LoadedConfig>,
+    checked: Option<Instant>,
+}
+
+impl ConfigCache {
+    pub fn get(&mut self) -> &LoadedConfig {
+        let recent = self
+            .checked
+            .is_some_and(|checked| checked.elapsed() < STALE_CHECK_INTERVAL);
+
+        if !recent {
+            self.checked = Some(Instant::now());
+
+            if self.loaded.as_ref().is_some_and(LoadedConfig::is_stale) {
+                self.loaded = None;
+            }
+        }
+
+        self.loaded.get_or_insert_with(LoadedConfig::load)
+    }
+
+    pub fn reload(&mut self) {
+        self.loaded = None;
+    }
+}
+
+/// Known options grouped by section, e.g. `input:touchpad`, in schema order. Deprecated
+/// options are listed with their section. Only options whose path contains `filter` are included
+pub(crate) fn options_by_section(filter: &str) -> Vec<(String, Vec<&'static OptionSchema>)> {
+    let mut sections: Vec<(String, Vec<&'static OptionSchema>)> = vec![];
+
+    for option in Schema::get()
+        .options()
+        .iter()
+        .filter(|option| option.path.contains(filter))
+    {
+        let section = option
+            .path
+            .rsplit_once(':')
+            .map_or(String::new(), |(section, _)| section.to_string());
+
+        match sections.iter_mut().find(|(name, _)| *name == section) {
+            Some((_, options)) => options.push(option),
+            None => sections.push((section, vec![option])),
+        }
+    }
+
+    sections
+}
+
+/// Type, default, range, allowed values and deprecation of an option, in a single line
+pub(crate) fn option_summary(option: &OptionSchema) -> String {
+    let mut summary = option.ty.to_string();
+
+    if let Some(default) = &option.default {
+        summary += &format!(", default `{default}`");
+    }
+
+    match (option.min, option.max) {
+        (Some(min), Some(max)) => summary += &format!(", {min} to {max}"),
+        (Some(min), None) => summary += &format!(", at least {min}"),
+        (None, Some(max)) => summary += &format!(", at most {max}"),
+        (None, None) => {}
+    }
+
+    if !option.values.is_empty() {
+        summary += &format!(", one of {}", option.values.join(", "));
+    }
+
+    if let Some(deprecated) = &option.deprecated {
+        summary += ", deprecated";
+
+        if let Some(replaced_by) = deprecated.replaced_by {
+            summary += &format!(", use `{replaced_by}`");
+        }
+    }
+
+    summary
+}
+
+fn monitor_state(monitor: &Monitor) -> MonitorState {
+    MonitorState {
+        name: monitor.name.clone(),
+        description: monitor.description.clone(),
+        width: monitor.width.into(),
+        height: monitor.height.into(),
+        refresh_rate: monitor.refresh_rate,
+        x: monitor.x,
+        y: monitor.y,
+        scale: monitor.scale,
+        transform: match monitor.transform {
+            Transforms::Normal => Transform::Normal,
+            Transforms::Normal90 => Transform::Normal90,
+            Transforms::Normal180 => Transform::Normal180,
+            Transforms::Normal270 => Transform::Normal270,
+            Transforms::Flipped => Transform::Flipped,
+            Transforms::Flipped90 => Transform::Flipped90,
+            Transforms::Flipped180 => Transform::Flipped180,
+            Transforms::Flipped270 => Transform::Flipped270,
+        },
+    }
+}
+
+fn monitors_request(args: &[String]) -> eyre::Result<()> {
+    let config = load_config(args.first())?;
+    let rules = config.monitor_rules()?;
+    let monitors = Monitors::get()?.iter().map(monitor_state).collect_vec();
+
+    for report in compare_monitors(&rules, &monitors) {
+        println!(
+            "Monitor {} ({}):",
+            report.monitor.name, report.monitor.description
+        );
+
+        match report.rule {
+            Some(rule) => println!("    rule: {rule}"),
+            None => println!("    no matching rule"),
+        }
+
+        match report.divergences.is_empty() {
+            true => println!("    matches the rule"),
+            false => report
+                .divergences
+                .iter()
+                .for_each(|divergence| println!("    {divergence}")),
+        }
+    }
+
+    Ok(())
+}
+
+fn sources_request(args: &[String]) -> eyre::Result<()> {
+    let tree = load_tree(args.first())?;
+
+    fn print_file(tree: &ConfigTree, file: usize, depth: usize) {
+        println!(
+            "{}{}",
+            "    ".repeat(depth),
+            tree.files()[file].path.display()
+        );
+
+        tree.includes()
+            .iter()
+            .filter(|include| include.file == file)
+            .sorted_by_key(|include| include.node)
+            .for_each(|include| print_file(tree, include.target, depth + 1));
+    }
+
+    print_file(&tree, 0, 0);
+
+    Ok(())
+}
+
+fn options_request(args: &[String]) -> eyre::Result<()> {
+    let filter = args.first().map_or("", String::as_str);
+
+    for (section, options) in options_by_section(filter) {
+        println!("{section}:");
+
+        for option in options {
+            let name = option.path.rsplit(':').next().unwrap_or(&option.path);
+
+            println!("    {name} ({})", option_summary(option));
+            println!("        {}", option.description);
+        }
+    }
+
+    Ok(())
+}
+
+fn get_request(args: &[String]) -> eyre::Result<()> {
+    let [option, path @ ..] = args else {
+        return Err(eyre::eyre!("Expected an option\n{USAGE}"));
+    };
+
+    let path = config_path(path.first())?;
+    let config = Config::from_path(&path)
+        .map_err(|err| eyre::eyre!("Couldn't load {}: {err:#}", path.display()))?;
+
+    match config.value(option) {
+        Some(value) => {
+            println!("{value}");
+            Ok(())
+        }
+        None => Err(eyre::eyre!("{option} isn't set in {}", path.display())),
+    }
+}
+
+fn set_request(args: &[String]) -> eyre::Result<()> {
+    let [option, value, path @ ..] = args else {
+        return Err(eyre::eyre!("Expected an option and a value\n{USAGE}"));
+    };
+
+    if let Some(schema) = Schema::get().option(option) {
+        if !value.starts_with('$') {
+            schema
+                .check(value)
+                .map_err(|err| eyre::eyre!("Invalid value for {option}: {err}"))?;
+        }
+    }
+
+    let path = config_path(path.first())?;
+    let mut config = Config::from_path(&path)
+        .map_err(|err| eyre::eyre!("Couldn't load {}: {err:#}", path.display()))?;
+
+    // Written as given, parsing would rewrite literals like `0.50`
+    config.set(option, VariableValue::String(value.to_string()));
+    std::fs::write(&path, config.to_string())?;
+
+    Ok(())
+}
+
+fn check_request(args: &[String]) -> eyre::Result<()> {
+    let path = config_path(args.first())?;
+    let tree = ConfigTree::load_recovering(&path)
+        .map_err(|err| eyre::eyre!("Couldn't load {}: {err:#}", path.display()))?;
+    let diagnostics = tree.validate();
+
+    for diagnostic in &diagnostics {
+        let file = &tree.files()[diagnostic.file];
+
+        println!(
+            "{}",
+            diagnostic.render(&file.config.to_string(), &file.path.display().to_string())
+        );
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+
+    match errors {
+        0 => {
+            println!("{} checked, {warnings} warning(s)", path.display());
+            Ok(())
+        }
+        _ => Err(eyre::eyre!(
+            "{} has {errors} error(s) and {warnings} warning(s)",
+            path.display()
+        )),
+    }
+}
+
+fn lint_request(args: &[String]) -> eyre::Result<()> {
+    let mut levels = LintLevels::default();
+    let mut fix = false;
+    let mut path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let level = match arg.as_str() {
+            "--fix" => {
+                fix = true;
+                continue;
+            }
+            "--allow" => LintLevel::Allow,
+            "--warn" => LintLevel::Warn,
+            "--deny" => LintLevel::Deny,
+            flag if flag.starts_with("--") => {
+                return Err(eyre::eyre!("Unknown flag `{flag}`\n{USAGE}"))
+            }
+            _ => {
+                path = Some(arg);
+                continue;
+            }
+        };
+
+        let Some(lint) = args.next() else {
+            return Err(eyre::eyre!("Expected a lint after `{arg}`\n{USAGE}"));
+        };
+        levels.set(lint.parse()?, level);
+    }
+
+    let path = config_path(path)?;
+    let mut tree = ConfigTree::load(&path)?;
+
+    if fix {
+        // Fixes can overlap or make room for other fixes, so lint until nothing changes
+        for _ in 0..MAX_FIX_ROUNDS {
+            let lints = tree.lint(&levels, is_executable);
+            if tree.apply_fixes(&lints) == 0 {
+                break;
+            }
+        }
+
+        tree.save()?
+            .iter()
+            .for_each(|path| println!("Fixed {}", path.display()));
+    }
+
+    let lints = tree.lint(&levels, is_executable);
+
+    for lint in &lints {
+        let file = &tree.files()[lint.diagnostic.file];
+
+        println!(
+            "{}",
+            lint.diagnostic
+                .render(&file.config.to_string(), &file.path.display().to_string())
+        );
+    }
+
+    let denied = lints
+        .iter()
+        .filter(|lint| lint.diagnostic.severity == Severity::Error)
+        .count();
+    let warnings = lints.len() - denied;
+
+    match denied {
+        0 => {
+            println!("{} linted, {warnings} warning(s)", path.display());
+            Ok(())
+        }
+        _ => Err(eyre::eyre!(
+            "{} has {denied} denied lint(s) and {warnings} warning(s)",
+            path.display()
+        )),
+    }
+}
+
+fn fmt_request(args: &[String]) -> eyre::Result<()> {
+    let (flags, paths): (Vec<_>, Vec<_>) = args.iter().partition(|arg| arg.starts_with("--"));
+
+    if let Some(flag) = flags
+        .iter()
+        .find(|flag| !["--check", "--align-binds"].contains(&flag.as_str()))
+    {
+        return Err(eyre::eyre!("Unknown flag `{flag}`\n{USAGE}"));
+    }
+
+    let check = flags.iter().any(|flag| *flag == "--check");
+    let options = FormatOptions {
+        align_binds: flags.iter().any(|flag| *flag == "--align-binds"),
+        ..Default::default()
+    };
+
+    let mut tree = load_tree(paths.first().copied())?;
+
+    for index in 0..tree.files().len() {
+        if let Some(file) = tree.file_mut(index) {
+            file.config = file.config.format(&options);
+        }
+    }
+
+    let unformatted = tree
+        .modified()
+        .map(|file| file.path.display().to_string())
+        .collect_vec();
+
+    match check {
+        true if unformatted.is_empty() => Ok(()),
+        true => {
+            unformatted.iter().for_each(|path| println!("{path}"));
+            Err(eyre::eyre!(
+                "{} file(s) aren't formatted",
+                unformatted.len()
+            ))
+        }
+        false => {
+            tree.save()?
+                .iter()
+                .for_each(|path| println!("Formatted {}", path.display()));
+            Ok(())
+        }
+    }
+}
+
+fn migrate_request(args: &[String]) -> eyre::Result<()> {
+    let (flags, paths): (Vec<_>, Vec<_>) = args.iter().partition(|arg| arg.starts_with("--"));
+
+    if let Some(flag) = flags.iter().find(|flag| flag.as_str() != "--dry-run") {
+        return Err(eyre::eyre!("Unknown flag `{flag}`\n{USAGE}"));
+    }
+
+    let dry_run = !flags.is_empty();
+    let mut tree = load_tree(paths.first().copied())?;
+    let mut count = 0;
+
+    for index in 0..tree.files().len() {
+        let Some(file) = tree.file_mut(index) else {
+            continue;
+        };
+
+        let migrations = file.config.migrate();
+        if migrations.is_empty() {
+            continue;
+        }
+
+        println!("{}", file.path.display());
+        migrations
+            .iter()
+            .for_each(|migration| println!("    {migration}"));
+        count += migrations.len();
+    }
+
+    match (count, dry_run) {
+        (0, _) => println!("Nothing to migrate"),
+        (count, true) => println!("{count} line(s) would be migrated"),
+        (_, false) => {
+            tree.save()?
+                .iter()
+                .for_each(|path| println!("Migrated {}", path.display()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Structured formats of `export` and `import`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentFormat {
+    Json,
+    Toml,
+    Yaml,
+    /// home-manager's `wayland.windowManager.hyprland.settings`
+    Nix,
+}
+
+impl DocumentFormat {
+    fn from_name(name: &str) -> eyre::Result<Self> {
+        match name {
+            "json" => Ok(DocumentFormat::Json),
+            "toml" => Ok(DocumentFormat::Toml),
+            "yaml" | "yml" => Ok(DocumentFormat::Yaml),
+            "nix" => Ok(DocumentFormat::Nix),
+            name => Err(eyre::eyre!(
+                "Unknown format `{name}`, expected json, toml, yaml or nix"
+            )),
+        }
+    }
+}
+
+/// Value of `flag` in `args`, and the other arguments
+fn split_flag<'a>(
+    args: &'a [String],
+    flag: &str,
+) -> eyre::Result<(Option<&'a String>, Vec<&'a String>)> {
+    let mut value = None;
+    let mut rest = vec![];
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            arg if arg == flag => match args.next() {
+                Some(next) => value = Some(next),
+                None => return Err(eyre::eyre!("Expected a format after `{flag}`\n{USAGE}")),
+            },
+            arg if arg.starts_with("--") => {
+                return Err(eyre::eyre!("Unknown flag `{arg}`\n{USAGE}"))
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    Ok((value, rest))
+}
+
+fn export_request(args: &[String]) -> eyre::Result<()> {
+    let (format, paths) = split_flag(args, "--to")?;
+    let format = match format {
+        Some(format) => DocumentFormat::from_name(format)?,
+        None => DocumentFormat::Json,
+    };
+
+    // The file itself rather than the merged tree, `source` lines are exported as they are
+    let path = config_path(paths.first().copied())?;
+    let input = std::fs::read_to_string(&path)
+        .map_err(|err| eyre::eyre!("Couldn't read {}: {err}", path.display()))?;
+    let config = Config::parse_recovering(&input);
+    let document = config.to_document();
+
+    let output = match format {
+        DocumentFormat::Json => serde_json::to_string_pretty(&document)?,
+        DocumentFormat::Toml => toml::to_string_pretty(&document)?,
+        DocumentFormat::Yaml => serde_yaml::to_string(&document)?,
+        DocumentFormat::Nix => config.to_nix()?.to_string(),
+    };
+    println!("{}", output.trim_end());
+
+    Ok(())
+}
+
+fn import_request(args: &[String]) -> eyre::Result<()> {
+    let (format, paths) = split_flag(args, "--from")?;
+    let [path] = paths.as_slice() else {
+        return Err(eyre::eyre!("Expected the file to import\n{USAGE}"));
+    };
+
+    let format = match format {
+        Some(format) => DocumentFormat::from_name(format)?,
+        None => {
+            let extension = std::path::Path::new(path.as_str())
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .unwrap_or_default();
+
+            DocumentFormat::from_name(extension)
+                .map_err(|_| eyre::eyre!("Can't tell the format of {path}, use --from"))?
+        }
+    };
+
+    let input = std::fs::read_to_string(path.as_str())
+        .map_err(|err| eyre::eyre!("Couldn't read {path}: {err}"))?;
+    let config = match format {
+        DocumentFormat::Json => serde_json::from_str::<Document>(&input)?.to_config(),
+        DocumentFormat::Toml => toml::from_str::<Document>(&input)?.to_config(),
+        DocumentFormat::Yaml => serde_yaml::from_str::<Document>(&input)?.to_config(),
+        DocumentFormat::Nix => Config::from_nix(&input)?,
+    };
+
+    print!("{config}");
+
+    Ok(())
+}
+
+fn diff_request(args: &[String]) -> eyre::Result<()> {
+    let (flags, paths): (Vec<_>, Vec<_>) = args.iter().partition(|arg| arg.starts_with("--"));
+    let json = match flags.as_slice() {
+        [] => false,
+        [flag] if flag.as_str() == "--json" => true,
+        _ => return Err(eyre::eyre!("{USAGE}")),
+    };
+
+    let [old, new] = paths.as_slice() else {
+        return Err(eyre::eyre!("Expected two configs to compare\n{USAGE}"));
+    };
+
+    let diff = load_tree(Some(old))?
+        .merged()
+        .diff(&load_tree(Some(new))?.merged())?;
+
+    match json {
+        true => {
+            let entries = diff
+                .entries
+                .iter()
+                .map(|entry| {
+                    serde_json::json!({
+                        "category": entry.category.to_string(),
+                        "kind": entry.kind().to_string(),
+                        "key": entry.key,
+                        "old": entry.old,
+                        "new": entry.new,
+                    })
+                })
+                .collect_vec();
+
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        false => print!("{diff}"),
+    }
+
+    Ok(())
+}
+
+/// Current value of an option in the running Hyprland, `None` if it doesn't know the option
+fn live_option(path: &str) -> Option<LiveOption> {
+    let reply = send_request(&format!("j/getoption {path}")).ok()?;
+    let value = serde_json::from_str::<serde_json::Value>(&reply).ok()?;
+
+    Some(LiveOption {
+        int: value["int"].as_i64().unwrap_or_default(),
+        float: value["float"].as_f64().unwrap_or_default(),
+        str: value["str"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+fn apply_request(args: &[String]) -> eyre::Result<()> {
+    let [path] = args else {
+        return Err(eyre::eyre!("Expected the config to apply\n{USAGE}"));
+    };
+
+    let config = load_config(Some(path))?;
+
+    // `live_option` can't tell a failed request from an unknown option, so the socket is checked
+    // first instead of listing every option as unknown
+    send_request("j/version")?;
+    let plan = config.apply_plan(live_option);
+
+    match plan.changes.is_empty() {
+        true => println!("Nothing to apply, the running Hyprland already matches {path}"),
+        false => {
+            let reply = send_request(&format!("[[BATCH]]{}", plan.batch()))?;
+            let replies = reply
+                .split("\n\n")
+                .map(str::trim)
+                .filter(|reply| !reply.is_empty())
+                .collect_vec();
+
+            println!("Applied:");
+            for (index, change) in plan.changes.iter().enumerate() {
+                match replies.get(index) {
+                    Some(&"ok") => println!("    {change}"),
+                    Some(error) => println!("    {change} (failed: {error})"),
+                    None => println!("    {change} (unknown, Hyprland didn't reply)"),
+                }
+            }
+        }
+    }
+
+    if !plan.skipped.is_empty() {
+        println!("Not applied:");
+        plan.skipped
+            .iter()
+            .for_each(|skipped| println!("    {skipped}"));
+    }
+
+    Ok(())
+}
+
+/// Binds of the running Hyprland grouped by submaps, the global submap first
+fn live_submaps() -> eyre::Result<Vec<Submap>> {
+    let reply = send_request("j/binds")?;
+    let binds = serde_json::from_str::<Vec<serde_json::Value>>(&reply)
+        .map_err(|err| eyre::eyre!("Couldn't read the binds of Hyprland: {err}"))?;
+
+    let mut submaps = vec![Submap::default()];
+
+    for value in binds {
+        let flag = |name: &str| value[name].as_bool().unwrap_or_default();
+        let string = |name: &str| value[name].as_str().unwrap_or_default().to_string();
+        let number = |name: &str| value[name].as_u64().unwrap_or_default() as u32;
+
+        let flags = BindFlags {
+            locked: flag("locked"),
+            release: flag("release"),
+            repeat: flag("repeat"),
+            non_consuming: flag("non_consuming"),
+            mouse: flag("mouse"),
+            ..Default::default()
+        };
+        // Hyprland always reports an `arg`, mouse binds are written without the field
+        let arg = Some(string("arg")).filter(|arg| !(flags.mouse && arg.is_empty()));
+
+        let bind = Bind {
+            flags,
+            mods: ModifierSet::from_mask(number("modmask")),
+            key: match string("key").as_str() {
+                "" => Key::Code(number("keycode")),
+                key => Key::from_value(key)?,
+            },
+            dispatcher: string("dispatcher"),
+            arg,
+        };
+
+        let name = Some(string("submap")).filter(|name| !name.is_empty());
+        match submaps.iter_mut().find(|submap| submap.name == name) {
+            Some(submap) => submap.binds.push(bind),
+            None => submaps.push(Submap {
+                name,
+                binds: vec![bind],
+                ..Default::default()
+            }),
+        }
+    }
+
+    Ok(submaps)
+}
+
+fn dump_request(args: &[String]) -> eyre::Result<()> {
+    let non_default_only = match args {
+        [] => false,
+        [flag] if flag.as_str() == "--non-default" => true,
+        _ => return Err(eyre::eyre!("{USAGE}")),
+    };
+
+    let state = LiveState {
+        monitors: Monitors::get()?
+            .iter()
+            .map(|monitor| MonitorRule::from(&monitor_state(monitor)))
+            .collect(),
+        submaps: live_submaps()?,
+    };
+
+    let config = Config::dump(&state, live_option, &DumpOptions { non_default_only });
+    print!("{config}");
+
+    Ok(())
+}
@@ -1,14 +1,17 @@
-use crate::gui::tabs::AppTab;
+use crate::{gui::tabs::TabState, tabs::AppTab};
 
 #[derive(serde::Serialize, serde::Deserialize)]
 pub(crate) struct App {
     selected_tabs: Vec<AppTab>,
+    #[serde(skip)]
+    state: TabState,
 }
 
 impl Default for App {
     fn default() -> Self {
         Self {
             selected_tabs: vec![AppTab::General],
+            state: TabState::default(),
         }
     }
 }
@@ -40,7 +43,7 @@ impl App {
     fn main_panel(&mut self, ctx: &egui::Context) {
         egui::CentralPanel::default().show(ctx, |ui| {
             self.selected_tabs.iter().for_each(|tab| {
-                tab.window(ui);
+                tab.window(ui, &mut self.state);
             });
         });
     }
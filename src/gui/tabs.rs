@@ -1,39 +1,24 @@
-use hyprland::data::{
-    Clients, Devices, Keyboard, LayerClient, LayerDisplay, Layers, Mouse, Tablet, TabletBelongsTo,
-    TabletType, Workspace,
-};
-use hyprland::{
-    data::{
-        Client, CursorPosition, Monitor, Monitors, Transforms, Version, WorkspaceBasic, Workspaces,
-    },
-    prelude::*,
+use hyprland_config::animation::Bezier;
+
+use crate::{
+    actions::ClientAction,
+    config::ConfigCache,
+    tabs::{AppTab, TabItem},
 };
-use itertools::Itertools;
-
-#[derive(Eq, PartialEq, enum_iterator::Sequence, serde::Serialize, serde::Deserialize)]
-pub(crate) enum AppTab {
-    General,
-    Monitors,
-    Workspaces,
-    Clients,
-    Layers,
-    Devices,
+
+/// State of the tabs that outlives a frame
+pub(crate) struct TabState {
+    config: ConfigCache,
+    /// Curve in the editor of the Animations tab
+    edited_bezier: Bezier,
 }
 
-impl std::fmt::Display for AppTab {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                AppTab::General => "General",
-                AppTab::Monitors => "Monitors",
-                AppTab::Workspaces => "Workspaces",
-                AppTab::Clients => "Clients",
-                AppTab::Layers => "Layers",
-                AppTab::Devices => "Devices",
-            }
-        )
+impl Default for TabState {
+    fn default() -> Self {
+        Self {
+            config: ConfigCache::default(),
+            edited_bezier: Bezier::default_curve(),
+        }
     }
 }
 
@@ -48,392 +33,173 @@ impl AppTab {
         response
     }
 
-    pub fn window(&self, ui: &mut egui::Ui) {
+    pub fn window(&self, ui: &mut egui::Ui, state: &mut TabState) {
         egui::Window::new(self.to_string())
             .resizable(true)
             .drag_bounds(ui.clip_rect())
             .show(ui.ctx(), |ui| {
-                self.data_view(ui);
+                self.data_view(ui, state);
             });
     }
 
-    fn data_view(&self, ui: &mut egui::Ui) {
-        fn client_data_view(
-            ui: &mut egui::Ui,
-            client: &Client,
-            title: impl Into<egui::WidgetText>,
-        ) {
-            ui.collapsing(title, |ui| {
-                let Client {
-                    address,
-                    at,
-                    size,
-                    workspace,
-                    floating,
-                    fullscreen,
-                    fullscreen_mode,
-                    monitor,
-                    class,
-                    title,
-                    pid,
-                    xwayland,
-                    pinned,
-                    grouped,
-                    swallowing,
-                } = client;
-
-                ui.label(format!("PID: {pid}"));
-                ui.label(format!("Address: {address}"));
-                ui.label(format!("Class: {class}"));
-                ui.label(format!("Title: {title}"));
-                ui.label(format!("At: {}x{}", at.0, at.1));
-                ui.label(format!("Size: {}x{}", size.0, size.1));
-                ui.label(format!("Monitor: {monitor}"));
-                workspace_basic_data_view(ui, workspace);
-                ui.label(format!("Floating: {floating}"));
-                ui.label(format!("Fullscreen: {fullscreen} ({fullscreen_mode})"));
-                ui.label(format!("XWayland: {xwayland}"));
-                ui.label(format!("Pinned: {pinned}"));
-
-                grouped.iter().for_each(|client| {
-                    client_data_view(ui, client, "Grouped");
-                });
-
-                if let Some(swallowing) = swallowing {
-                    client_data_view(ui, swallowing, "Swallowing");
-                }
-            });
-        }
-
-        fn general_data_view(ui: &mut egui::Ui) {
-            let mut any_shown = false;
-
-            if let Ok(version) = Version::get() {
-                ui.collapsing("Hyprland Version", |ui| {
-                    let Version {
-                        branch,
-                        commit,
-                        dirty,
-                        commit_message,
-                        flags,
-                    } = version;
-
-                    ui.label(format!("Branch: {branch}"));
-                    ui.label(format!("Commit: {commit}"));
-                    ui.label(format!("Dirty: {dirty}"));
-                    ui.label(format!("Commit Message: {commit_message}"));
-                    ui.label(format!("Flags: [{}]", flags.join(", ")));
-                });
-
-                any_shown = true;
+    fn data_view(&self, ui: &mut egui::Ui, state: &mut TabState) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            if matches!(self, AppTab::Options | AppTab::Animations) && ui.button("Reload").clicked()
+            {
+                state.config.reload();
             }
 
-            if let Ok(Some(active_window)) = Client::get_active() {
-                client_data_view(ui, &active_window, "Active Window");
+            for item in self.items(&mut state.config) {
+                item_view(ui, &item, &mut state.edited_bezier);
+            }
 
-                any_shown = true;
+            if *self == AppTab::Animations {
+                editor_view(ui, &mut state.edited_bezier);
             }
+        });
+    }
+}
 
-            if let Ok(cursor_position) = CursorPosition::get() {
-                ui.label(format!(
-                    "Cursor Position: {}x{}",
-                    cursor_position.x, cursor_position.y
-                ));
+/// Items with details or children are collapsing headers, client items get buttons for the
+/// client actions and bezier items a plot
+fn item_view(ui: &mut egui::Ui, item: &TabItem, edited: &mut Bezier) {
+    if let Some(bezier) = &item.bezier {
+        let mut bezier = bezier.clone();
 
-                any_shown = true;
-            }
+        ui.horizontal(|ui| {
+            bezier_plot(ui, &mut bezier, false);
 
-            if !any_shown {
-                ui.label("Error: No data available");
-            }
-        }
+            ui.vertical(|ui| {
+                ui.label(&item.title);
+                item.details.iter().for_each(|detail| {
+                    ui.label(detail);
+                });
 
-        fn transform_data_view(ui: &mut egui::Ui, transform: &Transforms) {
-            ui.label(format!(
-                "Transform: {}",
-                match transform {
-                    Transforms::Normal => "Normal",
-                    Transforms::Normal90 => "Normal +90",
-                    Transforms::Normal180 => "Normal +180",
-                    Transforms::Normal270 => "Normal +270",
-                    Transforms::Flipped => "Flipped",
-                    Transforms::Flipped90 => "Flipped +90",
-                    Transforms::Flipped180 => "Flipped +180",
-                    Transforms::Flipped270 => "Flipped +270",
+                if ui.button("Edit").clicked() {
+                    *edited = bezier.clone();
                 }
-            ));
-        }
+            });
+        });
 
-        fn workspace_basic_data_view(ui: &mut egui::Ui, workspace_basic: &WorkspaceBasic) {
-            let WorkspaceBasic { id, name } = workspace_basic;
+        return;
+    }
 
-            ui.label(format!("Workspace {name} ({id})"));
-        }
+    if item.details.is_empty() && item.children.is_empty() {
+        ui.label(&item.title);
+        return;
+    }
 
-        fn monitors_data_view(ui: &mut egui::Ui) {
-            match Monitors::get() {
-                Ok(monitors) => {
-                    monitors.iter().for_each(|monitor| {
-                        let Monitor {
-                            id,
-                            name,
-                            description,
-                            width,
-                            height,
-                            refresh_rate,
-                            x,
-                            y,
-                            active_workspace,
-                            reserved,
-                            scale,
-                            transform,
-                            focused,
-                            dpms_status,
-                        } = monitor;
-
-                        ui.collapsing(format!("Monitor {name} ({id})"), |ui| {
-                            ui.label(format!("Description: {description}"));
-                            ui.label(format!("Size: {width}x{height}"));
-                            ui.label(format!("Refresh Rate: {refresh_rate}"));
-                            ui.label(format!("Position: {x}x{y}"));
-                            workspace_basic_data_view(ui, active_workspace);
-                            ui.label(format!("Reserved: {reserved:?}"));
-                            ui.label(format!("Scale: {scale}"));
-                            transform_data_view(ui, transform);
-                            ui.label(format!("Focused: {focused}"));
-                            ui.label(format!("DPMS Status: {dpms_status}"));
-                        });
-                    });
-                }
-                Err(error) => {
-                    ui.label(format!("Error: {error}"));
-                }
-            }
-        }
+    ui.collapsing(&item.title, |ui| {
+        item.details.iter().for_each(|detail| {
+            ui.label(detail);
+        });
 
-        fn workspace_data_view(ui: &mut egui::Ui, workspace: &Workspace) {
-            let Workspace {
-                id,
-                name,
-                monitor,
-                windows,
-                fullscreen,
-                last_window,
-                last_window_title,
-            } = workspace;
-
-            ui.collapsing(format!("Workspace {name} ({id})"), |ui| {
-                ui.label(format!("Monitor: {monitor}"));
-                ui.label(format!("Fullscreen: {fullscreen}"));
-                ui.label(format!(
-                    "Windows: {windows} (Last: {last_window_title} ({last_window}))"
-                ));
+        if let Some(address) = &item.address {
+            ui.horizontal(|ui| {
+                enum_iterator::all::<ClientAction>().for_each(|action| {
+                    if ui.button(action.to_string()).clicked() {
+                        if let Err(err) = action.execute(address) {
+                            log::error!("{action} failed: {err}");
+                        }
+                    }
+                });
             });
         }
 
-        fn workspaces_data_view(ui: &mut egui::Ui) {
-            match Workspaces::get() {
-                Ok(workspaces) => {
-                    workspaces.iter().for_each(|workspace| {
-                        workspace_data_view(ui, workspace);
-                    });
-                }
-                Err(err) => {
-                    ui.label(format!("Error: {err}"));
-                }
-            }
-        }
+        item.children
+            .iter()
+            .for_each(|child| item_view(ui, child, edited));
+    });
+}
 
-        fn clients_data_view(ui: &mut egui::Ui) {
-            match Clients::get() {
-                Ok(clients) => {
-                    clients.iter().for_each(|client| {
-                        client_data_view(ui, client, &client.title);
-                    });
-                }
-                Err(err) => {
-                    ui.label(format!("Error: {err}"));
-                }
-            }
-        }
+/// Editor of the curve picked with the Edit button of a bezier
+fn editor_view(ui: &mut egui::Ui, edited: &mut Bezier) {
+    ui.collapsing("Editor", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Name");
+            ui.text_edit_singleline(&mut edited.name);
+        });
 
-        fn layer_client_data_view(ui: &mut egui::Ui, layer_client: &LayerClient) {
-            let LayerClient {
-                address,
-                x,
-                y,
-                w,
-                h,
-                namespace,
-            } = layer_client;
-
-            ui.collapsing(format!("LayerClient {address}"), |ui| {
-                ui.label(format!("Namespace: {namespace}"));
-                ui.label(format!("Size: {w}x{h}"));
-                ui.label(format!("Position: {x}x{y}"));
-            });
-        }
+        ui.horizontal(|ui| {
+            bezier_plot(ui, edited, true);
 
-        fn layer_data_view(ui: &mut egui::Ui, (layer_name, layer_data): (&String, &LayerDisplay)) {
-            ui.collapsing(format!("Layer {layer_name}"), |ui| {
-                ui.heading("Levels");
-                ui.separator();
-
-                layer_data
-                    .iter()
-                    .sorted_by_key(|(level_name, _)| (*level_name).clone())
-                    .for_each(|(level_name, level_data)| {
-                        ui.collapsing(format!("Level {level_name}"), |ui| {
-                            level_data.iter().for_each(|layer_client| {
-                                layer_client_data_view(ui, layer_client);
-                            });
-                        });
-                    });
+            ui.vertical(|ui| {
+                ui.add(egui::Slider::new(&mut edited.x1, 0.0..=1.0).text("X1"));
+                ui.add(egui::Slider::new(&mut edited.y1, -1.0..=2.0).text("Y1"));
+                ui.add(egui::Slider::new(&mut edited.x2, 0.0..=1.0).text("X2"));
+                ui.add(egui::Slider::new(&mut edited.y2, -1.0..=2.0).text("Y2"));
             });
-        }
-
-        fn layers_data_view(ui: &mut egui::Ui) {
-            match Layers::get() {
-                Ok(layers) => {
-                    layers
-                        .iter()
-                        .sorted_by_key(|(layer_name, _)| (*layer_name).clone())
-                        .for_each(|layer| {
-                            layer_data_view(ui, layer);
-                        });
-                }
-                Err(err) => {
-                    ui.label(format!("Error: {err}"));
-                }
-            }
-        }
-
-        fn devices_data_view(ui: &mut egui::Ui) {
-            match Devices::get() {
-                Ok(devices) => {
-                    let Devices {
-                        mice,
-                        keyboards,
-                        tablets,
-                    } = devices;
-                    let mut any_shown = false;
-
-                    if !mice.is_empty() {
-                        ui.collapsing("Mice", |ui| {
-                            mice.iter().for_each(|Mouse { address, name }| {
-                                ui.label(format!("{address} ({name})"));
-                            });
-                        });
-
-                        any_shown = true;
-                    }
+        });
 
-                    if !keyboards.is_empty() {
-                        ui.collapsing("Keyboards", |ui| {
-                            keyboards.iter().for_each(
-                                |Keyboard {
-                                     address,
-                                     name,
-                                     rules,
-                                     model,
-                                     layout,
-                                     variant,
-                                     options,
-                                     active_keymap,
-                                 }| {
-                                    ui.collapsing(format!("{name} ({model}) ({address})"), |ui| {
-                                        ui.label(format!("Rules: {rules}"));
-                                        ui.label(format!("Layout: {layout}"));
-                                        ui.label(format!("Variant: {variant}"));
-                                        ui.label(format!("Options: {options}"));
-                                        ui.label(format!("Active Keymap: {active_keymap}"));
-                                    });
-                                },
-                            );
-                        });
-
-                        any_shown = true;
-                    }
+        ui.code(edited.to_string());
+    });
+}
 
-                    if !tablets.is_empty() {
-                        ui.collapsing("Tablets", |ui| {
-                            tablets.iter().for_each(
-                                |Tablet {
-                                     address,
-                                     tablet_type,
-                                     belongs_to,
-                                     name,
-                                 }| {
-                                    ui.collapsing(
-                                        format!(
-                                            "{} ({address})",
-                                            match name {
-                                                Some(name) => name,
-                                                None => "Unknown",
-                                            }
-                                        ),
-                                        |ui| {
-                                            ui.label(format!(
-                                                "Type: {}",
-                                                match tablet_type {
-                                                    None => {
-                                                        "Unknown"
-                                                    }
-                                                    Some(tablet_type) => {
-                                                        match tablet_type {
-                                                            TabletType::TabletPad => "Pad",
-                                                            TabletType::TabletTool => "Tool",
-                                                        }
-                                                    }
-                                                }
-                                            ));
-                                            ui.label(format!(
-                                                "Belongs to: {}",
-                                                match belongs_to {
-                                                    None => {
-                                                        "None".to_string()
-                                                    }
-                                                    Some(belongs_to) => {
-                                                        match belongs_to {
-                                                            TabletBelongsTo::TabletPad {
-                                                                name,
-                                                                address,
-                                                            } => {
-                                                                format!("Pad {name} ({address})")
-                                                            }
-                                                            TabletBelongsTo::Address(address) => {
-                                                                address.to_string()
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            ));
-                                        },
-                                    );
-                                },
-                            );
-                        });
-
-                        any_shown = true;
-                    }
+/// Plot of `bezier`, its control points can be dragged when `editable`
+fn bezier_plot(ui: &mut egui::Ui, bezier: &mut Bezier, editable: bool) {
+    const SIZE: f32 = 160.0;
+    const SAMPLES: usize = 64;
+    // Curves can overshoot, so leave some room above and below
+    const MARGIN: f32 = 0.25;
+
+    let (response, painter) = ui.allocate_painter(egui::vec2(SIZE, SIZE), egui::Sense::hover());
+    let rect = response.rect;
+    let scale = 1.0 + 2.0 * MARGIN;
+    let to_screen = |x: f32, y: f32| {
+        egui::pos2(
+            rect.left() + x * rect.width(),
+            rect.bottom() - (y + MARGIN) / scale * rect.height(),
+        )
+    };
+    let from_screen = |pos: egui::Pos2| {
+        let round = |value: f32| (value * 100.0).round() / 100.0;
 
-                    if !any_shown {
-                        ui.label("No devices found");
-                    }
-                }
-                Err(err) => {
-                    ui.label(format!("Error: {err}"));
-                }
+        (
+            round(((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0)),
+            round((rect.bottom() - pos.y) / rect.height() * scale - MARGIN),
+        )
+    };
+
+    let weak = egui::Stroke::new(1.0, ui.visuals().weak_text_color());
+    painter.rect_stroke(rect, 0.0, weak);
+    painter.line_segment([to_screen(0.0, 0.0), to_screen(1.0, 0.0)], weak);
+    painter.line_segment([to_screen(0.0, 1.0), to_screen(1.0, 1.0)], weak);
+
+    let start = to_screen(0.0, 0.0);
+    let end = to_screen(1.0, 1.0);
+    let handles = [
+        (&mut bezier.x1, &mut bezier.y1, start),
+        (&mut bezier.x2, &mut bezier.y2, end),
+    ];
+
+    for (index, (x, y, anchor)) in handles.into_iter().enumerate() {
+        let mut center = to_screen(*x, *y);
+
+        if editable {
+            let handle = ui.interact(
+                egui::Rect::from_center_size(center, egui::vec2(12.0, 12.0)),
+                response.id.with(index),
+                egui::Sense::drag(),
+            );
+
+            if let Some(pointer) = handle.interact_pointer_pos().filter(|_| handle.dragged()) {
+                (*x, *y) = from_screen(pointer);
+                center = to_screen(*x, *y);
             }
         }
 
-        egui::ScrollArea::vertical().show(ui, |ui| match self {
-            AppTab::General => general_data_view(ui),
-            AppTab::Monitors => monitors_data_view(ui),
-            AppTab::Workspaces => workspaces_data_view(ui),
-            AppTab::Clients => clients_data_view(ui),
-            AppTab::Layers => layers_data_view(ui),
-            AppTab::Devices => devices_data_view(ui),
-        });
+        painter.line_segment([anchor, center], weak);
+        painter.circle_filled(center, 4.0, ui.visuals().selection.bg_fill);
     }
+
+    let points = (0..=SAMPLES)
+        .map(|step| {
+            let (x, y) = bezier.point(step as f32 / SAMPLES as f32);
+            to_screen(x, y)
+        })
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(2.0, ui.visuals().text_color()),
+    ));
 }
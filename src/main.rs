@@ -1,7 +1,12 @@
 #![feature(box_syntax)]
 
+mod actions;
 mod cli;
+mod config;
 mod gui;
+mod rules;
+mod tabs;
+mod tui;
 
 use color_eyre::eyre;
 use itertools::Itertools;
@@ -23,6 +28,8 @@ fn main() -> eyre::Result<()> {
 
     if ["-g", "--gui"].contains(&args[0].as_str()) {
         gui::execute()
+    } else if args[0] == "--tui" {
+        tui::execute()
     } else {
         cli::execute(args)
     }
@@ -0,0 +1,87 @@
+use color_eyre::eyre;
+use hyprland::{
+    data::{Client, Clients, Layers},
+    prelude::*,
+};
+use hyprland_config::rule::{matching_layer_rules, matching_window_rules, WindowState};
+use itertools::Itertools;
+
+use crate::config::load_config;
+
+pub(crate) const USAGE: &str = r"
+usage hyprr rules [command] [(opt) args]
+
+commands:
+    test [path] -> show which window and layer rules match each open window and layer surface
+";
+
+/// `hyprr rules ...`, `args` start after `rules`
+pub fn execute(args: &[String]) -> eyre::Result<()> {
+    let Some(command) = args.first() else {
+        return Err(eyre::eyre!("{USAGE}"));
+    };
+
+    match command.as_str() {
+        "test" => test_request(&args[1..]),
+        "--help" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        _ => Err(eyre::eyre!("{USAGE}")),
+    }
+}
+
+fn window_state(client: &Client) -> WindowState {
+    WindowState {
+        class: client.class.clone(),
+        title: client.title.clone(),
+        xwayland: client.xwayland,
+        floating: client.floating,
+        fullscreen: client.fullscreen,
+        pinned: client.pinned,
+        workspace_id: client.workspace.id,
+        workspace_name: client.workspace.name.clone(),
+    }
+}
+
+fn test_request(args: &[String]) -> eyre::Result<()> {
+    let config = load_config(args.first())?;
+    let window_rules = config.window_rules()?;
+    let layer_rules = config.layer_rules()?;
+
+    for client in Clients::get()?.iter() {
+        println!("Window {} ({}):", client.title, client.class);
+
+        let rules = matching_window_rules(&window_rules, &window_state(client));
+
+        match rules.is_empty() {
+            true => println!("    no matching rules"),
+            false => rules.iter().for_each(|rule| println!("    {rule}")),
+        }
+    }
+
+    let namespaces = Layers::get()?
+        .iter()
+        .flat_map(|(_, layer_data)| {
+            layer_data
+                .iter()
+                .flat_map(|(_, level_data)| level_data.iter().map(|layer| layer.namespace.clone()))
+                .collect_vec()
+        })
+        .unique()
+        .sorted()
+        .collect_vec();
+
+    for namespace in namespaces {
+        println!("Layer {namespace}:");
+
+        let rules = matching_layer_rules(&layer_rules, &namespace);
+
+        match rules.is_empty() {
+            true => println!("    no matching rules"),
+            false => rules.iter().for_each(|rule| println!("    {rule}")),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,527 @@
+use hyprland::{
+    data::{
+        Client, Clients, CursorPosition, Devices, Keyboard, LayerClient, Layers, Monitor, Monitors,
+        Mouse, Tablet, TabletBelongsTo, TabletType, Transforms, Version, Workspace, WorkspaceBasic,
+        Workspaces,
+    },
+    prelude::*,
+    shared::Address,
+};
+use hyprland_config::animation::{effective_animation, AnimationName, Bezier};
+use itertools::Itertools;
+
+use crate::config::{self, ConfigCache};
+
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, enum_iterator::Sequence, serde::Serialize, serde::Deserialize,
+)]
+pub(crate) enum AppTab {
+    General,
+    Monitors,
+    Workspaces,
+    Clients,
+    Layers,
+    Devices,
+    Options,
+    Animations,
+}
+
+impl std::fmt::Display for AppTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AppTab::General => "General",
+                AppTab::Monitors => "Monitors",
+                AppTab::Workspaces => "Workspaces",
+                AppTab::Clients => "Clients",
+                AppTab::Layers => "Layers",
+                AppTab::Devices => "Devices",
+                AppTab::Options => "Options",
+                AppTab::Animations => "Animations",
+            }
+        )
+    }
+}
+
+/// Entry of a tab, shared by the GUI, which shows items as collapsing headers, and the TUI,
+/// which lists the top-level items and shows the details of the selected one
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TabItem {
+    pub title: String,
+    pub details: Vec<String>,
+    pub children: Vec<TabItem>,
+    /// Set for client entries, so that client actions can be performed on them
+    pub address: Option<Address>,
+    /// Set for bezier entries, so that the GUI can plot and edit them
+    pub bezier: Option<Bezier>,
+}
+
+impl TabItem {
+    fn new(title: impl Into<String>, details: Vec<String>) -> Self {
+        Self {
+            title: title.into(),
+            details,
+            ..Default::default()
+        }
+    }
+
+    fn group(title: impl Into<String>, children: Vec<TabItem>) -> Self {
+        Self {
+            children,
+            ..Self::new(title, vec![])
+        }
+    }
+
+    fn error(error: impl std::fmt::Display) -> Self {
+        Self::new(format!("Error: {error}"), vec![])
+    }
+}
+
+impl AppTab {
+    /// Current entries of the tab. The Options and Animations tabs read the config from `cache`
+    pub fn items(&self, cache: &mut ConfigCache) -> Vec<TabItem> {
+        fn client_item(client: &Client, title: impl Into<String>) -> TabItem {
+            let Client {
+                address,
+                at,
+                size,
+                workspace,
+                floating,
+                fullscreen,
+                fullscreen_mode,
+                monitor,
+                class,
+                title: client_title,
+                pid,
+                xwayland,
+                pinned,
+                grouped,
+                swallowing,
+            } = client;
+
+            let children = grouped
+                .iter()
+                .map(|client| client_item(client, "Grouped"))
+                .chain(
+                    swallowing
+                        .iter()
+                        .map(|client| client_item(client, "Swallowing")),
+                )
+                .collect();
+
+            TabItem {
+                children,
+                address: Some(address.clone()),
+                ..TabItem::new(
+                    title,
+                    vec![
+                        format!("PID: {pid}"),
+                        format!("Address: {address}"),
+                        format!("Class: {class}"),
+                        format!("Title: {client_title}"),
+                        format!("At: {}x{}", at.0, at.1),
+                        format!("Size: {}x{}", size.0, size.1),
+                        format!("Monitor: {monitor}"),
+                        workspace_basic_details(workspace),
+                        format!("Floating: {floating}"),
+                        format!("Fullscreen: {fullscreen} ({fullscreen_mode})"),
+                        format!("XWayland: {xwayland}"),
+                        format!("Pinned: {pinned}"),
+                    ],
+                )
+            }
+        }
+
+        fn general_items() -> Vec<TabItem> {
+            let mut items = vec![];
+
+            if let Ok(version) = Version::get() {
+                let Version {
+                    branch,
+                    commit,
+                    dirty,
+                    commit_message,
+                    flags,
+                } = version;
+
+                items.push(TabItem::new(
+                    "Hyprland Version",
+                    vec![
+                        format!("Branch: {branch}"),
+                        format!("Commit: {commit}"),
+                        format!("Dirty: {dirty}"),
+                        format!("Commit Message: {commit_message}"),
+                        format!("Flags: [{}]", flags.join(", ")),
+                    ],
+                ));
+            }
+
+            if let Ok(Some(active_window)) = Client::get_active() {
+                items.push(client_item(&active_window, "Active Window"));
+            }
+
+            if let Ok(cursor_position) = CursorPosition::get() {
+                items.push(TabItem::new(
+                    format!(
+                        "Cursor Position: {}x{}",
+                        cursor_position.x, cursor_position.y
+                    ),
+                    vec![],
+                ));
+            }
+
+            if items.is_empty() {
+                items.push(TabItem::error("No data available"));
+            }
+
+            items
+        }
+
+        fn transform_details(transform: &Transforms) -> String {
+            format!(
+                "Transform: {}",
+                match transform {
+                    Transforms::Normal => "Normal",
+                    Transforms::Normal90 => "Normal +90",
+                    Transforms::Normal180 => "Normal +180",
+                    Transforms::Normal270 => "Normal +270",
+                    Transforms::Flipped => "Flipped",
+                    Transforms::Flipped90 => "Flipped +90",
+                    Transforms::Flipped180 => "Flipped +180",
+                    Transforms::Flipped270 => "Flipped +270",
+                }
+            )
+        }
+
+        fn workspace_basic_details(workspace_basic: &WorkspaceBasic) -> String {
+            let WorkspaceBasic { id, name } = workspace_basic;
+
+            format!("Workspace {name} ({id})")
+        }
+
+        fn monitors_items() -> Vec<TabItem> {
+            match Monitors::get() {
+                Ok(monitors) => monitors
+                    .iter()
+                    .map(|monitor| {
+                        let Monitor {
+                            id,
+                            name,
+                            description,
+                            width,
+                            height,
+                            refresh_rate,
+                            x,
+                            y,
+                            active_workspace,
+                            reserved,
+                            scale,
+                            transform,
+                            focused,
+                            dpms_status,
+                        } = monitor;
+
+                        TabItem::new(
+                            format!("Monitor {name} ({id})"),
+                            vec![
+                                format!("Description: {description}"),
+                                format!("Size: {width}x{height}"),
+                                format!("Refresh Rate: {refresh_rate}"),
+                                format!("Position: {x}x{y}"),
+                                workspace_basic_details(active_workspace),
+                                format!("Reserved: {reserved:?}"),
+                                format!("Scale: {scale}"),
+                                transform_details(transform),
+                                format!("Focused: {focused}"),
+                                format!("DPMS Status: {dpms_status}"),
+                            ],
+                        )
+                    })
+                    .collect(),
+                Err(err) => vec![TabItem::error(err)],
+            }
+        }
+
+        fn workspace_item(workspace: &Workspace) -> TabItem {
+            let Workspace {
+                id,
+                name,
+                monitor,
+                windows,
+                fullscreen,
+                last_window,
+                last_window_title,
+            } = workspace;
+
+            TabItem::new(
+                format!("Workspace {name} ({id})"),
+                vec![
+                    format!("Monitor: {monitor}"),
+                    format!("Fullscreen: {fullscreen}"),
+                    format!("Windows: {windows} (Last: {last_window_title} ({last_window}))"),
+                ],
+            )
+        }
+
+        fn workspaces_items() -> Vec<TabItem> {
+            match Workspaces::get() {
+                Ok(workspaces) => workspaces.iter().map(workspace_item).collect(),
+                Err(err) => vec![TabItem::error(err)],
+            }
+        }
+
+        fn clients_items() -> Vec<TabItem> {
+            match Clients::get() {
+                Ok(clients) => clients
+                    .iter()
+                    .map(|client| client_item(client, &client.title))
+                    .collect(),
+                Err(err) => vec![TabItem::error(err)],
+            }
+        }
+
+        fn layer_client_item(layer_client: &LayerClient) -> TabItem {
+            let LayerClient {
+                address,
+                x,
+                y,
+                w,
+                h,
+                namespace,
+            } = layer_client;
+
+            TabItem::new(
+                format!("LayerClient {address}"),
+                vec![
+                    format!("Namespace: {namespace}"),
+                    format!("Size: {w}x{h}"),
+                    format!("Position: {x}x{y}"),
+                ],
+            )
+        }
+
+        fn layers_items() -> Vec<TabItem> {
+            match Layers::get() {
+                Ok(layers) => layers
+                    .iter()
+                    .sorted_by_key(|(layer_name, _)| (*layer_name).clone())
+                    .map(|(layer_name, layer_data)| {
+                        let levels = layer_data
+                            .iter()
+                            .sorted_by_key(|(level_name, _)| (*level_name).clone())
+                            .map(|(level_name, level_data)| {
+                                TabItem::group(
+                                    format!("Level {level_name}"),
+                                    level_data.iter().map(layer_client_item).collect(),
+                                )
+                            })
+                            .collect();
+
+                        TabItem::group(format!("Layer {layer_name}"), levels)
+                    })
+                    .collect(),
+                Err(err) => vec![TabItem::error(err)],
+            }
+        }
+
+        fn devices_items() -> Vec<TabItem> {
+            match Devices::get() {
+                Ok(devices) => {
+                    let Devices {
+                        mice,
+                        keyboards,
+                        tablets,
+                    } = devices;
+                    let mut items = vec![];
+
+                    if !mice.is_empty() {
+                        items.push(TabItem::new(
+                            "Mice",
+                            mice.iter()
+                                .map(|Mouse { address, name }| format!("{address} ({name})"))
+                                .collect(),
+                        ));
+                    }
+
+                    if !keyboards.is_empty() {
+                        let keyboards = keyboards.iter().map(
+                            |Keyboard {
+                                 address,
+                                 name,
+                                 rules,
+                                 model,
+                                 layout,
+                                 variant,
+                                 options,
+                                 active_keymap,
+                             }| {
+                                TabItem::new(
+                                    format!("{name} ({model}) ({address})"),
+                                    vec![
+                                        format!("Rules: {rules}"),
+                                        format!("Layout: {layout}"),
+                                        format!("Variant: {variant}"),
+                                        format!("Options: {options}"),
+                                        format!("Active Keymap: {active_keymap}"),
+                                    ],
+                                )
+                            },
+                        );
+
+                        items.push(TabItem::group("Keyboards", keyboards.collect()));
+                    }
+
+                    if !tablets.is_empty() {
+                        let tablets = tablets.iter().map(
+                            |Tablet {
+                                 address,
+                                 tablet_type,
+                                 belongs_to,
+                                 name,
+                             }| {
+                                TabItem::new(
+                                    format!(
+                                        "{} ({address})",
+                                        match name {
+                                            Some(name) => name,
+                                            None => "Unknown",
+                                        }
+                                    ),
+                                    vec![
+                                        format!(
+                                            "Type: {}",
+                                            match tablet_type {
+                                                None => "Unknown",
+                                                Some(TabletType::TabletPad) => "Pad",
+                                                Some(TabletType::TabletTool) => "Tool",
+                                            }
+                                        ),
+                                        format!(
+                                            "Belongs to: {}",
+                                            match belongs_to {
+                                                None => "None".to_string(),
+                                                Some(TabletBelongsTo::TabletPad {
+                                                    name,
+                                                    address,
+                                                }) => format!("Pad {name} ({address})"),
+                                                Some(TabletBelongsTo::Address(address)) => {
+                                                    address.to_string()
+                                                }
+                                            }
+                                        ),
+                                    ],
+                                )
+                            },
+                        );
+
+                        items.push(TabItem::group("Tablets", tablets.collect()));
+                    }
+
+                    if items.is_empty() {
+                        items.push(TabItem::new("No devices found", vec![]));
+                    }
+
+                    items
+                }
+                Err(err) => vec![TabItem::error(err)],
+            }
+        }
+
+        fn options_items(cache: &mut ConfigCache) -> Vec<TabItem> {
+            let current = cache.get().config.as_ref().ok();
+
+            config::options_by_section("")
+                .into_iter()
+                .map(|(section, options)| {
+                    let options = options
+                        .into_iter()
+                        .map(|option| {
+                            let name = option.path.rsplit(':').next().unwrap_or(&option.path);
+                            let value = current
+                                .and_then(|config| config.value(&option.path))
+                                .map(|value| format!("Current: {value}"));
+
+                            TabItem::new(
+                                name,
+                                [option.description.clone(), config::option_summary(option)]
+                                    .into_iter()
+                                    .chain(value)
+                                    .collect(),
+                            )
+                        })
+                        .collect();
+
+                    TabItem::group(section, options)
+                })
+                .collect()
+        }
+
+        fn animations_items(cache: &mut ConfigCache) -> Vec<TabItem> {
+            let loaded = cache.get();
+            if let Err(err) = &loaded.config {
+                return vec![TabItem::error(err)];
+            }
+
+            let beziers = match &loaded.beziers {
+                Ok(beziers) => std::iter::once(Bezier::default_curve())
+                    .chain(beziers.iter().cloned())
+                    .map(|bezier| TabItem {
+                        details: vec![format!(
+                            "{}, {}, {}, {}",
+                            bezier.x1, bezier.y1, bezier.x2, bezier.y2
+                        )],
+                        title: bezier.name.clone(),
+                        bezier: Some(bezier),
+                        ..Default::default()
+                    })
+                    .collect(),
+                Err(err) => vec![TabItem::error(err)],
+            };
+
+            let animations = match &loaded.animations {
+                Ok(animations) => AnimationName::ALL
+                    .iter()
+                    .map(|name| {
+                        let label = match effective_animation(animations, *name) {
+                            Some(animation) => {
+                                let inherited = match animation.name == *name {
+                                    true => String::new(),
+                                    false => format!(" (from {})", animation.name),
+                                };
+
+                                match (animation.duration_ms(), &animation.curve) {
+                                    (Some(duration), Some(curve)) => {
+                                        format!("{name}: {duration}ms, {curve}{inherited}")
+                                    }
+                                    _ => format!("{name}: off{inherited}"),
+                                }
+                            }
+                            None => format!("{name}: Hyprland default"),
+                        };
+
+                        TabItem::new(label, vec![])
+                    })
+                    .collect(),
+                Err(err) => vec![TabItem::error(err)],
+            };
+
+            vec![
+                TabItem::group("Beziers", beziers),
+                TabItem::group("Animations", animations),
+            ]
+        }
+
+        match self {
+            AppTab::General => general_items(),
+            AppTab::Monitors => monitors_items(),
+            AppTab::Workspaces => workspaces_items(),
+            AppTab::Clients => clients_items(),
+            AppTab::Layers => layers_items(),
+            AppTab::Devices => devices_items(),
+            AppTab::Options => options_items(cache),
+            AppTab::Animations => animations_items(cache),
+        }
+    }
+}
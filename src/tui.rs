@@ -0,0 +1,73 @@
+mod app;
+
+use std::{io, time::Duration};
+
+use color_eyre::eyre;
+use crossterm::{
+    cursor::Show,
+    event::{self, Event, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+
+use crate::tui::app::App;
+
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+pub fn execute() -> eyre::Result<()> {
+    // The panic message would be lost on the alternate screen, so the terminal is restored
+    // before it is printed
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        hook(info);
+    }));
+
+    let _guard = TerminalGuard::new()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    run(&mut terminal)
+}
+
+/// Raw mode and the alternate screen, left when dropped, also on errors and panics
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> eyre::Result<Self> {
+        enable_raw_mode()?;
+        let guard = Self;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+}
+
+fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> eyre::Result<()> {
+    let mut app = App::default();
+
+    while !app.should_quit() {
+        terminal.draw(|f| app.draw(f))?;
+
+        if event::poll(TICK_RATE)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.on_key(key);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
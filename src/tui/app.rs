@@ -0,0 +1,256 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use hyprland::shared::Address;
+use itertools::Itertools;
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Spans,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap},
+    Frame,
+};
+
+use crate::{
+    actions::ClientAction,
+    config::ConfigCache,
+    tabs::{AppTab, TabItem},
+};
+
+pub(crate) struct App {
+    tab: AppTab,
+    items: Vec<TabItem>,
+    list_state: ListState,
+    status: String,
+    /// Client waiting for `y` to be closed
+    confirm_close: Option<Address>,
+    quit: bool,
+    config: ConfigCache,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        let mut app = Self {
+            tab: AppTab::General,
+            items: vec![],
+            list_state: ListState::default(),
+            status: String::new(),
+            confirm_close: None,
+            quit: false,
+            config: ConfigCache::default(),
+        };
+        app.refresh();
+
+        app
+    }
+}
+
+impl App {
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    pub fn on_key(&mut self, key: KeyEvent) {
+        if let Some(address) = self.confirm_close.take() {
+            match key.code {
+                KeyCode::Char('y') => self.execute(ClientAction::Close, &address),
+                _ => self.status = "Close cancelled".to_string(),
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => {
+                self.select_tab(enum_iterator::next_cycle(&self.tab).unwrap())
+            }
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+                self.select_tab(enum_iterator::previous_cycle(&self.tab).unwrap())
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                if let Some(tab) = enum_iterator::all::<AppTab>().nth(c as usize - '1' as usize) {
+                    self.select_tab(tab);
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Char('r') => {
+                self.config.reload();
+                self.refresh();
+                self.status = "Refreshed".to_string();
+            }
+            KeyCode::Enter => self.run_action(ClientAction::Focus),
+            KeyCode::Char(c) => {
+                if let Some(action) = enum_iterator::all::<ClientAction>().find(|a| a.key() == c) {
+                    self.run_action(action);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(f.size());
+
+        self.draw_header(f, chunks[0]);
+        self.draw_main_panel(f, chunks[1]);
+        self.draw_footer(f, chunks[2]);
+    }
+
+    fn draw_header<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let titles = enum_iterator::all::<AppTab>()
+            .map(|tab| Spans::from(tab.to_string()))
+            .collect_vec();
+        let selected = enum_iterator::all::<AppTab>()
+            .position(|tab| tab == self.tab)
+            .unwrap_or_default();
+
+        let tabs = Tabs::new(titles)
+            .block(Block::default().borders(Borders::ALL).title("Hyprr"))
+            .select(selected)
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        f.render_widget(tabs, area);
+    }
+
+    fn draw_main_panel<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .split(area);
+
+        let list = List::new(
+            self.items
+                .iter()
+                .map(|item| ListItem::new(item.title.clone()))
+                .collect_vec(),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(self.tab.to_string()),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+
+        f.render_stateful_widget(list, chunks[0], &mut self.list_state);
+
+        let details = self
+            .selected_item()
+            .map(|item| {
+                detail_lines(item, 0)
+                    .into_iter()
+                    .map(Spans::from)
+                    .collect_vec()
+            })
+            .unwrap_or_default();
+
+        let paragraph = Paragraph::new(details)
+            .block(Block::default().borders(Borders::ALL).title("Details"))
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, chunks[1]);
+    }
+
+    fn draw_footer<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let actions = enum_iterator::all::<ClientAction>()
+            .map(|action| format!("{}: {action}", action.key()))
+            .join(" | ");
+
+        let help = match self.status.is_empty() {
+            true => format!("q: Quit | Tab/1-7: Switch tab | j/k: Move | r: Refresh | {actions}"),
+            false => self.status.clone(),
+        };
+
+        f.render_widget(Paragraph::new(help), area);
+    }
+
+    fn selected_item(&self) -> Option<&TabItem> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.items.get(index))
+    }
+
+    fn select_tab(&mut self, tab: AppTab) {
+        self.tab = tab;
+        self.list_state.select(None);
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        self.items = self.tab.items(&mut self.config);
+
+        let selected = match self.items.is_empty() {
+            true => None,
+            false => Some(
+                self.list_state
+                    .selected()
+                    .unwrap_or_default()
+                    .min(self.items.len() - 1),
+            ),
+        };
+        self.list_state.select(selected);
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let len = self.items.len() as isize;
+        let current = self.list_state.selected().unwrap_or_default() as isize;
+
+        self.list_state
+            .select(Some((current + offset).rem_euclid(len) as usize));
+        self.status.clear();
+    }
+
+    fn run_action(&mut self, action: ClientAction) {
+        let Some(address) = self.selected_item().and_then(|item| item.address.clone()) else {
+            self.status = format!("{action}: no client selected");
+            return;
+        };
+
+        if action == ClientAction::Close {
+            self.status = format!("Close {address}? y/n");
+            self.confirm_close = Some(address);
+            return;
+        }
+
+        self.execute(action, &address);
+    }
+
+    fn execute(&mut self, action: ClientAction, address: &Address) {
+        self.status = match action.execute(address) {
+            Ok(()) => format!("{action}: {address}"),
+            Err(err) => format!("{action} failed: {err}"),
+        };
+
+        self.refresh();
+    }
+}
+
+/// Details of `item` followed by its children, each indented below its title
+fn detail_lines(item: &TabItem, indent: usize) -> Vec<String> {
+    let pad = " ".repeat(indent);
+    let children = item.children.iter().flat_map(|child| {
+        std::iter::once(format!("{pad}{}", child.title)).chain(detail_lines(child, indent + 2))
+    });
+
+    item.details
+        .iter()
+        .map(|detail| format!("{pad}{detail}"))
+        .chain(children)
+        .collect()
+}